@@ -0,0 +1,58 @@
+use crate::error::{Error, Kind};
+use crate::{collect_log, interrupt, pass, throw, Proton, ProtonEvent};
+use std::path::PathBuf;
+use std::process::ExitStatus;
+
+impl Proton {
+    /// Runs the launch on tokio, resolving to the program's exit status once it exits, then
+    /// moves the Proton log into place if one was asked for
+    ///
+    /// As with `Proton::spawn`, the program leads a process group of its own, and save sync
+    /// hooks, retries, waiting modes, time limits and resource monitoring do not apply. Dropping
+    /// the future before it resolves cancels the launch, sending `SIGTERM` to the group.
+    ///
+    /// # Errors
+    ///
+    /// Will fail on:
+    /// * Creating a Proton compat env directory fails
+    /// * The prefix architecture does not fit the launch
+    /// * Spawning or waiting for Proton fails
+    pub async fn spawn_async(mut self) -> Result<ExitStatus, Error> {
+        let (command, log): (std::process::Command, Option<(PathBuf, PathBuf)>) =
+            self.detached()?;
+
+        let mut child: tokio::process::Child = match tokio::process::Command::from(command).spawn()
+        {
+            Ok(c) => c,
+            Err(e) => throw!(Kind::ProtonSpawn, "{}\nDebug:\n{:#?}", e, self),
+        };
+        self.record_use();
+
+        let mut cancel: Cancel = Cancel(child.id());
+        let status: ExitStatus = match child.wait().await {
+            Ok(status) => status,
+            Err(e) => throw!(Kind::ProtonWait, "'{:?}': {}", child.id(), e),
+        };
+        cancel.0 = None;
+
+        if let Some((dir, file)) = &log {
+            collect_log(dir, file)?;
+            ProtonEvent::LogWritten(file.clone()).emit();
+        }
+
+        pass!(status)
+    }
+}
+
+/// Terminates the process group a launch leads when dropped before the launch exits
+struct Cancel(Option<u32>);
+
+impl Drop for Cancel {
+    fn drop(&mut self) {
+        use signal_hook::consts::SIGTERM;
+
+        if let Some(group) = self.0 {
+            interrupt::send(SIGTERM, &format!("-{}", group));
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use crate::error::{Error, Kind};
+use crate::{pass, throw, Config, Proton, Version};
+use std::path::{Path, PathBuf};
+
+/// Builds a `Proton` from named settings, checking them before anything is launched
+///
+/// The Proton path, the program and the compat data directory are required. The version is
+/// read from the Proton directory's name unless given, and Steam is looked for in
+/// `~/.steam/steam` unless set.
+#[derive(Debug, Clone, Default)]
+pub struct ProtonBuilder {
+    version: Option<Version>,
+    path: Option<PathBuf>,
+    program: Option<PathBuf>,
+    args: Vec<String>,
+    log: bool,
+    compat: Option<PathBuf>,
+    steam: Option<PathBuf>,
+}
+
+impl ProtonBuilder {
+    #[must_use]
+    /// Creates a new, empty `ProtonBuilder`
+    pub fn new() -> ProtonBuilder {
+        ProtonBuilder::default()
+    }
+
+    #[must_use]
+    /// Takes the compat data directory and the Steam install from `config`
+    pub fn config(self, config: &Config) -> ProtonBuilder {
+        self.compat(config.data()).steam(config.steam())
+    }
+
+    #[must_use]
+    /// Sets the Proton version, used to name the compat data directory and checked against the
+    /// prefix's last one
+    pub fn version(mut self, version: Version) -> ProtonBuilder {
+        self.version = Some(version);
+        self
+    }
+
+    #[must_use]
+    /// Sets the Proton directory, or the path of its `proton` script
+    pub fn path(mut self, path: PathBuf) -> ProtonBuilder {
+        self.path = Some(path);
+        self
+    }
+
+    #[must_use]
+    /// Sets the program to run
+    pub fn program(mut self, program: PathBuf) -> ProtonBuilder {
+        self.program = Some(program);
+        self
+    }
+
+    #[must_use]
+    /// Sets the arguments passed to the program
+    pub fn args(mut self, args: Vec<String>) -> ProtonBuilder {
+        self.args = args;
+        self
+    }
+
+    #[must_use]
+    /// Writes a Proton log of the launch, as `Proton::log` does
+    pub fn log(mut self, log: bool) -> ProtonBuilder {
+        self.log = log;
+        self
+    }
+
+    #[must_use]
+    /// Sets the directory per version compat data directories are made in, the config's `data`
+    pub fn compat(mut self, compat: PathBuf) -> ProtonBuilder {
+        self.compat = Some(compat);
+        self
+    }
+
+    #[must_use]
+    /// Sets the directory Steam is installed in
+    pub fn steam(mut self, steam: PathBuf) -> ProtonBuilder {
+        self.steam = Some(steam);
+        self
+    }
+
+    /// Checks the settings and builds the `Proton`
+    ///
+    /// # Errors
+    ///
+    /// Will fail if...
+    /// * The Proton path, program or compat data directory is not set
+    /// * The Proton path does not exist
+    /// * The compat data directory is not a directory
+    /// * Steam is not set and `HOME` is missing
+    pub fn build(self) -> Result<Proton, Error> {
+        let Some(path) = self.path else {
+            throw!(Kind::ArgumentMissing, "Proton path");
+        };
+
+        let Some(program) = self.program else {
+            throw!(Kind::ArgumentMissing, "program");
+        };
+
+        let Some(compat) = self.compat else {
+            throw!(Kind::ArgumentMissing, "compat data directory");
+        };
+
+        if !path.exists() {
+            throw!(
+                Kind::ProtonMissing,
+                "'{}' does not exist",
+                path.to_string_lossy()
+            );
+        }
+
+        if !compat.is_dir() {
+            throw!(
+                Kind::ProtonDir,
+                "compat data directory '{}' is not a directory",
+                compat.to_string_lossy()
+            );
+        }
+
+        let steam: PathBuf = match self.steam.or_else(default_steam) {
+            Some(steam) => steam,
+            None => throw!(Kind::Environment, "HOME missing, set the Steam directory"),
+        };
+
+        // A script path is named after the directory holding it
+        let dir: &Path = if path.is_file() {
+            path.parent().unwrap_or(&path)
+        } else {
+            &path
+        };
+        let version: Version = self.version.unwrap_or_else(|| Version::from_custom(dir));
+
+        pass!(Proton::create(
+            version, path, program, self.args, self.log, compat, steam,
+        ))
+    }
+}
+
+/// Returns where Steam is installed for the user by default
+fn default_steam() -> Option<PathBuf> {
+    std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".steam/steam"))
+}
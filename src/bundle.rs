@@ -0,0 +1,253 @@
+use crate::error::{Error, Kind};
+use crate::registry::{RegFile, Registry};
+use crate::util::{cache_dir, is_plain_name, run, timestamp};
+use crate::{pass, throw, Config, Game, Prefix};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::SystemTime;
+
+/// Name of the manifest stored at the root of a bundle
+static MANIFEST: &str = "bundle.toml";
+
+/// Contents of a bundle's manifest
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct Manifest {
+    name: String,
+    prefix: Option<String>,
+    game: Game,
+    #[serde(default)]
+    overrides: BTreeMap<String, String>,
+}
+
+/// Bundle type to move a program's complete setup between machines
+///
+/// A bundle is a compressed tar archive holding a `bundle.toml` manifest with the
+/// program's `[games.NAME]` settings and DLL overrides, and optionally its prefix.
+#[derive(Debug, Clone)]
+pub struct Bundle {
+    manifest: Manifest,
+}
+
+impl Bundle {
+    /// Exports the settings of the program `name`, and optionally its prefix, to `out`
+    ///
+    /// The compression is chosen by `tar` from the extension of `out`, such as `.tar.zst`.
+    ///
+    /// # Errors
+    ///
+    /// Will fail if...
+    /// * The program has no settings in the config
+    /// * The prefix is requested but does not exist
+    /// * Writing the manifest or running `tar` fails
+    pub fn export(config: &Config, name: &str, with_prefix: bool, out: &Path) -> Result<(), Error> {
+        let game: Game = match config.game(name) {
+            Some(g) => g.clone(),
+            None => throw!(Kind::GameMissing, "'{}'", name),
+        };
+
+        let prefix: Option<Prefix> =
+            Prefix::find(&config.data(), game.prefix().unwrap_or(name)).ok();
+
+        let overrides: BTreeMap<String, String> = match &prefix {
+            Some(p) => Registry::open(&p.pfx().join("user.reg"))
+                .ok()
+                .and_then(|r| r.section("Software\\Wine\\DllOverrides").cloned())
+                .map(|s| s.values.into_iter().collect())
+                .unwrap_or_default(),
+            None => BTreeMap::new(),
+        };
+
+        let included: Option<&Prefix> = if with_prefix {
+            match &prefix {
+                Some(p) => Some(p),
+                None => throw!(Kind::PrefixMissing, "'{}' has no prefix to export", name),
+            }
+        } else {
+            None
+        };
+
+        let manifest: Manifest = Manifest {
+            name: name.to_string(),
+            prefix: included.map(|p| p.name().to_string()),
+            game,
+            overrides,
+        };
+
+        let staging: PathBuf = staging_dir(name)?;
+        let text: String = match toml::to_string(&manifest) {
+            Ok(t) => t,
+            Err(e) => throw!(Kind::Bundle, "{}", e),
+        };
+
+        if let Err(e) = std::fs::write(staging.join(MANIFEST), text) {
+            throw!(Kind::Bundle, "{}", e);
+        }
+
+        let mut command: Command = Command::new("tar");
+        command
+            .arg("-caf")
+            .arg(out)
+            .arg("-C")
+            .arg(&staging)
+            .arg(MANIFEST);
+
+        if let Some(p) = included {
+            command.arg("-C").arg(config.data()).arg(p.name());
+        }
+
+        let result: Result<(), Error> = run(&mut command, Kind::Bundle);
+        let _ = std::fs::remove_dir_all(&staging);
+        result
+    }
+
+    /// Opens a bundle made by `Bundle::export`, reading its manifest
+    ///
+    /// # Errors
+    ///
+    /// Will fail if the archive or its manifest can not be read
+    pub fn open(archive: &Path) -> Result<Bundle, Error> {
+        let staging: PathBuf = staging_dir("import")?;
+
+        let result: Result<String, Error> = run(
+            Command::new("tar")
+                .arg("-xf")
+                .arg(archive)
+                .arg("-C")
+                .arg(&staging)
+                .arg(MANIFEST),
+            Kind::Bundle,
+        )
+        .and_then(|()| match std::fs::read_to_string(staging.join(MANIFEST)) {
+            Ok(t) => pass!(t),
+            Err(e) => throw!(Kind::Bundle, "{}", e),
+        });
+
+        let _ = std::fs::remove_dir_all(&staging);
+
+        let manifest: Manifest = match toml::from_str(&result?) {
+            Ok(m) => m,
+            Err(e) => throw!(Kind::Bundle, "{}", e),
+        };
+
+        pass!(Bundle { manifest })
+    }
+
+    #[must_use]
+    /// Returns the name of the bundled program
+    pub fn name(&self) -> &str {
+        &self.manifest.name
+    }
+
+    #[must_use]
+    /// Returns the bundled program settings
+    pub fn game(&self) -> &Game {
+        &self.manifest.game
+    }
+
+    #[must_use]
+    /// Returns the name of the bundled prefix, if the bundle contains one
+    pub fn prefix(&self) -> Option<&str> {
+        self.manifest.prefix.as_deref()
+    }
+
+    #[must_use]
+    /// Returns the bundled DLL overrides
+    pub fn overrides(&self) -> &BTreeMap<String, String> {
+        &self.manifest.overrides
+    }
+
+    /// Installs the bundle from `archive`, adding its settings to the config and extracting its prefix
+    ///
+    /// # Errors
+    ///
+    /// Will fail if...
+    /// * The name or prefix of the bundle is not a plain file name
+    /// * The program already has settings or its prefix already exists
+    /// * Extracting the prefix or writing the config fails
+    pub fn install(&self, config: &Config, archive: &Path) -> Result<(), Error> {
+        self.check_names()?;
+
+        if config.game(self.name()).is_some() {
+            throw!(Kind::Bundle, "'{}' is already in the config", self.name());
+        }
+
+        if let Some(prefix) = self.prefix() {
+            let data: PathBuf = config.data();
+            if data.join(prefix).exists() {
+                throw!(Kind::PrefixExists, "'{}'", prefix);
+            }
+
+            run(
+                Command::new("tar")
+                    .arg("-xf")
+                    .arg(archive)
+                    .arg("-C")
+                    .arg(&data)
+                    .arg(prefix),
+                Kind::Bundle,
+            )?;
+        }
+
+        config.add_game(self.name(), self.game())
+    }
+
+    /// Writes the bundled DLL overrides to a registry file to import with `regedit`
+    ///
+    /// # Errors
+    ///
+    /// Will fail if the name of the bundle is not a plain file name, or the registry file can
+    /// not be written
+    pub fn overrides_reg(&self) -> Result<PathBuf, Error> {
+        check_component("name", self.name())?;
+
+        let mut reg: RegFile = RegFile::new();
+        reg.key("HKEY_CURRENT_USER\\Software\\Wine\\DllOverrides");
+
+        for (dll, mode) in &self.manifest.overrides {
+            reg.string(dll, mode);
+        }
+
+        let path: PathBuf = cache_dir("bundles")?.join(format!("{}.reg", self.name()));
+        reg.write(&path)?;
+
+        pass!(path)
+    }
+
+    /// Checks the name and prefix of the bundle are plain file names, which a crafted manifest
+    /// could otherwise point outside the data and cache directories
+    fn check_names(&self) -> Result<(), Error> {
+        check_component("name", self.name())?;
+
+        match self.prefix() {
+            Some(prefix) => check_component("prefix", prefix),
+            None => pass!(),
+        }
+    }
+}
+
+/// Checks the manifest's `what` is exactly one normal path component
+fn check_component(what: &str, value: &str) -> Result<(), Error> {
+    if !is_plain_name(value) {
+        throw!(
+            Kind::Bundle,
+            "the bundle's {} '{}' is not a plain file name",
+            what,
+            value
+        );
+    }
+
+    pass!()
+}
+
+/// Creates an empty directory to stage bundle files in
+fn staging_dir(name: &str) -> Result<PathBuf, Error> {
+    let dir: PathBuf =
+        cache_dir("bundles")?.join(format!("{}-{}", name, timestamp(SystemTime::now())));
+
+    if let Err(e) = std::fs::create_dir_all(&dir) {
+        throw!(Kind::Bundle, "{}: {}", dir.to_string_lossy(), e);
+    }
+
+    pass!(dir)
+}
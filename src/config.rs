@@ -1,17 +1,194 @@
 use crate::{
     error::{Error, Kind},
+    schema::{self, SCHEMA},
     throw,
+    util::{data_dir, xdg_base},
+    CompatPolicy, Discord, Nvidia, Policy, Shaders, SyncHooks, Warnings,
 };
 use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::fmt::{Display, Formatter};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Config type for parsing config files
 #[derive(Debug, serde::Deserialize)]
 pub struct Config {
+    schema: Option<u32>,
     data: PathBuf,
     steam: PathBuf,
     common: Option<PathBuf>,
+    backups: Option<PathBuf>,
+    runners: Option<PathBuf>,
+    retries: Option<u32>,
+    downgrade: Option<Policy>,
+    major_jump: Option<Policy>,
+    dxvk_async: Option<bool>,
+    gpl: Option<bool>,
+    radv_perftest: Option<String>,
+    nvapi: Option<bool>,
+    dlss_indicator: Option<bool>,
+    discord: Option<bool>,
+    discord_client_id: Option<String>,
+    allow_menus: Option<bool>,
+    dedup: Option<bool>,
+    #[serde(default)]
+    games: BTreeMap<String, Game>,
+    #[serde(default)]
+    aliases: BTreeMap<String, PathBuf>,
+    #[serde(default)]
+    roots: Vec<PathBuf>,
+    #[serde(default)]
+    env_allow: Vec<String>,
+    #[serde(default)]
+    env_deny: Vec<String>,
+    #[serde(skip)]
+    location: PathBuf,
+    #[serde(skip)]
+    default_common: bool,
+    #[serde(skip)]
+    overridden: Vec<&'static str>,
+}
+
+/// Where a config value comes from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Origin {
+    /// Read from the config file at the path
+    File(PathBuf),
+    /// Not configured, proton-call's default
+    Default,
+    /// Given on the command line, over the config file
+    Flag,
+}
+
+impl Display for Origin {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Origin::File(path) => write!(f, "{}", path.to_string_lossy()),
+            Origin::Default => write!(f, "default"),
+            Origin::Flag => write!(f, "command line"),
+        }
+    }
+}
+
+/// The values a config resolves to, each with where it comes from
+#[derive(Debug, Clone, Default)]
+pub struct Settings {
+    values: Vec<(String, toml::Value, Origin)>,
+}
+
+impl Settings {
+    /// Adds the value of `key`
+    fn push(&mut self, key: &str, value: toml::Value, origin: Origin) {
+        self.values.push((key.to_string(), value, origin));
+    }
+
+    /// Iterates over every key, its value and origin
+    pub fn values(&self) -> impl Iterator<Item = (&str, &toml::Value, &Origin)> {
+        self.values.iter().map(|(k, v, o)| (k.as_str(), v, o))
+    }
+}
+
+impl Display for Settings {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let width: usize = self
+            .values
+            .iter()
+            .map(|(k, v, _)| k.len() + v.to_string().len())
+            .max()
+            .unwrap_or_default();
+
+        for (i, (key, value, origin)) in self.values.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+
+            let line: String = format!("{} = {}", key, value);
+            write!(f, "{:<width$}  # {}", line, origin, width = width + 3)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Per program settings, from a `[games.NAME]` table in the config
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct Game {
+    path: Option<PathBuf>,
+    prefix: Option<String>,
+    proton: Option<String>,
+    retention: Option<usize>,
+    sync_before: Option<String>,
+    sync_after: Option<String>,
+    command_template: Option<String>,
+    nvapi: Option<bool>,
+    dlss_indicator: Option<bool>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    args: Vec<String>,
+    // Tables go last, toml writes them after the other values
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    env: BTreeMap<String, String>,
+}
+
+impl Game {
+    #[must_use]
+    /// Returns the path to the program's executable
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    #[must_use]
+    /// Returns the name of the prefix the program uses
+    pub fn prefix(&self) -> Option<&str> {
+        self.prefix.as_deref()
+    }
+
+    #[must_use]
+    /// Returns the Proton version the program is pinned to, as given to `-p`
+    pub fn proton(&self) -> Option<&str> {
+        self.proton.as_deref()
+    }
+
+    #[must_use]
+    /// Returns how many save backups to keep for the program
+    pub fn retention(&self) -> Option<usize> {
+        self.retention
+    }
+
+    #[must_use]
+    /// Returns the template the program's launches are run through, where `%command%` is the launch
+    pub fn command_template(&self) -> Option<&str> {
+        self.command_template.as_deref()
+    }
+
+    #[must_use]
+    /// Returns the commands syncing the program's saves around a launch
+    pub fn sync_hooks(&self) -> SyncHooks {
+        SyncHooks::new(self.sync_before.clone(), self.sync_after.clone())
+    }
+
+    #[must_use]
+    /// Returns whether the program sees NVAPI, over the config's `nvapi`
+    pub fn nvapi(&self) -> Option<bool> {
+        self.nvapi
+    }
+
+    #[must_use]
+    /// Returns whether the program shows DLSS' indicator, over the config's `dlss_indicator`
+    pub fn dlss_indicator(&self) -> Option<bool> {
+        self.dlss_indicator
+    }
+
+    #[must_use]
+    /// Returns the variables set for the program's launches
+    pub fn env(&self) -> &BTreeMap<String, String> {
+        &self.env
+    }
+
+    #[must_use]
+    /// Returns the arguments the program is passed when a launch gives none
+    pub fn args(&self) -> &[String] {
+        &self.args
+    }
 }
 
 impl Config {
@@ -24,14 +201,22 @@ impl Config {
     /// * Can not open config file
     /// * Can not parse config into `Config`
     pub fn open() -> Result<Config, Error> {
+        Config::open_from(&Config::config_location()?)
+    }
+
+    /// Opens and returns the config at `loc`
+    ///
+    /// # Errors
+    ///
+    /// This function will fail if...
+    /// * Can not open config file
+    /// * Can not parse config into `Config`
+    pub fn open_from(loc: &Path) -> Result<Config, Error> {
         use std::fs::File;
         use std::io::Read;
 
-        // Get default config location
-        let loc: PathBuf = Config::config_location()?;
-
         // Open the config file
-        let mut file: File = match File::open(&loc) {
+        let mut file: File = match File::open(loc) {
             Ok(f) => f,
             Err(e) => throw!(Kind::ConfigOpen, "{}", e),
         };
@@ -46,22 +231,66 @@ impl Config {
         // Parse the config into `Config`
         let slice: &[u8] = buffer.as_slice();
 
-        let mut config: Config = toml::from_slice(slice)?;
+        let mut table: toml::value::Table = toml::from_slice(slice)?;
+        schema::check(&mut table);
 
+        let mut config: Config = toml::Value::Table(table).try_into()?;
+
+        config.location = loc.to_path_buf();
         config.default_common();
 
         Ok(config)
     }
 
-    /// Finds one of the two default config locations
+    /// Appends a `[games.NAME]` table for `game` to the config file this config was read from
+    ///
+    /// # Errors
+    ///
+    /// Will fail if the config file can not be written
+    pub fn add_game(&self, name: &str, game: &Game) -> Result<(), Error> {
+        use std::fs::OpenOptions;
+        use std::io::Write;
+
+        #[derive(serde::Serialize)]
+        struct Games<'a> {
+            games: BTreeMap<&'a str, &'a Game>,
+        }
+
+        let mut games: BTreeMap<&str, &Game> = BTreeMap::new();
+        games.insert(name, game);
+
+        let text: String = match toml::to_string(&Games { games }) {
+            Ok(t) => t,
+            Err(e) => throw!(Kind::ConfigWrite, "{}", e),
+        };
+
+        let loc: &Path = &self.location;
+
+        let result = OpenOptions::new()
+            .append(true)
+            .open(loc)
+            .and_then(|mut f| write!(f, "\n{}", text));
+
+        if let Err(e) = result {
+            throw!(Kind::ConfigWrite, "{}: {}", loc.to_string_lossy(), e);
+        }
+
+        Ok(())
+    }
+
+    /// Finds the config given by `PROTON_CALL_CONFIG`, otherwise one of the two default config
+    /// locations
     ///
     /// # Errors
     ///
-    /// Will only fail if `XDG_CONFIG_HOME` and `HOME` do not exist in environment
+    /// Will only fail if `PROTON_CALL_CONFIG`, `XDG_CONFIG_HOME` and `HOME` do not exist in
+    /// environment
     pub fn config_location() -> Result<PathBuf, Error> {
         use std::env::var;
 
-        if let Ok(val) = var("XDG_CONFIG_HOME") {
+        if let Some(val) = std::env::var_os("PROTON_CALL_CONFIG").filter(|v| !v.is_empty()) {
+            Ok(PathBuf::from(val))
+        } else if let Ok(val) = var("XDG_CONFIG_HOME") {
             let path = format!("{}/proton.conf", val);
             Ok(PathBuf::from(path))
         } else if let Ok(val) = var("HOME") {
@@ -72,18 +301,85 @@ impl Config {
         }
     }
 
+    #[must_use]
+    /// Finds the Steam install in the places Steam installs to, natively or as a Flatpak, for a
+    /// new config
+    pub fn detect_steam() -> Option<PathBuf> {
+        let home: PathBuf = PathBuf::from(std::env::var_os("HOME")?);
+
+        [
+            ".steam/steam",
+            ".local/share/Steam",
+            ".var/app/com.valvesoftware.Steam/.local/share/Steam",
+        ]
+        .iter()
+        .map(|dir| home.join(dir))
+        .find(|dir| dir.join("steamapps").is_dir())
+        .map(|dir| dir.canonicalize().unwrap_or(dir))
+    }
+
+    #[must_use]
+    /// Returns the compat data directory proposed for a new config, in the user's data directory
+    pub fn default_data() -> Option<PathBuf> {
+        xdg_base("XDG_DATA_HOME", ".local/share").map(|dir| dir.join("proton-call/compat"))
+    }
+
+    /// Writes a new config to `loc` with `data`, `steam` and `common`, creating `data` and the
+    /// directory `loc` is in
+    ///
+    /// # Errors
+    ///
+    /// Will fail if the config can not be serialized, or a directory or the file can not be
+    /// written
+    pub fn init(loc: &Path, data: &Path, steam: &Path, common: &Path) -> Result<(), Error> {
+        #[derive(serde::Serialize)]
+        struct New<'a> {
+            schema: u32,
+            data: &'a Path,
+            steam: &'a Path,
+            common: &'a Path,
+        }
+
+        let text: String = match toml::to_string(&New {
+            schema: SCHEMA,
+            data,
+            steam,
+            common,
+        }) {
+            Ok(t) => t,
+            Err(e) => throw!(Kind::ConfigWrite, "{}", e),
+        };
+
+        for dir in [Some(data), loc.parent()].into_iter().flatten() {
+            if let Err(e) = std::fs::create_dir_all(dir) {
+                throw!(Kind::ConfigWrite, "{}: {}", dir.to_string_lossy(), e);
+            }
+        }
+
+        if let Err(e) = std::fs::write(loc, text) {
+            throw!(Kind::ConfigWrite, "{}: {}", loc.to_string_lossy(), e);
+        }
+
+        Ok(())
+    }
+
     /// Sets a default common if not given by user
     fn default_common(&mut self) {
         if self.common.is_none() {
-            let common: PathBuf = self._default_common();
-            self.common = Some(common);
+            self.common = Some(self._default_common());
+            self.default_common = true;
+            Warnings::push("config", self.default_common_warning());
         }
     }
 
+    /// Returns the warning about using the default common directory
+    fn default_common_warning(&self) -> String {
+        format!("using default common {}", self.common().to_string_lossy())
+    }
+
     #[must_use]
     /// Generates a default common directory
     fn _default_common(&self) -> PathBuf {
-        eprintln!("warning: using default common");
         let steam: Cow<str> = self.steam.to_string_lossy();
         let common_str: String = format!("{}/steamapps/common/", steam);
         PathBuf::from(common_str)
@@ -99,6 +395,44 @@ impl Config {
         }
     }
 
+    #[must_use]
+    /// Uses `data` as the compat data directory over the config file's, for one run
+    pub fn with_data(mut self, data: PathBuf) -> Config {
+        self.data = data;
+        self.overridden.push("data");
+        self
+    }
+
+    #[must_use]
+    /// Uses `steam` as the Steam directory over the config file's, for one run
+    ///
+    /// A `common` which is not configured follows it.
+    pub fn with_steam(mut self, steam: PathBuf) -> Config {
+        if self.default_common {
+            Warnings::retract("config", &self.default_common_warning());
+            self.steam = steam;
+            self.common = Some(self._default_common());
+            Warnings::push("config", self.default_common_warning());
+        } else {
+            self.steam = steam;
+        }
+        self.overridden.push("steam");
+        self
+    }
+
+    #[must_use]
+    /// Uses `common` as Steam's common directory over the config file's, for one run
+    pub fn with_common(mut self, common: PathBuf) -> Config {
+        // The default was warned about while reading the file
+        if self.default_common {
+            Warnings::retract("config", &self.default_common_warning());
+        }
+        self.common = Some(common);
+        self.default_common = false;
+        self.overridden.push("common");
+        self
+    }
+
     #[must_use]
     /// Returns the in use steam directory
     pub fn steam(&self) -> PathBuf {
@@ -110,6 +444,328 @@ impl Config {
     pub fn data(&self) -> PathBuf {
         self.data.clone()
     }
+
+    /// Returns the directory save backups are kept in
+    ///
+    /// # Errors
+    ///
+    /// Will fail if no directory is configured and the default can not be created
+    pub fn backups(&self) -> Result<PathBuf, Error> {
+        match &self.backups {
+            Some(backups) => Ok(backups.clone()),
+            None => data_dir("backups"),
+        }
+    }
+
+    #[must_use]
+    /// Returns the directory Wine runners are indexed from, Lutris' by default
+    pub fn runners(&self) -> Option<PathBuf> {
+        match &self.runners {
+            Some(runners) => Some(runners.clone()),
+            None => Some(xdg_base("XDG_DATA_HOME", ".local/share")?.join("lutris/runners/wine")),
+        }
+    }
+
+    #[must_use]
+    /// Returns how many times a launch failing while starting up is retried, none by default
+    pub fn retries(&self) -> u32 {
+        self.retries.unwrap_or_default()
+    }
+
+    #[must_use]
+    /// Returns the policies for launching into a prefix another Proton version last used, warning
+    /// on downgrades and allowing major version jumps by default
+    pub fn compat_policy(&self) -> CompatPolicy {
+        let default: CompatPolicy = CompatPolicy::default();
+
+        CompatPolicy::new(
+            self.downgrade.unwrap_or(default.downgrade()),
+            self.major_jump.unwrap_or(default.major_jump()),
+        )
+    }
+
+    #[must_use]
+    /// Returns the shader compilation options launches use, DXVK's and Mesa's defaults unless set
+    pub fn shaders(&self) -> Shaders {
+        let mut shaders: Shaders =
+            Shaders::default().dxvk_async(self.dxvk_async.unwrap_or_default());
+
+        if let Some(enable) = self.gpl {
+            shaders = shaders.gpl(enable);
+        }
+
+        if let Some(options) = &self.radv_perftest {
+            shaders = shaders.radv_perftest(options);
+        }
+
+        shaders
+    }
+
+    #[must_use]
+    /// Returns whether new prefixes share identical files with the others through reflinks,
+    /// `false` by default
+    pub fn dedup(&self) -> bool {
+        self.dedup.unwrap_or_default()
+    }
+
+    #[must_use]
+    /// Returns whether launches may add entries to the host's menus, `false` by default
+    pub fn allow_menus(&self) -> bool {
+        self.allow_menus.unwrap_or_default()
+    }
+
+    #[must_use]
+    /// Returns the Discord bridge launches use, when the config's `discord` or `enable` turns it on
+    pub fn discord(&self, enable: bool) -> Option<Discord> {
+        (enable || self.discord.unwrap_or_default())
+            .then(|| Discord::new(self.discord_client_id.clone()))
+    }
+
+    #[must_use]
+    /// Returns the NVIDIA options launches of `game` use, its own settings winning over the
+    /// config's, Proton's defaults unless set
+    pub fn nvidia(&self, game: Option<&Game>) -> Nvidia {
+        let mut nvidia: Nvidia = Nvidia::default();
+
+        if let Some(enable) = game.and_then(Game::nvapi).or(self.nvapi) {
+            nvidia = nvidia.nvapi(enable);
+        }
+
+        let indicator: Option<bool> = game.and_then(Game::dlss_indicator);
+        nvidia.dlss_indicator(indicator.or(self.dlss_indicator).unwrap_or_default())
+    }
+
+    #[must_use]
+    /// Returns the directories games are installed in, `common` by default
+    pub fn roots(&self) -> Vec<PathBuf> {
+        if self.roots.is_empty() {
+            vec![self.common()]
+        } else {
+            self.roots.clone()
+        }
+    }
+
+    #[must_use]
+    /// Returns the inherited variables allowed to reach launched programs, all when empty
+    pub fn env_allow(&self) -> &[String] {
+        &self.env_allow
+    }
+
+    #[must_use]
+    /// Returns the inherited variables kept from launched programs
+    pub fn env_deny(&self) -> &[String] {
+        &self.env_deny
+    }
+
+    #[must_use]
+    /// Returns the path the alias `name` stands for, from the `[aliases]` table
+    pub fn alias(&self, name: &str) -> Option<&Path> {
+        self.aliases.get(name).map(PathBuf::as_path)
+    }
+
+    #[must_use]
+    /// Returns the settings for the program `name`
+    pub fn game(&self, name: &str) -> Option<&Game> {
+        self.games.get(name)
+    }
+
+    /// Iterates over every program's settings and their names
+    pub fn games(&self) -> impl Iterator<Item = (&str, &Game)> {
+        self.games.iter().map(|(name, game)| (name.as_str(), game))
+    }
+
+    #[must_use]
+    /// Finds the settings, and their name, for the program at `program`
+    pub fn game_for(&self, program: &Path) -> Option<(&str, &Game)> {
+        let program: PathBuf = program
+            .canonicalize()
+            .unwrap_or_else(|_| program.to_path_buf());
+
+        self.games
+            .iter()
+            .find(|(_, game)| match &game.path {
+                Some(path) => path.canonicalize().unwrap_or_else(|_| path.clone()) == program,
+                None => false,
+            })
+            .map(|(name, game)| (name.as_str(), game))
+    }
+
+    #[must_use]
+    /// Returns every value the config resolves to, noting whether it was configured or defaulted
+    pub fn settings(&self) -> Settings {
+        let file: Origin = Origin::File(self.location.clone());
+        let origin = |configured: bool| -> Origin {
+            if configured {
+                file.clone()
+            } else {
+                Origin::Default
+            }
+        };
+
+        let mut settings: Settings = Settings::default();
+
+        settings.push(
+            "schema",
+            toml::Value::Integer(self.schema.unwrap_or(SCHEMA).into()),
+            origin(self.schema.is_some()),
+        );
+        let overridden = |key: &str, origin: Origin| -> Origin {
+            if self.overridden.contains(&key) {
+                Origin::Flag
+            } else {
+                origin
+            }
+        };
+
+        settings.push(
+            "data",
+            path_value(&self.data),
+            overridden("data", file.clone()),
+        );
+        settings.push(
+            "steam",
+            path_value(&self.steam),
+            overridden("steam", file.clone()),
+        );
+        settings.push(
+            "common",
+            path_value(&self.common()),
+            overridden("common", origin(!self.default_common)),
+        );
+
+        if let Ok(backups) = self.backups() {
+            settings.push(
+                "backups",
+                path_value(&backups),
+                origin(self.backups.is_some()),
+            );
+        }
+
+        if let Some(runners) = self.runners() {
+            settings.push(
+                "runners",
+                path_value(&runners),
+                origin(self.runners.is_some()),
+            );
+        }
+
+        settings.push(
+            "retries",
+            toml::Value::Integer(self.retries().into()),
+            origin(self.retries.is_some()),
+        );
+
+        let policy: CompatPolicy = self.compat_policy();
+        settings.push(
+            "downgrade",
+            toml::Value::String(policy.downgrade().to_string()),
+            origin(self.downgrade.is_some()),
+        );
+        settings.push(
+            "major_jump",
+            toml::Value::String(policy.major_jump().to_string()),
+            origin(self.major_jump.is_some()),
+        );
+
+        self.launch_settings(&mut settings);
+
+        settings.push(
+            "dedup",
+            toml::Value::Boolean(self.dedup()),
+            origin(self.dedup.is_some()),
+        );
+
+        let roots: Vec<toml::Value> = self.roots().iter().map(|r| path_value(r)).collect();
+        settings.push(
+            "roots",
+            toml::Value::Array(roots),
+            origin(!self.roots.is_empty()),
+        );
+
+        for (key, list) in [("env_allow", &self.env_allow), ("env_deny", &self.env_deny)] {
+            let values: Vec<toml::Value> = list.iter().cloned().map(toml::Value::String).collect();
+            settings.push(key, toml::Value::Array(values), origin(!list.is_empty()));
+        }
+
+        for (name, path) in &self.aliases {
+            settings.push(&format!("aliases.{}", name), path_value(path), file.clone());
+        }
+
+        for (name, game) in &self.games {
+            if let Ok(toml::Value::Table(table)) = toml::Value::try_from(game) {
+                for (key, value) in table {
+                    settings.push(&format!("games.{}.{}", name, key), value, file.clone());
+                }
+            }
+        }
+
+        settings
+    }
+
+    /// Adds the values of the options launches set variables for to `settings`
+    fn launch_settings(&self, settings: &mut Settings) {
+        let file: Origin = Origin::File(self.location.clone());
+        let origin = |configured: bool| -> Origin {
+            if configured {
+                file.clone()
+            } else {
+                Origin::Default
+            }
+        };
+
+        settings.push(
+            "dxvk_async",
+            toml::Value::Boolean(self.dxvk_async.unwrap_or_default()),
+            origin(self.dxvk_async.is_some()),
+        );
+
+        if let Some(gpl) = self.gpl {
+            settings.push("gpl", toml::Value::Boolean(gpl), file.clone());
+        }
+
+        if let Some(options) = &self.radv_perftest {
+            settings.push(
+                "radv_perftest",
+                toml::Value::String(options.clone()),
+                file.clone(),
+            );
+        }
+
+        if let Some(nvapi) = self.nvapi {
+            settings.push("nvapi", toml::Value::Boolean(nvapi), file.clone());
+        }
+
+        settings.push(
+            "dlss_indicator",
+            toml::Value::Boolean(self.dlss_indicator.unwrap_or_default()),
+            origin(self.dlss_indicator.is_some()),
+        );
+
+        settings.push(
+            "allow_menus",
+            toml::Value::Boolean(self.allow_menus()),
+            origin(self.allow_menus.is_some()),
+        );
+
+        settings.push(
+            "discord",
+            toml::Value::Boolean(self.discord.unwrap_or_default()),
+            origin(self.discord.is_some()),
+        );
+
+        if let Some(id) = &self.discord_client_id {
+            settings.push(
+                "discord_client_id",
+                toml::Value::String(id.clone()),
+                file.clone(),
+            );
+        }
+    }
+}
+
+/// Turns a path into a TOML string
+fn path_value(path: &Path) -> toml::Value {
+    toml::Value::String(path.to_string_lossy().to_string())
 }
 
 impl Display for Config {
@@ -0,0 +1,150 @@
+use crate::error::{Error, Kind};
+use crate::util::{cache_dir, file_hash, run};
+use crate::{pass, throw, ProtonEvent};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Endings of the archives Proton builds are released as, which `-c` extracts before launching
+static TARBALLS: &[&str] = &[".tar.gz", ".tgz", ".tar.xz", ".txz", ".tar.zst", ".tar"];
+
+/// Most entries of a directory named when reporting what `-c` pointed at
+const LISTED: usize = 8;
+
+/// Finds the proton script a custom path points at: the script itself, a directory holding it,
+/// a directory one level above it, such as a single folder from extracting a tarball, or a
+/// tarball, which is extracted into the cache first
+///
+/// # Errors
+///
+/// Will fail if no script is found, naming what was found instead, if several builds are, or
+/// if a tarball can not be extracted
+pub fn proton_script(custom: &Path) -> Result<PathBuf, Error> {
+    if is_tarball(custom) {
+        return proton_script(&extract(custom)?);
+    }
+
+    if custom.is_file() {
+        return pass!(custom.to_path_buf());
+    }
+
+    if !custom.is_dir() {
+        throw!(
+            Kind::ProtonMissing,
+            "'{}' does not exist",
+            custom.to_string_lossy()
+        );
+    }
+
+    if custom.join("proton").is_file() {
+        return pass!(custom.join("proton"));
+    }
+
+    let mut entries: Vec<PathBuf> = custom
+        .read_dir()
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .collect();
+    entries.sort();
+
+    let nested: Vec<PathBuf> = entries
+        .iter()
+        .map(|entry| entry.join("proton"))
+        .filter(|script| script.is_file())
+        .collect();
+
+    match nested.as_slice() {
+        [script] => pass!(script.clone()),
+        [] => throw!(
+            Kind::ProtonMissing,
+            "expected a `proton` script in '{}' or a directory in it, found {}",
+            custom.to_string_lossy(),
+            describe(&entries)
+        ),
+        _ => throw!(
+            Kind::ProtonMissing,
+            "'{}' holds several Proton builds, give the one to use: {}",
+            custom.to_string_lossy(),
+            describe(&nested)
+        ),
+    }
+}
+
+/// Names the entries of a directory which did not hold a proton script, noting Wine builds and
+/// scripts under another name
+fn describe(entries: &[PathBuf]) -> String {
+    if entries.is_empty() {
+        return String::from("nothing, the directory is empty");
+    }
+
+    let mut described: Vec<String> = entries
+        .iter()
+        .take(LISTED)
+        .map(|entry| {
+            let name: String = entry
+                .file_name()
+                .unwrap_or(entry.as_os_str())
+                .to_string_lossy()
+                .to_string();
+
+            if entry.join("bin/wine").is_file() {
+                format!("{}/ (a Wine build, without Proton's script)", name)
+            } else if entry.is_dir() {
+                format!("{}/", name)
+            } else if name.starts_with("proton") {
+                format!("{} (not named `proton`)", name)
+            } else {
+                name
+            }
+        })
+        .collect();
+
+    if entries.len() > LISTED {
+        described.push(format!("and {} more", entries.len() - LISTED));
+    }
+
+    described.join(", ")
+}
+
+/// Tells whether `path` is a tarball, by its name
+fn is_tarball(path: &Path) -> bool {
+    let name: String = path.to_string_lossy().to_lowercase();
+    path.is_file() && TARBALLS.iter().any(|ending| name.ends_with(ending))
+}
+
+/// Extracts a tarball into the cache, keyed by a hash of its contents so a changed build at the
+/// same path is extracted again, and returns where
+fn extract(tarball: &Path) -> Result<PathBuf, Error> {
+    let hash: u64 = match file_hash(tarball) {
+        Ok(h) => h,
+        Err(e) => throw!(Kind::Extract, "{}: {}", tarball.to_string_lossy(), e),
+    };
+    let dir: PathBuf = cache_dir("builds")?.join(format!("{:016x}", hash));
+    if dir.is_dir() {
+        return pass!(dir);
+    }
+
+    // Extracted aside and renamed once complete, so an interrupted extraction is not reused
+    let partial: PathBuf = dir.with_extension("partial");
+    let _ = std::fs::remove_dir_all(&partial);
+    if let Err(e) = std::fs::create_dir_all(&partial) {
+        throw!(Kind::Extract, "{}: {}", partial.to_string_lossy(), e);
+    }
+
+    ProtonEvent::Extracting(tarball.to_path_buf()).emit();
+    run(
+        Command::new("tar")
+            .arg("-xf")
+            .arg(tarball)
+            .arg("-C")
+            .arg(&partial),
+        Kind::Extract,
+    )?;
+
+    if let Err(e) = std::fs::rename(&partial, &dir) {
+        throw!(Kind::Extract, "{}: {}", dir.to_string_lossy(), e);
+    }
+
+    pass!(dir)
+}
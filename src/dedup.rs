@@ -0,0 +1,185 @@
+use crate::error::{Error, Kind};
+use crate::util::{file_hash, format_size};
+use crate::{pass, throw};
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::{Display, Formatter};
+use std::io::Read;
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Smallest file worth sharing, below which the savings do not pay for the hashing
+const MIN_SIZE: u64 = 64 * 1024;
+
+/// Ending of the copy a duplicate is replaced with, renamed over it once complete
+static PARTIAL: &str = "proton-call-dedup";
+
+/// Space saved by sharing identical files between prefixes through copy-on-write reflinks,
+/// which btrfs and XFS support
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Dedup {
+    files: usize,
+    saved: u64,
+}
+
+impl Dedup {
+    /// Replaces files with identical contents below `dirs` with reflinks to one of them, keeping
+    /// each file's permissions
+    ///
+    /// Files already sharing their data are counted again, so the space saved is an upper bound.
+    ///
+    /// # Errors
+    ///
+    /// Will fail if the filesystem does not support reflinks, or a duplicate can not be replaced
+    pub fn run(dirs: &[PathBuf]) -> Result<Dedup, Error> {
+        // Only files on the same filesystem and of the same size can share their data
+        let mut candidates: BTreeMap<(u64, u64), Vec<PathBuf>> = BTreeMap::new();
+        let mut inodes: HashSet<(u64, u64)> = HashSet::new();
+
+        for dir in dirs {
+            for (path, meta) in files(dir) {
+                if meta.len() >= MIN_SIZE && inodes.insert((meta.dev(), meta.ino())) {
+                    candidates
+                        .entry((meta.dev(), meta.len()))
+                        .or_default()
+                        .push(path);
+                }
+            }
+        }
+
+        let mut dedup: Dedup = Dedup::default();
+
+        for ((_, size), paths) in candidates {
+            if paths.len() < 2 {
+                continue;
+            }
+
+            let mut hashed: BTreeMap<u64, Vec<PathBuf>> = BTreeMap::new();
+            for path in paths {
+                if let Ok(hash) = file_hash(&path) {
+                    hashed.entry(hash).or_default().push(path);
+                }
+            }
+
+            for (_, mut same) in hashed {
+                same.sort();
+                let Some((source, duplicates)) = same.split_first() else {
+                    continue;
+                };
+
+                for duplicate in duplicates {
+                    if same_contents(source, duplicate) {
+                        reflink(source, duplicate)?;
+                        dedup.files += 1;
+                        dedup.saved += size;
+                    }
+                }
+            }
+        }
+
+        pass!(dedup)
+    }
+
+    #[must_use]
+    /// Returns how many files now share their data with another
+    pub fn files(&self) -> usize {
+        self.files
+    }
+
+    #[must_use]
+    /// Returns the space saved, in bytes
+    pub fn saved(&self) -> u64 {
+        self.saved
+    }
+}
+
+impl Display for Dedup {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Deduplicated {} files, saving up to {}",
+            self.files,
+            format_size(self.saved)
+        )
+    }
+}
+
+/// Lists the regular files below `dir` with their metadata, without following symlinks
+fn files(dir: &Path) -> Vec<(PathBuf, std::fs::Metadata)> {
+    let mut found: Vec<(PathBuf, std::fs::Metadata)> = Vec::new();
+
+    for entry in dir.read_dir().into_iter().flatten().flatten() {
+        let path: PathBuf = entry.path();
+        let Ok(meta) = path.symlink_metadata() else {
+            continue;
+        };
+
+        if meta.is_dir() {
+            found.extend(files(&path));
+        } else if meta.is_file() {
+            found.push((path, meta));
+        }
+    }
+
+    found
+}
+
+/// Compares two files byte for byte, since equal hashes do not prove equal contents
+fn same_contents(a: &Path, b: &Path) -> bool {
+    let (Ok(mut a), Ok(mut b)) = (std::fs::File::open(a), std::fs::File::open(b)) else {
+        return false;
+    };
+
+    let mut left: Vec<u8> = vec![0; 1 << 16];
+    let mut right: Vec<u8> = vec![0; 1 << 16];
+
+    loop {
+        let Ok(read) = a.read(&mut left) else {
+            return false;
+        };
+
+        if read == 0 {
+            return b.read(&mut right).is_ok_and(|n| n == 0);
+        }
+
+        if b.read_exact(&mut right[..read]).is_err() || left[..read] != right[..read] {
+            return false;
+        }
+    }
+}
+
+/// Replaces `duplicate` with a reflink of `source`, through a copy renamed over it
+fn reflink(source: &Path, duplicate: &Path) -> Result<(), Error> {
+    let partial: PathBuf = duplicate.with_extension(PARTIAL);
+
+    let copied = Command::new("cp")
+        .arg("--reflink=always")
+        .arg(source)
+        .arg(&partial)
+        .output();
+
+    match copied {
+        Ok(output) if output.status.success() => (),
+        Ok(output) => {
+            let _ = std::fs::remove_file(&partial);
+            throw!(
+                Kind::Unsupported,
+                "reflinks on '{}': {}",
+                duplicate.to_string_lossy(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Err(e) => throw!(Kind::Unsupported, "failed to run cp: {}", e),
+    }
+
+    if let Ok(meta) = duplicate.metadata() {
+        let _ = std::fs::set_permissions(&partial, meta.permissions());
+    }
+
+    if let Err(e) = std::fs::rename(&partial, duplicate) {
+        let _ = std::fs::remove_file(&partial);
+        throw!(Kind::PrefixWrite, "{}: {}", duplicate.to_string_lossy(), e);
+    }
+
+    pass!()
+}
@@ -0,0 +1,53 @@
+use crate::error::{Error, Kind};
+use crate::{pass, throw};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// Size of a Wine virtual desktop to run a program in, given as `WxH`
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct VirtualDesktop {
+    width: u32,
+    height: u32,
+}
+
+impl VirtualDesktop {
+    #[must_use]
+    /// Creates a new `VirtualDesktop` of `width` by `height` pixels
+    pub fn new(width: u32, height: u32) -> VirtualDesktop {
+        VirtualDesktop { width, height }
+    }
+
+    #[must_use]
+    /// Returns the argument making `explorer` open the desktop, named after proton-call
+    pub fn argument(&self) -> String {
+        format!("/desktop=protoncall,{}", self)
+    }
+}
+
+impl Display for VirtualDesktop {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}x{}", self.width, self.height)
+    }
+}
+
+impl FromStr for VirtualDesktop {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let size: Option<(u32, u32)> = s
+            .to_ascii_lowercase()
+            .split_once('x')
+            .and_then(|(w, h)| Some((w.trim().parse().ok()?, h.trim().parse().ok()?)));
+
+        match size {
+            Some((width, height)) if width > 0 && height > 0 => {
+                pass!(VirtualDesktop::new(width, height))
+            }
+            _ => throw!(
+                Kind::ArgumentParse,
+                "virtual desktop '{}', expected WIDTHxHEIGHT such as 1920x1080",
+                s
+            ),
+        }
+    }
+}
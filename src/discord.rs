@@ -0,0 +1,168 @@
+use crate::Warnings;
+use std::io::{Read, Write};
+use std::os::unix::net::UnixStream;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Directories below `XDG_RUNTIME_DIR` sandboxed Discord clients put their IPC socket in
+static SANDBOXED: &[&str] = &[
+    "app/com.discordapp.Discord",
+    "app/com.discordapp.DiscordCanary",
+    "app/dev.vencord.Vesktop",
+    ".flatpak/dev.vencord.Vesktop/xdg-run",
+    "snap.discord",
+];
+
+/// Numbered IPC sockets a Discord client may listen on, the first free one taken
+const SOCKETS: u8 = 10;
+
+/// How long to wait on Discord's replies
+const TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Opt-in bridge between a launch and the host's Discord client, for Rich Presence
+#[derive(Debug, Clone, Default)]
+pub struct Discord {
+    client_id: Option<String>,
+}
+
+impl Discord {
+    #[must_use]
+    /// Creates a new `Discord` bridge, setting a presence as the Discord application `client_id`
+    /// when given
+    pub fn new(client_id: Option<String>) -> Discord {
+        Discord { client_id }
+    }
+
+    /// Exposes the host's Discord socket where Wine's Discord bridges look for it, and sets a
+    /// presence for `name`, which lasts as long as the returned `Presence`
+    pub(crate) fn start(&self, name: &str) -> Option<Presence> {
+        let socket: PathBuf = match expose() {
+            Ok(socket) => socket,
+            Err(problem) => {
+                Warnings::push("launch", format!("Discord bridge: {}", problem));
+                return None;
+            }
+        };
+
+        let client_id: &str = self.client_id.as_deref()?;
+        match Presence::set(&socket, client_id, name) {
+            Ok(presence) => Some(presence),
+            Err(e) => {
+                Warnings::push("launch", format!("Discord presence: {}", e));
+                None
+            }
+        }
+    }
+}
+
+/// A Rich Presence shown while the connection to Discord stays open
+pub(crate) struct Presence {
+    _stream: UnixStream,
+}
+
+impl Presence {
+    /// Connects to Discord at `socket` as `client_id` and sets an activity playing `name`
+    fn set(socket: &Path, client_id: &str, name: &str) -> std::io::Result<Presence> {
+        let mut stream: UnixStream = UnixStream::connect(socket)?;
+        stream.set_read_timeout(Some(TIMEOUT))?;
+
+        send(
+            &mut stream,
+            0,
+            &serde_json::json!({ "v": 1, "client_id": client_id }),
+        )?;
+        receive(&mut stream)?;
+
+        let start: u64 = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        send(
+            &mut stream,
+            1,
+            &serde_json::json!({
+                "cmd": "SET_ACTIVITY",
+                "nonce": format!("proton-call-{}", start),
+                "args": {
+                    "pid": std::process::id(),
+                    "activity": {
+                        "details": name,
+                        "timestamps": { "start": start },
+                    },
+                },
+            }),
+        )?;
+        receive(&mut stream)?;
+
+        Ok(Presence { _stream: stream })
+    }
+}
+
+/// Finds the host's Discord socket and links it to `discord-ipc-0` in `XDG_RUNTIME_DIR`, where
+/// Wine's Discord bridges look, when a sandboxed client put it elsewhere
+fn expose() -> Result<PathBuf, String> {
+    let Some(runtime) = std::env::var_os("XDG_RUNTIME_DIR").map(PathBuf::from) else {
+        return Err(String::from("XDG_RUNTIME_DIR is not set"));
+    };
+
+    let expected: PathBuf = runtime.join("discord-ipc-0");
+    if UnixStream::connect(&expected).is_ok() {
+        return Ok(expected);
+    }
+
+    let found: Option<PathBuf> = SANDBOXED
+        .iter()
+        .map(|dir| runtime.join(dir))
+        .flat_map(|dir| (0..SOCKETS).map(move |n| dir.join(format!("discord-ipc-{}", n))))
+        .find(|socket| UnixStream::connect(socket).is_ok());
+
+    let Some(found) = found else {
+        return Err(String::from("Discord is not running"));
+    };
+
+    // A socket left behind by a client which quit is replaced
+    let _ = std::fs::remove_file(&expected);
+    if let Err(e) = std::os::unix::fs::symlink(&found, &expected) {
+        return Err(format!("{}: {}", expected.to_string_lossy(), e));
+    }
+
+    Ok(expected)
+}
+
+/// Writes a frame of Discord's IPC protocol, an opcode and length before the JSON payload
+fn send(stream: &mut UnixStream, opcode: u32, payload: &serde_json::Value) -> std::io::Result<()> {
+    let payload: Vec<u8> = payload.to_string().into_bytes();
+    let length: u32 = u32::try_from(payload.len()).unwrap_or(u32::MAX);
+
+    let mut frame: Vec<u8> = Vec::with_capacity(payload.len() + 8);
+    frame.extend(opcode.to_le_bytes());
+    frame.extend(length.to_le_bytes());
+    frame.extend(payload);
+
+    stream.write_all(&frame)
+}
+
+/// Reads a frame of Discord's IPC protocol, failing on an error Discord sends back
+fn receive(stream: &mut UnixStream) -> std::io::Result<()> {
+    let mut header: [u8; 8] = [0; 8];
+    stream.read_exact(&mut header)?;
+
+    let opcode: u32 = u32::from_le_bytes([header[0], header[1], header[2], header[3]]);
+    let length: u32 = u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
+
+    let mut payload: Vec<u8> = vec![0; length as usize];
+    stream.read_exact(&mut payload)?;
+
+    let reply: serde_json::Value = serde_json::from_slice(&payload).unwrap_or_default();
+
+    // Opcode 2 closes the connection, and replies with an `ERROR` event describe a failure
+    if opcode == 2 || reply["evt"] == "ERROR" {
+        let message: &str = reply["data"]["message"]
+            .as_str()
+            .or_else(|| reply["message"].as_str())
+            .unwrap_or("Discord refused the connection");
+        return Err(std::io::Error::other(message.to_string()));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,102 @@
+use std::collections::BTreeMap;
+use std::ffi::{OsStr, OsString};
+use std::fmt::{Display, Formatter};
+use std::process::Command;
+
+/// Where a variable in a launch's environment comes from
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum EnvSource {
+    /// Passed through from proton-call's own environment
+    Inherited,
+    /// Set by proton-call for every launch
+    ProtonCall,
+    /// Set because of a command line flag
+    Flag,
+    /// Set from the config
+    Config,
+    /// Set by a fix from the fixes database
+    Fix,
+}
+
+impl Display for EnvSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EnvSource::Inherited => write!(f, "inherited"),
+            EnvSource::ProtonCall => write!(f, "proton-call"),
+            EnvSource::Flag => write!(f, "flag"),
+            EnvSource::Config => write!(f, "config"),
+            EnvSource::Fix => write!(f, "fix"),
+        }
+    }
+}
+
+/// The complete environment a launch runs with, each variable tagged with its source
+#[derive(Debug, Clone, Default)]
+pub struct Environment {
+    vars: BTreeMap<OsString, (OsString, EnvSource)>,
+}
+
+impl Environment {
+    #[must_use]
+    /// Starts from proton-call's own environment, keeping the variables `keep` accepts
+    pub(crate) fn inherited(keep: impl Fn(&str) -> bool) -> Environment {
+        let mut env: Environment = Environment::default();
+
+        for (key, value) in std::env::vars_os() {
+            if keep(&key.to_string_lossy()) {
+                env.vars.insert(key, (value, EnvSource::Inherited));
+            }
+        }
+
+        env
+    }
+
+    /// Sets a variable, replacing any earlier value
+    pub(crate) fn set(&mut self, key: &str, value: impl AsRef<OsStr>, source: EnvSource) {
+        self.vars
+            .insert(key.into(), (value.as_ref().to_os_string(), source));
+    }
+
+    #[must_use]
+    /// Returns the value of a variable
+    pub fn get(&self, key: &str) -> Option<&OsStr> {
+        self.vars.get(OsStr::new(key)).map(|(v, _)| v.as_os_str())
+    }
+
+    #[must_use]
+    /// Returns where a variable comes from
+    pub fn source(&self, key: &str) -> Option<EnvSource> {
+        self.vars.get(OsStr::new(key)).map(|(_, s)| *s)
+    }
+
+    /// Iterates over every variable, its value and source, sorted by name
+    pub fn vars(&self) -> impl Iterator<Item = (&OsStr, &OsStr, EnvSource)> {
+        self.vars
+            .iter()
+            .map(|(k, (v, s))| (k.as_os_str(), v.as_os_str(), *s))
+    }
+
+    /// Replaces the environment of `command` with this one
+    pub(crate) fn apply(&self, command: &mut Command) {
+        command.env_clear();
+        command.envs(self.vars.iter().map(|(k, (v, _))| (k, v)));
+    }
+}
+
+impl Display for Environment {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Environment:")?;
+
+        for (key, value, source) in self.vars() {
+            write!(
+                f,
+                "\n{}={}  ({})",
+                key.to_string_lossy(),
+                value.to_string_lossy(),
+                source
+            )?;
+        }
+
+        Ok(())
+    }
+}
@@ -1,3 +1,4 @@
+use crate::locale::tr;
 use lliw::Fg::Red;
 use lliw::Reset;
 use std::fmt::{Display, Formatter};
@@ -117,30 +118,94 @@ pub enum Kind {
     ArgumentMissing,
     /// for when Jargon has an internal Error,
     JargonInternal,
+    /// for when an unknown subcommand is given
+    UnknownCommand,
+    /// for when a requested prefix is not found
+    PrefixMissing,
+    /// for when reading a prefix's files fails
+    PrefixRead,
+    /// for when modifying a prefix's files fails
+    PrefixWrite,
+    /// for when creating a prefix which already exists
+    PrefixExists,
+    /// for when downloading a file fails
+    Download,
+    /// for when extracting an archive fails
+    Extract,
+    /// for when an unknown font pack is requested
+    FontPack,
+    /// for when backing up or restoring saves fails
+    Backup,
+    /// for when a hook command fails
+    Hook,
+    /// for when a program has no settings in the config
+    GameMissing,
+    /// for when writing the config file fails
+    ConfigWrite,
+    /// for when exporting or importing a bundle fails
+    Bundle,
+    /// for when a command line argument can not be parsed
+    ArgumentParse,
+    /// for when winetricks or protontricks fails
+    Tricks,
+    /// for when a launch would mix prefix architectures
+    ArchMismatch,
+    /// for when writing a log file fails
+    Log,
+    /// for when a launch asks for another version than the program is pinned to
+    VersionPinned,
+    /// for when a launch would downgrade or jump the Proton version of a prefix, against policy
+    PrefixMismatch,
+    /// for when installing the Steam compatibility tool fails
+    ToolInstall,
+    /// for when the fixes database can not be read
+    Fixes,
+    /// for when the Proton version or the system in use lacks what is asked for
+    Unsupported,
 }
 
 impl Display for Kind {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                Kind::Internal => "internal error",
-                Kind::Environment => "failed to read environment",
-                Kind::ConfigOpen => "failed to open config",
-                Kind::ConfigRead => "failed to read config",
-                Kind::ConfigParse => "failed to parse config",
-                Kind::ProtonDir => "failed to create Proton directory",
-                Kind::ProtonSpawn => "failed to spawn Proton",
-                Kind::ProtonWait => "failed to wait for Proton child",
-                Kind::IndexReadDir => "failed to Index",
-                Kind::VersionParse => "failed to parse version",
-                Kind::ProtonMissing => "cannot find Proton",
-                Kind::ProgramMissing => "cannot find program",
-                Kind::ProtonExit => "proton exited with",
-                Kind::ArgumentMissing => "missing command line argument",
-                Kind::JargonInternal => "jargon args internal error",
-            }
-        )
+        let id: &'static str = match self {
+            Kind::Internal => "kind-internal",
+            Kind::Environment => "kind-environment",
+            Kind::ConfigOpen => "kind-config-open",
+            Kind::ConfigRead => "kind-config-read",
+            Kind::ConfigParse => "kind-config-parse",
+            Kind::ProtonDir => "kind-proton-dir",
+            Kind::ProtonSpawn => "kind-proton-spawn",
+            Kind::ProtonWait => "kind-proton-wait",
+            Kind::IndexReadDir => "kind-index-read-dir",
+            Kind::VersionParse => "kind-version-parse",
+            Kind::ProtonMissing => "kind-proton-missing",
+            Kind::ProgramMissing => "kind-program-missing",
+            Kind::ProtonExit => "kind-proton-exit",
+            Kind::ArgumentMissing => "kind-argument-missing",
+            Kind::JargonInternal => "kind-jargon-internal",
+            Kind::UnknownCommand => "kind-unknown-command",
+            Kind::PrefixMissing => "kind-prefix-missing",
+            Kind::PrefixRead => "kind-prefix-read",
+            Kind::PrefixWrite => "kind-prefix-write",
+            Kind::PrefixExists => "kind-prefix-exists",
+            Kind::Download => "kind-download",
+            Kind::Extract => "kind-extract",
+            Kind::FontPack => "kind-font-pack",
+            Kind::Backup => "kind-backup",
+            Kind::Hook => "kind-hook",
+            Kind::GameMissing => "kind-game-missing",
+            Kind::ConfigWrite => "kind-config-write",
+            Kind::Bundle => "kind-bundle",
+            Kind::ArgumentParse => "kind-argument-parse",
+            Kind::Tricks => "kind-tricks",
+            Kind::ArchMismatch => "kind-arch-mismatch",
+            Kind::Log => "kind-log",
+            Kind::VersionPinned => "kind-version-pinned",
+            Kind::PrefixMismatch => "kind-prefix-mismatch",
+            Kind::ToolInstall => "kind-tool-install",
+            Kind::Fixes => "kind-fixes",
+            Kind::Unsupported => "kind-unsupported",
+        };
+
+        write!(f, "{}", tr(id))
     }
 }
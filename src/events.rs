@@ -0,0 +1,154 @@
+use crate::{Timing, Usage};
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+/// A function events are passed to instead of being printed, shared so it is called without
+/// holding the lock
+type Observer = Arc<dyn Fn(&ProtonEvent) + Send + Sync>;
+
+/// The observer set by `ProtonEvent::observe`
+static OBSERVER: Mutex<Option<Observer>> = Mutex::new(None);
+
+/// Something `Proton` and the setup steps around it report while they work, printed unless an
+/// observer is set with `ProtonEvent::observe`
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum ProtonEvent {
+    /// A launch is starting `program` through `build`, such as `Proton 8.0` or `Wine wine-ge-8-26`
+    Launching {
+        /// The Proton or Wine build launching the program
+        build: String,
+        /// The program launched
+        program: PathBuf,
+    },
+    /// A launch failed while starting up, with `failure` in its error output, and is retried
+    Retrying {
+        /// The line of error output showing the failure
+        failure: String,
+        /// The attempt about to start, from 1
+        attempt: u32,
+        /// How many attempts are allowed
+        retries: u32,
+    },
+    /// The Proton log was written to its file
+    LogWritten(PathBuf),
+    /// How long a launch took to reach each of its milestones, for a timed launch
+    Timed(Timing),
+    /// The resources a monitored launch used
+    Used(Usage),
+    /// A launch waits for the programs left running in its prefix
+    WaitingForPrefix,
+    /// A launch waits for the processes its program started
+    WaitingForChildren,
+    /// A launch's time limit passed and its prefix is shut down
+    TimeUp,
+    /// proton-call was interrupted and the launch's prefix is shut down
+    Interrupted,
+    /// A program did not exit after a forwarded signal and is killed
+    Killing,
+    /// A release is downloaded
+    Downloading(String),
+    /// A tarball is extracted
+    Extracting(PathBuf),
+    /// A save sync hook runs `command`
+    SyncingSaves(String),
+    /// Verbs are installed with protontricks for a Steam app
+    Protontricks {
+        /// The Steam app's ID
+        appid: u32,
+        /// The verbs installed
+        verbs: Vec<String>,
+    },
+    /// Verbs are installed with winetricks in a prefix
+    Winetricks {
+        /// The verbs installed
+        verbs: Vec<String>,
+        /// The prefix's name
+        prefix: String,
+    },
+}
+
+impl ProtonEvent {
+    /// Passes every event from now on to `observer` instead of printing it, for frontends to
+    /// show them their own way
+    ///
+    /// The observer may emit events or replace itself, it is not called with the lock held.
+    pub fn observe(observer: impl Fn(&ProtonEvent) + Send + Sync + 'static) {
+        if let Ok(mut current) = OBSERVER.lock() {
+            *current = Some(Arc::new(observer));
+        }
+    }
+
+    /// Passes the event to the observer, or prints it, to standard error if it reports trouble
+    pub(crate) fn emit(self) {
+        let observer: Option<Observer> = OBSERVER.lock().ok().and_then(|o| o.clone());
+
+        if let Some(observer) = observer {
+            observer(&self);
+            return;
+        }
+
+        match self {
+            ProtonEvent::Retrying { .. } | ProtonEvent::Interrupted | ProtonEvent::Killing => {
+                eprintln!("{}", self);
+            }
+            _ => println!("{}", self),
+        }
+    }
+}
+
+impl Display for ProtonEvent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProtonEvent::Launching { build, program } => {
+                write!(f, "Running {} for {}", build, program.to_string_lossy())
+            }
+            ProtonEvent::Retrying {
+                failure,
+                attempt,
+                retries,
+            } => write!(
+                f,
+                "warning: launch failed while starting up: {}\nretrying, attempt {} of {}",
+                failure, attempt, retries
+            ),
+            ProtonEvent::LogWritten(file) => {
+                write!(f, "Proton log written to {}", file.to_string_lossy())
+            }
+            ProtonEvent::Timed(timing) => write!(f, "{}", timing),
+            ProtonEvent::Used(usage) => write!(f, "{}", usage),
+            ProtonEvent::WaitingForPrefix => {
+                write!(
+                    f,
+                    "Waiting for the programs left running in the prefix to exit"
+                )
+            }
+            ProtonEvent::WaitingForChildren => {
+                write!(f, "Waiting for the processes the program started to exit")
+            }
+            ProtonEvent::TimeUp => {
+                write!(
+                    f,
+                    "Time is up, shutting down the programs running in the prefix"
+                )
+            }
+            ProtonEvent::Interrupted => write!(
+                f,
+                "Interrupted, shutting down the programs running in the prefix"
+            ),
+            ProtonEvent::Killing => write!(f, "The program did not exit, killing it"),
+            ProtonEvent::Downloading(name) => write!(f, "Downloading {}", name),
+            ProtonEvent::Extracting(tarball) => {
+                write!(f, "Extracting {}", tarball.to_string_lossy())
+            }
+            ProtonEvent::SyncingSaves(command) => write!(f, "Syncing saves: {}", command),
+            ProtonEvent::Protontricks { appid, verbs } => {
+                write!(f, "Running protontricks {} {}", appid, verbs.join(" "))
+            }
+            ProtonEvent::Winetricks { verbs, prefix } => {
+                write!(f, "Running winetricks {} in '{}'", verbs.join(" "), prefix)
+            }
+        }
+    }
+}
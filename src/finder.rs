@@ -0,0 +1,166 @@
+use std::cmp::Reverse;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+
+/// How deep below a game root to look for executables
+const MAX_DEPTH: usize = 5;
+
+/// Directories which only hold installers, runtimes or tools, skipped while searching
+static IGNORED: &[&str] = &[
+    "_commonredist",
+    "commonredist",
+    "redist",
+    "redistributables",
+    "directx",
+    "vcredist",
+    "dotnet",
+    "support",
+    "installers",
+    "pfx",
+];
+
+/// Prefixes of directory names in `common` which are not games
+static TOOLS: &[&str] = &["proton ", "steam linux runtime", "steamworks"];
+
+/// A found executable and how well it matched the query
+#[derive(Debug, Clone)]
+pub struct Found {
+    path: PathBuf,
+    score: usize,
+}
+
+impl Found {
+    #[must_use]
+    /// Returns the path to the executable
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// Finder type to fuzzily search game directories for executables
+#[derive(Debug, Clone, Default)]
+pub struct Finder {
+    found: Vec<Found>,
+}
+
+impl Finder {
+    #[must_use]
+    /// Searches every directory in `roots` for executables matching `query`, best match first
+    ///
+    /// A query matches when its letters appear in order in the executable's name or the
+    /// directory it is installed in, so `sse` finds `SkyrimSE.exe`.
+    pub fn search(roots: &[PathBuf], query: &str) -> Finder {
+        let query: String = normalize(query);
+        let mut finder: Finder = Finder::default();
+
+        for root in roots {
+            finder.scan(root, &query, 0);
+        }
+
+        finder
+            .found
+            .sort_by_key(|f| (Reverse(f.score), f.path.clone()));
+        finder
+    }
+
+    #[must_use]
+    /// Returns the matches, best first
+    pub fn found(&self) -> &[Found] {
+        &self.found
+    }
+
+    /// Keeps only the best `n` matches
+    pub fn truncate(&mut self, n: usize) {
+        self.found.truncate(n);
+    }
+
+    #[must_use]
+    /// Returns true if nothing matched
+    pub fn is_empty(&self) -> bool {
+        self.found.is_empty()
+    }
+
+    /// Looks through `dir` for executables matching `query`
+    fn scan(&mut self, dir: &Path, query: &str, depth: usize) {
+        for entry in dir.read_dir().into_iter().flatten().flatten() {
+            let path: PathBuf = entry.path();
+            let name: String = entry.file_name().to_string_lossy().to_lowercase();
+
+            if path.is_dir() {
+                let skipped: bool = IGNORED.contains(&name.as_str())
+                    || (depth == 0 && TOOLS.iter().any(|t| name.starts_with(t)));
+
+                if depth < MAX_DEPTH && !skipped {
+                    self.scan(&path, query, depth + 1);
+                }
+            } else if matches!(path.extension(), Some(e) if e.eq_ignore_ascii_case("exe")) {
+                if let Some(score) = score(&path, query) {
+                    self.found.push(Found { path, score });
+                }
+            }
+        }
+    }
+}
+
+impl Display for Finder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Found {} executables:", self.found.len())?;
+
+        for (i, found) in self.found.iter().enumerate() {
+            write!(f, "\n{:>3}  {}", i + 1, found.path.to_string_lossy())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Scores how well `path` matches `query`, or `None` if it does not match
+///
+/// Matches in the executable's own name, and consecutive letters, score higher.
+fn score(path: &Path, query: &str) -> Option<usize> {
+    let name: String = normalize(&path.file_stem()?.to_string_lossy());
+    let dir: String = path
+        .parent()
+        .and_then(Path::file_name)
+        .map(|d| normalize(&d.to_string_lossy()))
+        .unwrap_or_default();
+
+    let in_name: Option<usize> = subsequence(&name, query).map(|s| s * 2);
+    let in_dir: Option<usize> = subsequence(&dir, query);
+
+    in_name.max(in_dir)
+}
+
+/// Scores `query` as a subsequence of `text`, counting consecutive letters extra
+fn subsequence(text: &str, query: &str) -> Option<usize> {
+    let mut score: usize = 0;
+    let mut last: Option<usize> = None;
+    let mut chars = text.char_indices();
+
+    for q in query.chars() {
+        let (i, _) = chars.find(|(_, c)| *c == q)?;
+        score += if matches!(last, Some(l) if l + 1 == i) {
+            3
+        } else {
+            1
+        };
+        last = Some(i);
+    }
+
+    // Exact and prefix matches beat scattered ones
+    if text == query {
+        score += 10;
+    } else if text.starts_with(query) {
+        score += 5;
+    }
+
+    Some(score)
+}
+
+/// Lowercases and strips everything but letters and digits, for loose matching
+fn normalize(s: &str) -> String {
+    s.chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
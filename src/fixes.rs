@@ -0,0 +1,356 @@
+use crate::error::{Error, Kind};
+use crate::util::{cache_dir, data_dir, fetch, file_hash};
+use crate::{pass, throw, Warnings};
+use lliw::{Fg, Reset};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+
+/// What a game needs to run well, applied before it is launched
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Fix {
+    #[serde(default)]
+    verbs: Vec<String>,
+    #[serde(default)]
+    env: BTreeMap<String, String>,
+    #[serde(default)]
+    overrides: BTreeMap<String, String>,
+}
+
+impl Fix {
+    #[must_use]
+    /// Returns the winetricks verbs to install in the prefix once
+    pub fn verbs(&self) -> &[String] {
+        &self.verbs
+    }
+
+    #[must_use]
+    /// Returns the variables to launch with
+    pub fn env(&self) -> &BTreeMap<String, String> {
+        &self.env
+    }
+
+    #[must_use]
+    /// Returns the DLL overrides to launch with, by DLL name
+    pub fn overrides(&self) -> &BTreeMap<String, String> {
+        &self.overrides
+    }
+
+    #[must_use]
+    /// Returns the overrides in the form of `WINEDLLOVERRIDES`, `None` without any
+    pub fn dll_overrides(&self) -> Option<String> {
+        if self.overrides.is_empty() {
+            return None;
+        }
+
+        let overrides: Vec<String> = self
+            .overrides
+            .iter()
+            .map(|(dll, mode)| format!("{}={}", dll, mode))
+            .collect();
+
+        Some(overrides.join(";"))
+    }
+
+    /// Adds the verbs, variables and overrides of `other`, which win over this fix's own
+    fn merge(&mut self, other: &Fix) {
+        for verb in &other.verbs {
+            if !self.verbs.contains(verb) {
+                self.verbs.push(verb.clone());
+            }
+        }
+
+        self.env.extend(other.env.clone());
+        self.overrides.extend(other.overrides.clone());
+    }
+}
+
+impl Display for Fix {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if !self.verbs.is_empty() {
+            writeln!(f, "verbs:    {}", self.verbs.join(" "))?;
+        }
+
+        for (key, value) in &self.env {
+            writeln!(f, "env:      {}={}", key, value)?;
+        }
+
+        for (dll, mode) in &self.overrides {
+            writeln!(f, "override: {}={}", dll, mode)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// One file of the fixes database
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FixFile {
+    #[serde(default)]
+    apps: BTreeMap<String, Fix>,
+    #[serde(default)]
+    exes: BTreeMap<String, Fix>,
+}
+
+/// Local database of per-game fixes, read from the TOML files in proton-call's `fixes` data
+/// directory, each with `[apps.<appid>]` and `[exes.<hash>]` tables
+#[derive(Debug, Clone, Default)]
+pub struct Fixes {
+    apps: BTreeMap<u32, Fix>,
+    exes: BTreeMap<String, Fix>,
+}
+
+impl Fixes {
+    /// Returns the directory the database is read from
+    ///
+    /// # Errors
+    ///
+    /// Will fail if the data directory can not be found or created
+    pub fn dir() -> Result<PathBuf, Error> {
+        data_dir("fixes")
+    }
+
+    /// Reads every file of the database, in order of name so later files win
+    ///
+    /// # Errors
+    ///
+    /// Will fail if a file can not be read or parsed, or an app id is not a number
+    pub fn open() -> Result<Fixes, Error> {
+        let mut database: Fixes = Fixes::default();
+        for file in Fixes::files()? {
+            database.add(&file)?;
+        }
+
+        pass!(database)
+    }
+
+    /// Reads every file of the database like `Fixes::open`, skipping files which can not be
+    /// read or parsed with a warning, so one broken file does not stop a launch
+    ///
+    /// # Errors
+    ///
+    /// Will fail if the data directory can not be found or created
+    pub fn open_skipping() -> Result<Fixes, Error> {
+        let mut database: Fixes = Fixes::default();
+        for file in Fixes::files()? {
+            if let Err(e) = database.add(&file) {
+                Warnings::push("fixes", format!("{}, the file is skipped", e));
+            }
+        }
+
+        pass!(database)
+    }
+
+    /// Returns the files of the database, in order of name
+    fn files() -> Result<Vec<PathBuf>, Error> {
+        let mut files: Vec<PathBuf> = Fixes::dir()?
+            .read_dir()
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|e| e == "toml"))
+            .collect();
+        files.sort();
+
+        pass!(files)
+    }
+
+    /// Reads one file of the database into it
+    fn add(&mut self, file: &Path) -> Result<(), Error> {
+        let text: String = match std::fs::read_to_string(file) {
+            Ok(t) => t,
+            Err(e) => throw!(Kind::Fixes, "{}: {}", file.to_string_lossy(), e),
+        };
+
+        self.add_text(file, &text)
+    }
+
+    /// Parses the text of a database file at `file` into it
+    fn add_text(&mut self, file: &Path, text: &str) -> Result<(), Error> {
+        let parsed: FixFile = match toml::from_str(text) {
+            Ok(p) => p,
+            Err(e) => throw!(Kind::Fixes, "{}: {}", file.to_string_lossy(), e),
+        };
+
+        // App ids are all checked before merging, so a file in error adds nothing
+        let mut apps: Vec<(u32, Fix)> = Vec::new();
+        for (appid, fix) in parsed.apps {
+            match appid.parse() {
+                Ok(id) => apps.push((id, fix)),
+                Err(_) => throw!(
+                    Kind::Fixes,
+                    "{}: app id '{}' is not a number",
+                    file.to_string_lossy(),
+                    appid
+                ),
+            }
+        }
+
+        for (appid, fix) in apps {
+            self.apps.entry(appid).or_default().merge(&fix);
+        }
+
+        for (hash, fix) in parsed.exes {
+            self.exes
+                .entry(hash.to_ascii_lowercase())
+                .or_default()
+                .merge(&fix);
+        }
+
+        pass!()
+    }
+
+    #[must_use]
+    /// Returns the key `program` is found by in `[exes]` tables, a hash of its contents
+    pub fn key(program: &Path) -> Option<String> {
+        file_hash(program).ok().map(|hash| format!("{:016x}", hash))
+    }
+
+    #[must_use]
+    /// Finds the fix for the Steam app `appid` and the executable `program`, the executable's
+    /// winning where both have one
+    pub fn find(&self, appid: Option<u32>, program: &Path) -> Option<Fix> {
+        let app: Option<&Fix> = appid.and_then(|id| self.apps.get(&id));
+
+        // Executables are only hashed when there are fixes to find by hash
+        let exe: Option<&Fix> = if self.exes.is_empty() {
+            None
+        } else {
+            Fixes::key(program).and_then(|key| self.exes.get(&key))
+        };
+
+        match (app, exe) {
+            (None, None) => None,
+            (app, exe) => {
+                let mut fix: Fix = app.cloned().unwrap_or_default();
+                if let Some(exe) = exe {
+                    fix.merge(exe);
+                }
+                Some(fix)
+            }
+        }
+    }
+}
+
+/// A shared fix file to import into the fixes database, as a file of its own
+#[derive(Debug, Clone)]
+pub struct FixImport {
+    dest: PathBuf,
+    before: String,
+    after: String,
+}
+
+impl FixImport {
+    /// Reads a shared fix file from a path or an `http(s)` URL to import as `name`, by default
+    /// the file name of the source, checking it parses first
+    ///
+    /// # Errors
+    ///
+    /// Will fail if the file can not be read or downloaded, or it does not parse
+    pub fn new(source: &str, name: Option<&str>) -> Result<FixImport, Error> {
+        let name: String = name
+            .unwrap_or_else(|| source.rsplit('/').next().unwrap_or(source))
+            .trim_end_matches(".toml")
+            .chars()
+            .map(|c| match c {
+                'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '_' => c,
+                _ => '-',
+            })
+            .collect();
+
+        if name.is_empty() {
+            throw!(Kind::ArgumentMissing, "a name for the fix, use --name");
+        }
+
+        let path: PathBuf = if source.starts_with("http://") || source.starts_with("https://") {
+            let download: PathBuf = cache_dir("fixes")?.join(format!("{}.toml", name));
+            let _ = std::fs::remove_file(&download);
+            fetch(source, &download)?;
+            download
+        } else {
+            PathBuf::from(source)
+        };
+
+        let after: String = match std::fs::read_to_string(&path) {
+            Ok(t) => t,
+            Err(e) => throw!(Kind::Fixes, "{}: {}", path.to_string_lossy(), e),
+        };
+        Fixes::default().add_text(&path, &after)?;
+
+        let dest: PathBuf = Fixes::dir()?.join(format!("{}.toml", name));
+        let before: String = std::fs::read_to_string(&dest).unwrap_or_default();
+
+        pass!(FixImport {
+            dest,
+            before,
+            after,
+        })
+    }
+
+    #[must_use]
+    /// Returns where the fix file is imported to
+    pub fn dest(&self) -> &Path {
+        &self.dest
+    }
+
+    #[must_use]
+    /// Tells whether the database already holds the file as it is
+    pub fn is_unchanged(&self) -> bool {
+        self.before == self.after
+    }
+
+    #[must_use]
+    /// Returns the lines the import removes and adds, against the file imported before under
+    /// the same name
+    pub fn diff(&self) -> String {
+        let before: Vec<&str> = self.before.lines().collect();
+        let after: Vec<&str> = self.after.lines().collect();
+
+        // Longest common subsequence of lines, from the end so the diff reads from the start
+        let mut common: Vec<Vec<usize>> = vec![vec![0; after.len() + 1]; before.len() + 1];
+        for i in (0..before.len()).rev() {
+            for j in (0..after.len()).rev() {
+                common[i][j] = if before[i] == after[j] {
+                    common[i + 1][j + 1] + 1
+                } else {
+                    common[i + 1][j].max(common[i][j + 1])
+                };
+            }
+        }
+
+        let mut lines: Vec<String> = Vec::new();
+        let (mut i, mut j): (usize, usize) = (0, 0);
+        while i < before.len() || j < after.len() {
+            if i < before.len() && j < after.len() && before[i] == after[j] {
+                lines.push(format!("  {}", before[i]));
+                i += 1;
+                j += 1;
+            } else if j < after.len() && (i == before.len() || common[i][j + 1] >= common[i + 1][j])
+            {
+                lines.push(format!("{}+ {}{}", Fg::Green, after[j], Reset));
+                j += 1;
+            } else {
+                lines.push(format!("{}- {}{}", Fg::Red, before[i], Reset));
+                i += 1;
+            }
+        }
+
+        lines.join("\n")
+    }
+
+    /// Writes the fix file into the database
+    ///
+    /// # Errors
+    ///
+    /// Will fail if the file can not be written
+    pub fn write(&self) -> Result<(), Error> {
+        if let Err(e) = std::fs::write(&self.dest, &self.after) {
+            throw!(Kind::Fixes, "{}: {}", self.dest.to_string_lossy(), e);
+        }
+
+        pass!()
+    }
+}
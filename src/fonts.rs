@@ -0,0 +1,273 @@
+use crate::error::{Error, Kind};
+use crate::registry::RegFile;
+use crate::util::{cache_dir, fetch, run};
+use crate::{pass, throw, Prefix};
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::str::FromStr;
+
+/// Mirror of the Microsoft core fonts archives, as used by winetricks
+static COREFONTS_URL: &str = "https://github.com/pushcx/corefonts/raw/master";
+
+/// Core fonts archives and the fonts each contains, with their registry names
+static COREFONTS: &[(&str, &[(&str, &str)])] = &[
+    ("andale32.exe", &[("andalemo.ttf", "Andale Mono")]),
+    (
+        "arial32.exe",
+        &[
+            ("arial.ttf", "Arial"),
+            ("arialbd.ttf", "Arial Bold"),
+            ("arialbi.ttf", "Arial Bold Italic"),
+            ("ariali.ttf", "Arial Italic"),
+        ],
+    ),
+    ("arialb32.exe", &[("ariblk.ttf", "Arial Black")]),
+    (
+        "comic32.exe",
+        &[
+            ("comic.ttf", "Comic Sans MS"),
+            ("comicbd.ttf", "Comic Sans MS Bold"),
+        ],
+    ),
+    (
+        "courie32.exe",
+        &[
+            ("cour.ttf", "Courier New"),
+            ("courbd.ttf", "Courier New Bold"),
+            ("courbi.ttf", "Courier New Bold Italic"),
+            ("couri.ttf", "Courier New Italic"),
+        ],
+    ),
+    (
+        "georgi32.exe",
+        &[
+            ("georgia.ttf", "Georgia"),
+            ("georgiab.ttf", "Georgia Bold"),
+            ("georgiai.ttf", "Georgia Italic"),
+            ("georgiaz.ttf", "Georgia Bold Italic"),
+        ],
+    ),
+    ("impact32.exe", &[("impact.ttf", "Impact")]),
+    (
+        "times32.exe",
+        &[
+            ("times.ttf", "Times New Roman"),
+            ("timesbd.ttf", "Times New Roman Bold"),
+            ("timesbi.ttf", "Times New Roman Bold Italic"),
+            ("timesi.ttf", "Times New Roman Italic"),
+        ],
+    ),
+    (
+        "trebuc32.exe",
+        &[
+            ("trebuc.ttf", "Trebuchet MS"),
+            ("trebucbd.ttf", "Trebuchet MS Bold"),
+            ("trebucbi.ttf", "Trebuchet MS Bold Italic"),
+            ("trebucit.ttf", "Trebuchet MS Italic"),
+        ],
+    ),
+    (
+        "verdan32.exe",
+        &[
+            ("verdana.ttf", "Verdana"),
+            ("verdanab.ttf", "Verdana Bold"),
+            ("verdanai.ttf", "Verdana Italic"),
+            ("verdanaz.ttf", "Verdana Bold Italic"),
+        ],
+    ),
+    ("webdin32.exe", &[("webdings.ttf", "Webdings")]),
+];
+
+/// Noto Sans CJK collection, which covers Japanese, Chinese and Korean
+static CJK_URL: &str =
+    "https://github.com/notofonts/noto-cjk/raw/main/Sans/OTC/NotoSansCJK-Regular.ttc";
+
+/// File name of the CJK font collection
+static CJK_FONT: &str = "NotoSansCJK-Regular.ttc";
+
+/// Face in the CJK collection used as the fallback
+static CJK_FACE: &str = "Noto Sans CJK JP";
+
+/// UI fonts which get the CJK collection linked as a fallback
+static CJK_LINKED: &[&str] = &[
+    "Tahoma",
+    "Microsoft Sans Serif",
+    "MS Sans Serif",
+    "Segoe UI",
+    "Arial",
+    "Lucida Sans Unicode",
+];
+
+/// Commonly requested CJK fonts, substituted with the CJK collection
+static CJK_SUBSTITUTES: &[&str] = &[
+    "MS Gothic",
+    "MS PGothic",
+    "MS UI Gothic",
+    "MS Mincho",
+    "MS PMincho",
+    "Meiryo",
+    "Meiryo UI",
+    "Yu Gothic",
+    "SimSun",
+    "NSimSun",
+    "SimHei",
+    "Microsoft YaHei",
+    "MingLiU",
+    "PMingLiU",
+    "Microsoft JhengHei",
+    "Gulim",
+    "Dotum",
+    "Batang",
+    "Malgun Gothic",
+];
+
+/// Font packs which can be installed into a prefix
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum FontPack {
+    /// Microsoft's core fonts for the web
+    CoreFonts,
+    /// CJK fallback fonts, fixing missing glyphs in Japanese, Chinese and Korean text
+    Cjk,
+}
+
+impl FontPack {
+    /// Installs the fonts into `prefix`, returning a registry file registering them
+    ///
+    /// The registry file should be imported with `regedit` inside the prefix.
+    /// Downloaded archives are cached and shared between prefixes.
+    ///
+    /// # Errors
+    ///
+    /// Will fail if...
+    /// * The prefix has not been initialized
+    /// * Downloading or extracting the fonts fails
+    /// * Copying the fonts into the prefix fails
+    pub fn install(self, prefix: &Prefix) -> Result<PathBuf, Error> {
+        if !prefix.is_initialized() {
+            throw!(
+                Kind::PrefixRead,
+                "'{}' has not been initialized, run `prefix create` first",
+                prefix.name()
+            );
+        }
+
+        let fonts: PathBuf = prefix.pfx().join("drive_c/windows/Fonts");
+        let mut entries: Vec<(String, String)> = Vec::new();
+        let mut reg: RegFile = RegFile::new();
+
+        match self {
+            FontPack::CoreFonts => {
+                let cache: PathBuf = cache_dir("fonts")?;
+                let extracted: PathBuf = cache.join("corefonts");
+
+                for (archive, files) in COREFONTS {
+                    let archive_path: PathBuf = cache.join(archive);
+                    fetch(&format!("{}/{}", COREFONTS_URL, archive), &archive_path)?;
+
+                    if !files.iter().all(|(f, _)| extracted.join(f).exists()) {
+                        cabextract(&archive_path, &extracted)?;
+                    }
+
+                    for (file, name) in *files {
+                        copy_font(&extracted.join(file), &fonts)?;
+                        entries.push((format!("{} (TrueType)", name), (*file).to_string()));
+                    }
+                }
+            }
+            FontPack::Cjk => {
+                let font: PathBuf = cache_dir("fonts")?.join(CJK_FONT);
+                fetch(CJK_URL, &font)?;
+                copy_font(&font, &fonts)?;
+
+                entries.push((format!("{} (TrueType)", CJK_FACE), CJK_FONT.to_string()));
+                cjk_links(&mut reg);
+            }
+        }
+
+        register_fonts(&mut reg, &entries);
+
+        let path: PathBuf = cache_dir("fonts")?.join(format!("{}.reg", self));
+        reg.write(&path)?;
+
+        pass!(path)
+    }
+}
+
+impl Display for FontPack {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FontPack::CoreFonts => write!(f, "corefonts"),
+            FontPack::Cjk => write!(f, "cjk"),
+        }
+    }
+}
+
+impl FromStr for FontPack {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "corefonts" => pass!(FontPack::CoreFonts),
+            "cjk" => pass!(FontPack::Cjk),
+            _ => throw!(Kind::FontPack, "'{}'", s),
+        }
+    }
+}
+
+/// Extracts the fonts from a cabinet archive into `dir` with lowercase names
+fn cabextract(archive: &Path, dir: &Path) -> Result<(), Error> {
+    run(
+        Command::new("cabextract")
+            .args(["-q", "-L", "-F", "*.ttf", "-d"])
+            .arg(dir)
+            .arg(archive),
+        Kind::Extract,
+    )
+}
+
+/// Copies a font file into the prefix's font directory
+fn copy_font(font: &Path, fonts: &Path) -> Result<(), Error> {
+    if let Err(e) = std::fs::create_dir_all(fonts) {
+        throw!(Kind::PrefixWrite, "{}: {}", fonts.to_string_lossy(), e);
+    }
+
+    if let Some(name) = font.file_name() {
+        if let Err(e) = std::fs::copy(font, fonts.join(name)) {
+            throw!(Kind::PrefixWrite, "{}: {}", font.to_string_lossy(), e);
+        }
+    }
+
+    pass!()
+}
+
+/// Adds the font link and substitute registry entries for the CJK collection
+fn cjk_links(reg: &mut RegFile) {
+    let link: String = format!("{},{}", CJK_FONT, CJK_FACE);
+
+    reg.key(
+        "HKEY_LOCAL_MACHINE\\Software\\Microsoft\\Windows NT\\CurrentVersion\\FontLink\\SystemLink",
+    );
+    for font in CJK_LINKED {
+        reg.multi_string(font, &[&link]);
+    }
+
+    reg.key("HKEY_LOCAL_MACHINE\\Software\\Microsoft\\Windows NT\\CurrentVersion\\FontSubstitutes");
+    for font in CJK_SUBSTITUTES {
+        reg.string(font, CJK_FACE);
+    }
+}
+
+/// Adds registry entries registering `entries` as installed fonts
+fn register_fonts(reg: &mut RegFile, entries: &[(String, String)]) {
+    for key in ["Windows NT", "Windows"] {
+        reg.key(&format!(
+            "HKEY_LOCAL_MACHINE\\Software\\Microsoft\\{}\\CurrentVersion\\Fonts",
+            key
+        ));
+
+        for (name, file) in entries {
+            reg.string(name, file);
+        }
+    }
+}
@@ -0,0 +1,364 @@
+use crate::error::{Error, Kind};
+use crate::util::{shell_quote, xdg_base};
+use crate::{pass, throw, Index, Prefix, Version};
+use std::fmt::{Display, Formatter, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Name of the stamp recording which Proton an imported prefix should run with
+pub(crate) static IMPORTED: &str = "proton-call-imported";
+
+/// Other launchers whose prefixes can be imported
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Tool {
+    /// Bottles, native or from Flatpak
+    Bottles,
+    /// Lutris Wine games
+    Lutris,
+    /// Heroic Games Launcher, for Epic and GOG games, native or from Flatpak
+    Heroic,
+}
+
+impl Tool {
+    /// Returns the directories the tool keeps its prefixes or game configs in
+    fn dirs(self) -> Vec<PathBuf> {
+        let data: Option<PathBuf> = xdg_base("XDG_DATA_HOME", ".local/share");
+        let home: Option<PathBuf> = std::env::var("HOME").ok().map(PathBuf::from);
+
+        match self {
+            Tool::Bottles => vec![
+                data.map(|d| d.join("bottles/bottles")),
+                home.map(|h| h.join(".var/app/com.usebottles.bottles/data/bottles/bottles")),
+            ],
+            Tool::Lutris => vec![
+                data.map(|d| d.join("lutris/games")),
+                xdg_base("XDG_CONFIG_HOME", ".config").map(|c| c.join("lutris/games")),
+            ],
+            Tool::Heroic => vec![
+                xdg_base("XDG_CONFIG_HOME", ".config").map(|c| c.join("heroic/GamesConfig")),
+                home.map(|h| {
+                    h.join(".var/app/com.heroicgameslauncher.hgl/config/heroic/GamesConfig")
+                }),
+            ],
+        }
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+}
+
+impl Display for Tool {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Tool::Bottles => write!(f, "bottles"),
+            Tool::Lutris => write!(f, "lutris"),
+            Tool::Heroic => write!(f, "heroic"),
+        }
+    }
+}
+
+impl FromStr for Tool {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "bottles" => pass!(Tool::Bottles),
+            "lutris" => pass!(Tool::Lutris),
+            "heroic" => pass!(Tool::Heroic),
+            _ => throw!(
+                Kind::ArgumentParse,
+                "'{}' is not bottles, lutris or heroic",
+                s
+            ),
+        }
+    }
+}
+
+/// A Wine prefix set up by another launcher
+#[derive(Debug, Clone)]
+pub struct ForeignPrefix {
+    tool: Tool,
+    name: String,
+    pfx: PathBuf,
+    runner: Option<String>,
+}
+
+impl ForeignPrefix {
+    #[must_use]
+    /// Finds every prefix set up by `tool`, sorted by name
+    pub fn discover(tool: Tool) -> Vec<ForeignPrefix> {
+        let mut found: Vec<ForeignPrefix> = Vec::new();
+
+        for dir in tool.dirs() {
+            for entry in dir.read_dir().into_iter().flatten().flatten() {
+                let path: PathBuf = entry.path();
+
+                let prefix: Option<ForeignPrefix> = match tool {
+                    Tool::Bottles => ForeignPrefix::bottle(&path),
+                    Tool::Lutris => ForeignPrefix::lutris(&path),
+                    Tool::Heroic => ForeignPrefix::heroic(&path),
+                };
+
+                if let Some(p) = prefix {
+                    if !found.iter().any(|f| f.pfx == p.pfx) {
+                        found.push(p);
+                    }
+                }
+            }
+        }
+
+        found.sort_by(|a, b| a.name.cmp(&b.name));
+        found
+    }
+
+    /// Reads a bottle, which keeps `bottle.yml` next to `drive_c`
+    fn bottle(path: &Path) -> Option<ForeignPrefix> {
+        let text: String = std::fs::read_to_string(path.join("bottle.yml")).ok()?;
+
+        if !path.join("drive_c").is_dir() {
+            return None;
+        }
+
+        Some(ForeignPrefix {
+            tool: Tool::Bottles,
+            name: path.file_name()?.to_string_lossy().to_string(),
+            pfx: path.to_path_buf(),
+            runner: yaml_value(&text, None, "Runner"),
+        })
+    }
+
+    /// Reads a Lutris game config, named `<slug>-<timestamp>.yml`
+    fn lutris(path: &Path) -> Option<ForeignPrefix> {
+        if path.extension()? != "yml" {
+            return None;
+        }
+
+        let text: String = std::fs::read_to_string(path).ok()?;
+        let pfx: PathBuf = PathBuf::from(yaml_value(&text, Some("game"), "prefix")?);
+
+        if !pfx.join("drive_c").is_dir() {
+            return None;
+        }
+
+        let stem: String = path.file_stem()?.to_string_lossy().to_string();
+        let name: &str = match stem.rsplit_once('-') {
+            Some((slug, stamp)) if stamp.chars().all(|c| c.is_ascii_digit()) => slug,
+            _ => &stem,
+        };
+
+        Some(ForeignPrefix {
+            tool: Tool::Lutris,
+            name: name.to_string(),
+            pfx,
+            runner: yaml_value(&text, Some("wine"), "version"),
+        })
+    }
+
+    /// Reads a Heroic game config, `<app name>.json` holding the settings under the app name
+    ///
+    /// Proton prefixes in Heroic are compat data directories, while Wine ones are plain prefixes.
+    fn heroic(path: &Path) -> Option<ForeignPrefix> {
+        if path.extension()? != "json" {
+            return None;
+        }
+
+        let text: String = std::fs::read_to_string(path).ok()?;
+        let json: serde_json::Value = serde_json::from_str(&text).ok()?;
+        let settings: &serde_json::Value = json.get(path.file_stem()?.to_str()?)?;
+
+        let prefix: PathBuf = PathBuf::from(settings.get("winePrefix")?.as_str()?);
+        let wine: Option<&serde_json::Value> = settings.get("wineVersion");
+
+        let pfx: PathBuf = match wine.and_then(|w| w.get("type")?.as_str()) {
+            Some("proton") => prefix.join("pfx"),
+            _ => prefix.clone(),
+        };
+
+        if !pfx.join("drive_c").is_dir() {
+            return None;
+        }
+
+        Some(ForeignPrefix {
+            tool: Tool::Heroic,
+            // Heroic names prefixes after the game's title, unlike its config files
+            name: prefix.file_name()?.to_string_lossy().to_string(),
+            pfx,
+            runner: wine
+                .and_then(|w| w.get("name")?.as_str())
+                .map(str::to_string),
+        })
+    }
+
+    #[must_use]
+    /// Returns the launcher which set up the prefix
+    pub fn tool(&self) -> Tool {
+        self.tool
+    }
+
+    #[must_use]
+    /// Returns the name the prefix has in its launcher
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[must_use]
+    /// Returns the path to the Wine prefix
+    pub fn pfx(&self) -> &Path {
+        &self.pfx
+    }
+
+    #[must_use]
+    /// Returns the Wine or Proton build the launcher runs the prefix with
+    pub fn runner(&self) -> Option<&str> {
+        self.runner.as_deref()
+    }
+
+    #[must_use]
+    /// Maps the launcher's runner onto an indexed Proton version
+    ///
+    /// Runners such as `soda-7.0-9` match that exact version, while GE builds such as
+    /// `GE-Proton8-26` or `wine-ge-8-26` match the newest indexed version of the same major.
+    pub fn proton(&self, index: &Index) -> Option<Version> {
+        let runner: &str = self.runner.as_deref()?;
+
+        if runner.to_ascii_lowercase().contains("experimental") {
+            return index
+                .get(Version::Experimental)
+                .map(|_| Version::Experimental);
+        }
+
+        let numbers: Vec<u8> = runner
+            .split(|c: char| !c.is_ascii_digit())
+            .filter(|n| !n.is_empty())
+            .filter_map(|n| n.parse().ok())
+            .collect();
+
+        let major: u8 = *numbers.first()?;
+
+        if runner.contains(&format!("{}.", major)) {
+            if let Some(minor) = numbers.get(1) {
+                let exact: Version = Version::new(major, *minor);
+                if index.get(exact).is_some() {
+                    return Some(exact);
+                }
+            }
+        }
+
+        index
+            .entries()
+            .into_iter()
+            .map(|(v, _)| v)
+            .filter(|v| matches!(v, Version::Mainline(mj, _) if *mj == major))
+            .max()
+    }
+
+    /// Registers the prefix in `data` as a named prefix, linking to the existing Wine prefix
+    ///
+    /// When `version` is given, it is recorded as the Proton the prefix should run with.
+    ///
+    /// # Errors
+    ///
+    /// Will fail if a prefix with the same name exists, or the link can not be created
+    pub fn register(&self, data: &Path, version: Option<Version>) -> Result<Prefix, Error> {
+        let prefix: Prefix = Prefix::new(data.join(&self.name));
+
+        if prefix.path().exists() {
+            throw!(Kind::PrefixExists, "'{}'", self.name);
+        }
+
+        if let Err(e) = std::fs::create_dir_all(prefix.path()) {
+            throw!(
+                Kind::PrefixWrite,
+                "{}: {}",
+                prefix.path().to_string_lossy(),
+                e
+            );
+        }
+
+        if let Err(e) = std::os::unix::fs::symlink(&self.pfx, prefix.pfx()) {
+            throw!(
+                Kind::PrefixWrite,
+                "{}: {}",
+                prefix.pfx().to_string_lossy(),
+                e
+            );
+        }
+
+        if let Some(v) = version {
+            let stamp: PathBuf = prefix.path().join(IMPORTED);
+            if let Err(e) = std::fs::write(&stamp, v.to_string()) {
+                throw!(Kind::PrefixWrite, "{}: {}", stamp.to_string_lossy(), e);
+            }
+        }
+
+        pass!(prefix)
+    }
+}
+
+/// Renders a Lutris game config running `exe` in the Wine prefix `pfx`
+pub(crate) fn lutris_game(
+    exe: &Path,
+    args: &[String],
+    pfx: &Path,
+    version: &str,
+    env: &[(&str, String)],
+) -> String {
+    let mut yaml: String = String::from("game:\n");
+
+    let _ = writeln!(yaml, "  exe: {}", yaml_quote(&exe.to_string_lossy()));
+    if !args.is_empty() {
+        let args: Vec<String> = args.iter().map(|a| shell_quote(a)).collect();
+        let _ = writeln!(yaml, "  args: {}", yaml_quote(&args.join(" ")));
+    }
+    let _ = writeln!(yaml, "  prefix: {}", yaml_quote(&pfx.to_string_lossy()));
+
+    let _ = write!(yaml, "wine:\n  version: {}\n", yaml_quote(version));
+
+    if !env.is_empty() {
+        yaml.push_str("system:\n  env:\n");
+        for (name, value) in env {
+            let _ = writeln!(yaml, "    {}: {}", name, yaml_quote(value));
+        }
+    }
+
+    yaml
+}
+
+/// Quotes a string for YAML
+fn yaml_quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Reads `key` from a simple YAML file, at the top level or within the top level `section`
+fn yaml_value(text: &str, section: Option<&str>, key: &str) -> Option<String> {
+    let mut current: Option<&str> = None;
+
+    for line in text.lines() {
+        let indented: bool = line.starts_with(' ') || line.starts_with('\t');
+        let line: &str = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let (name, value): (&str, &str) = match line.split_once(':') {
+            Some((n, v)) => (n.trim(), v.trim()),
+            None => continue,
+        };
+
+        if !indented {
+            current = Some(name);
+        }
+
+        if name == key && indented == section.is_some() && current == section.or(Some(name)) {
+            let value: &str = value.trim_matches(|c| c == '"' || c == '\'');
+            return if value.is_empty() {
+                None
+            } else {
+                Some(value.to_string())
+            };
+        }
+    }
+
+    None
+}
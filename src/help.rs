@@ -0,0 +1,992 @@
+use crate::locale::tr;
+use std::fmt::{Display, Formatter};
+
+/// Column the descriptions of flags and commands start at
+const COLUMN: usize = 24;
+
+/// Width help text is wrapped at
+const WIDTH: usize = 100;
+
+/// A flag a command takes
+#[derive(Debug, Copy, Clone)]
+pub struct Flag {
+    short: Option<&'static str>,
+    long: &'static str,
+    value: Option<&'static str>,
+    about: &'static str,
+}
+
+impl Flag {
+    #[must_use]
+    /// Returns the short form, such as `-p`
+    pub fn short(&self) -> Option<&'static str> {
+        self.short
+    }
+
+    #[must_use]
+    /// Returns the long form, such as `--proton`
+    pub fn long(&self) -> &'static str {
+        self.long
+    }
+
+    #[must_use]
+    /// Returns the name of the value the flag takes, if it takes one
+    pub fn value(&self) -> Option<&'static str> {
+        self.value
+    }
+
+    #[must_use]
+    /// Returns what the flag does, translated
+    pub fn about(&self) -> &'static str {
+        tr(self.about)
+    }
+}
+
+impl Display for Flag {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut name: String = match self.short {
+            Some(short) => format!("{}, {}", short, self.long),
+            None => format!("    {}", self.long),
+        };
+
+        if let Some(value) = self.value {
+            name = format!("{} {}", name, value);
+        }
+
+        write!(f, "{}", column(&name, self.about()))
+    }
+}
+
+/// A command, with everything `proton-call help COMMAND` shows about it
+#[derive(Debug, Copy, Clone)]
+pub struct Command {
+    name: &'static str,
+    usage: &'static [&'static str],
+    about: &'static str,
+    flags: &'static [Flag],
+    examples: &'static [&'static str],
+    config: &'static [&'static str],
+}
+
+impl Command {
+    #[must_use]
+    /// Finds the command `name`
+    pub fn find(name: &str) -> Option<&'static Command> {
+        COMMANDS.iter().find(|c| c.name == name)
+    }
+
+    #[must_use]
+    /// Returns every command, the launch itself first
+    pub fn all() -> &'static [Command] {
+        COMMANDS
+    }
+
+    #[must_use]
+    /// Returns the command's name, as typed after `proton-call`
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    #[must_use]
+    /// Returns what the command does, translated
+    pub fn about(&self) -> &'static str {
+        tr(self.about)
+    }
+
+    #[must_use]
+    /// Returns the flags the command takes
+    pub fn flags(&self) -> &'static [Flag] {
+        self.flags
+    }
+
+    #[must_use]
+    /// Returns the config keys the command reads
+    pub fn config(&self) -> &'static [&'static str] {
+        self.config
+    }
+}
+
+impl Display for Command {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, usage) in self.usage.iter().enumerate() {
+            let label: &str = if i == 0 { tr("Usage:") } else { "" };
+            writeln!(
+                f,
+                "{:<width$} proton-call {}",
+                label,
+                usage,
+                width = tr("Usage:").chars().count()
+            )?;
+        }
+
+        write!(f, "\n{}", wrap(self.about()))?;
+
+        if !self.flags.is_empty() {
+            write!(f, "\n\n{}", tr("Options:"))?;
+            for flag in self.flags {
+                write!(f, "\n{}", flag)?;
+            }
+        }
+
+        if !self.examples.is_empty() {
+            write!(f, "\n\n{}", tr("Examples:"))?;
+            for example in self.examples {
+                write!(f, "\n    {}", example)?;
+            }
+        }
+
+        if !self.config.is_empty() {
+            write!(
+                f,
+                "\n\n{}\n    {}",
+                tr("Config keys:"),
+                self.config.join(", ")
+            )?;
+        }
+
+        if self.name == "config" {
+            write!(f, "\n\n{}", tr("help-config"))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The overview shown by `proton-call --help`: the launch's options and a list of commands
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Overview;
+
+impl Display for Overview {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{} proton-call [OPTIONS]... EXE [EXTRA]...\n{:<width$} proton-call COMMAND [ARGS]...",
+            tr("Usage:"),
+            "",
+            width = tr("Usage:").chars().count()
+        )?;
+
+        write!(f, "\n{}", tr("Options:"))?;
+        for flag in RUN_FLAGS.iter().chain(GENERAL_FLAGS) {
+            write!(f, "\n{}", flag)?;
+        }
+
+        write!(f, "\n\n{}", tr("Commands:"))?;
+        for command in COMMANDS {
+            write!(f, "\n{}", column(command.name, command.about()))?;
+        }
+
+        write!(f, "\n\n{}", tr("help-more"))
+    }
+}
+
+/// Wraps `text` to the width of the description column
+fn wrap(text: &str) -> String {
+    column("", text)[COLUMN..].replace(&format!("\n{}", " ".repeat(COLUMN)), "\n")
+}
+
+/// Lays out `name` and `about` in two columns, starting `about` on the next line when `name` is
+/// long and wrapping it at `WIDTH`
+fn column(name: &str, about: &str) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    let mut line: String = String::new();
+
+    for word in about.split_whitespace() {
+        if !line.is_empty() && COLUMN + line.chars().count() + 1 + word.chars().count() > WIDTH {
+            lines.push(std::mem::take(&mut line));
+        }
+
+        if !line.is_empty() {
+            line.push(' ');
+        }
+
+        line.push_str(word);
+    }
+
+    lines.push(line);
+
+    let indent: String = " ".repeat(COLUMN);
+    let first: String = if name.chars().count() + 4 < COLUMN {
+        format!("    {:<width$}{}", name, lines[0], width = COLUMN - 4)
+    } else {
+        format!("    {}\n{}{}", name, indent, lines[0])
+    };
+
+    lines[1..]
+        .iter()
+        .fold(first, |text, line| format!("{}\n{}{}", text, indent, line))
+}
+
+/// Flags which are not part of any one command
+static GENERAL_FLAGS: &[Flag] = &[
+    Flag {
+        short: Some("-h"),
+        long: "--help",
+        value: None,
+        about: "View this help message",
+    },
+    Flag {
+        short: None,
+        long: "--config",
+        value: Some("PATH"),
+        about: "Read the config from PATH instead of proton.conf, also set by PROTON_CALL_CONFIG",
+    },
+    Flag {
+        short: None,
+        long: "--data",
+        value: Some("DIR"),
+        about: "Use DIR as `data` for this run, over the config",
+    },
+    Flag {
+        short: None,
+        long: "--steam",
+        value: Some("DIR"),
+        about: "Use DIR as `steam` for this run, over the config",
+    },
+    Flag {
+        short: None,
+        long: "--common",
+        value: Some("DIR"),
+        about: "Use DIR as `common` for this run, over the config",
+    },
+    Flag {
+        short: Some("-i"),
+        long: "--index",
+        value: None,
+        about: "View an index of installed Proton versions and Wine runners",
+    },
+    Flag {
+        short: Some("-V"),
+        long: "--verbose",
+        value: None,
+        about: "Run in verbose mode",
+    },
+    Flag {
+        short: Some("-v"),
+        long: "--version",
+        value: None,
+        about: "View version information",
+    },
+];
+
+/// Flags of a launch, shared by `find` and `export-lutris`
+static RUN_FLAGS: &[Flag] = &[
+    Flag {
+        short: Some("-r"),
+        long: "--run",
+        value: Some("EXE"),
+        about: "Run EXE in proton, an alias, @N for the Nth recent program, or - to read EXE and its arguments from stdin, one per line",
+    },
+    Flag {
+        short: Some("-p"),
+        long: "--proton",
+        value: Some("VERSION"),
+        about: "Use Proton VERSION from `common`, a GE-Proton build, latest, experimental, hotfix, ge-latest, or a Wine runner such as wine-ge-8-26",
+    },
+    Flag {
+        short: Some("-c"),
+        long: "--custom",
+        value: Some("PATH"),
+        about: "Path to the proton script to use, a directory containing it, or a Proton tarball",
+    },
+    Flag {
+        short: Some("-P"),
+        long: "--prefix",
+        value: Some("NAME"),
+        about: "Use the prefix NAME in `data` instead of one per version",
+    },
+    Flag {
+        short: None,
+        long: "--appid",
+        value: Some("ID"),
+        about: "Steam app id given to the program, otherwise found or generated",
+    },
+    Flag {
+        short: Some("-e"),
+        long: "--env",
+        value: Some("KEY=VALUE"),
+        about: "Set the variable KEY to VALUE for the program, over the config and fixes, repeatable",
+    },
+    Flag {
+        short: None,
+        long: "--clean-env",
+        value: None,
+        about: "Launch with a minimal environment instead of inheriting this one",
+    },
+    Flag {
+        short: None,
+        long: "--force",
+        value: None,
+        about: "Launch even when the prefix architecture does not fit, the program is pinned to another version or the compat policy blocks it",
+    },
+    Flag {
+        short: Some("-l"),
+        long: "--log",
+        value: None,
+        about: "Pass PROTON_LOG variable to Proton",
+    },
+    Flag {
+        short: None,
+        long: "--log-file",
+        value: Some("PATH"),
+        about: "Write the Proton log to PATH instead of $HOME/steam-<appid>.log",
+    },
+    Flag {
+        short: None,
+        long: "--stdout",
+        value: Some("PATH"),
+        about: "Write the program's standard output to PATH",
+    },
+    Flag {
+        short: None,
+        long: "--stderr",
+        value: Some("PATH"),
+        about: "Write the program's standard error to PATH",
+    },
+    Flag {
+        short: None,
+        long: "--append",
+        value: None,
+        about: "With --stdout or --stderr, append to the files instead of truncating",
+    },
+    Flag {
+        short: None,
+        long: "--overlay",
+        value: None,
+        about: "Load the Steam overlay from `steam`, for screenshots and the FPS counter",
+    },
+    Flag {
+        short: None,
+        long: "--no-overlay",
+        value: None,
+        about: "Strip the Steam overlay from an inherited LD_PRELOAD",
+    },
+    Flag {
+        short: None,
+        long: "--tty",
+        value: None,
+        about: "Run on a pseudo-terminal, for interactive console programs such as server consoles",
+    },
+    Flag {
+        short: None,
+        long: "--no-tty",
+        value: None,
+        about: "Never run on a pseudo-terminal, which console programs get when launched from one",
+    },
+    Flag {
+        short: None,
+        long: "--wait-prefix",
+        value: None,
+        about: "Wait for every program in the prefix to exit, for launchers which start the game and exit",
+    },
+    Flag {
+        short: None,
+        long: "--wait-children",
+        value: None,
+        about: "Wait for every process the program started to exit, not only the program",
+    },
+    Flag {
+        short: None,
+        long: "--kill-on-exit",
+        value: None,
+        about: "Shut down every Wine process in the prefix when interrupted, not only the program",
+    },
+    Flag {
+        short: None,
+        long: "--no-eac",
+        value: None,
+        about: "Do not pass the installed EasyAntiCheat runtime to Proton",
+    },
+    Flag {
+        short: None,
+        long: "--no-battleye",
+        value: None,
+        about: "Do not pass the installed BattlEye runtime to Proton",
+    },
+    Flag {
+        short: None,
+        long: "--runtime",
+        value: Some("RUNTIME"),
+        about: "Launch through the Steam Linux Runtime sniper, soldier or none, instead of the one Proton requires",
+    },
+    Flag {
+        short: None,
+        long: "--win32",
+        value: None,
+        about: "Run through the 32-bit Wine loader of Proton, for 16 and 32-bit installers which fail otherwise",
+    },
+    Flag {
+        short: None,
+        long: "--print-env",
+        value: None,
+        about: "Print every variable the launch runs with, and where it comes from",
+    },
+    Flag {
+        short: None,
+        long: "--plan",
+        value: None,
+        about: "Print the resolved launch as a JSON plan, with its command line, environment and directories, instead of running it",
+    },
+    Flag {
+        short: None,
+        long: "--dry-run",
+        value: None,
+        about: "Show the Proton build, verb, compat data directory, command line and environment of the launch instead of running it",
+    },
+    Flag {
+        short: None,
+        long: "--detach",
+        value: None,
+        about: "Start the program and exit without waiting for it",
+    },
+    Flag {
+        short: None,
+        long: "--print-pid",
+        value: None,
+        about: "With --detach, print the process ID of the program started",
+    },
+    Flag {
+        short: None,
+        long: "--time",
+        value: None,
+        about: "Report the time to spawn, time to first output and total runtime",
+    },
+    Flag {
+        short: None,
+        long: "--monitor",
+        value: None,
+        about: "Sample the CPU, memory and GPU memory use of the launch and report it once done",
+    },
+    Flag {
+        short: None,
+        long: "--no-fixes",
+        value: None,
+        about: "Launch without the program's fix from the fixes database",
+    },
+    Flag {
+        short: None,
+        long: "--virtual-desktop",
+        value: Some("WxH"),
+        about: "Run inside a Wine virtual desktop of WxH pixels, for older programs which break on alt-tab or resolution changes",
+    },
+    Flag {
+        short: None,
+        long: "--no-crash-dialog",
+        value: None,
+        about: "Disable Wine's crash dialog, so a crashing program exits instead of waiting on a click",
+    },
+    Flag {
+        short: None,
+        long: "--allow-menus",
+        value: None,
+        about: "Let installers add menu entries and file associations to the host, which is off by default",
+    },
+    Flag {
+        short: None,
+        long: "--discord",
+        value: None,
+        about: "Bridge the host's Discord client into the launch, showing the program as played",
+    },
+    Flag {
+        short: None,
+        long: "--dxvk-async",
+        value: None,
+        about: "Compile shaders in the background, with DXVK builds which support it",
+    },
+    Flag {
+        short: None,
+        long: "--gpl",
+        value: None,
+        about: "Force DXVK's graphics pipeline library on, --no-gpl forces it off",
+    },
+    Flag {
+        short: None,
+        long: "--radv-perftest",
+        value: Some("OPTS"),
+        about: "Add comma separated options to Mesa's RADV_PERFTEST",
+    },
+    Flag {
+        short: None,
+        long: "--nvapi",
+        value: None,
+        about: "Expose NVAPI and the NVIDIA GPU so games offer DLSS, --no-nvapi hides both",
+    },
+    Flag {
+        short: None,
+        long: "--dlss-indicator",
+        value: None,
+        about: "Show DLSS' on-screen indicator, to check DLSS and frame generation are running",
+    },
+    Flag {
+        short: None,
+        long: "--wayland-native",
+        value: None,
+        about: "Run on Wine's native Wayland driver instead of XWayland, with Proton builds which ship it",
+    },
+    Flag {
+        short: None,
+        long: "--precache",
+        value: None,
+        about: "Run the program for 90 seconds, headless through gamescope if installed, to warm its shader caches",
+    },
+    Flag {
+        short: None,
+        long: "--precache-for",
+        value: Some("SECS"),
+        about: "Like --precache, for SECS seconds",
+    },
+];
+
+/// Every command, the launch itself first
+static COMMANDS: &[Command] = &[
+    Command {
+        name: "run",
+        usage: &[
+            "run EXE [OPTIONS]... [EXTRA]...",
+            "[OPTIONS]... -r EXE [EXTRA]...",
+        ],
+        about: "Run EXE through Proton, passing EXTRA to it, the default when no command is given",
+        flags: RUN_FLAGS,
+        examples: &[
+            "proton-call run foo.exe",
+            "proton-call -r foo.exe",
+            "proton-call -p 7.0 -r foo.exe --goes --to program",
+            "proton-call -p ge-latest -r foo.exe",
+            "proton-call -p GE-Proton8-26 -r foo.exe",
+            "proton-call -c GE-Proton9-20.tar.gz -r foo.exe",
+            "proton-call -P skyrim --log-file ~/logs/skyrim.log -r SkyrimSE.exe",
+            "proton-call -e DXVK_HUD=fps -e WINEDLLOVERRIDES=dinput8=n,b -r foo.exe",
+            "proton-call -r @1",
+            "find ~/Games -name '*.exe' | fzf | proton-call -r -",
+        ],
+        config: &[
+            "schema",
+            "data",
+            "steam",
+            "common",
+            "runners",
+            "retries",
+            "downgrade",
+            "major_jump",
+            "env_allow",
+            "env_deny",
+            "dxvk_async",
+            "gpl",
+            "radv_perftest",
+            "nvapi",
+            "dlss_indicator",
+            "allow_menus",
+            "discord",
+            "discord_client_id",
+            "aliases",
+            "games.NAME",
+        ],
+    },
+    Command {
+        name: "index",
+        usage: &[
+            "index [--long | --plain | --json] [--sort ORDER] [--reverse]",
+            "-i [--long | --plain | --json] [--sort ORDER] [--reverse]",
+        ],
+        about: "View an index of installed Proton versions and Wine runners",
+        flags: &[
+            Flag {
+                short: None,
+                long: "--long",
+                value: None,
+                about: "With --index, view size, install date, source and prefix use",
+            },
+            Flag {
+                short: None,
+                long: "--sort",
+                value: Some("ORDER"),
+                about: "With --index, sort by version, date or size",
+            },
+            Flag {
+                short: None,
+                long: "--plain",
+                value: None,
+                about: "With --index, print the plain listing even on a terminal",
+            },
+            Flag {
+                short: None,
+                long: "--json",
+                value: None,
+                about: "With --index, print each version's name, version, path and source as JSON",
+            },
+            Flag {
+                short: None,
+                long: "--reverse",
+                value: None,
+                about: "With --index, reverse the order",
+            },
+        ],
+        examples: &["proton-call index --long --sort date --reverse"],
+        config: &["common", "runners"],
+    },
+    Command {
+        name: "config",
+        usage: &["config show", "config init [-y] [--force]"],
+        about: "View every config value in use and the file it was read from, or whether it is a default, or write a new config from the Steam install found",
+        flags: &[
+            Flag {
+                short: Some("-y"),
+                long: "--yes",
+                value: None,
+                about: "With init, take the proposed directories without asking",
+            },
+            Flag {
+                short: None,
+                long: "--force",
+                value: None,
+                about: "With init, replace an existing config",
+            },
+        ],
+        examples: &["proton-call config show", "proton-call config init"],
+        config: &[],
+    },
+    Command {
+        name: "prefix",
+        usage: &[
+            "prefix list",
+            "prefix info NAME",
+            "prefix repair NAME [-p VERSION]",
+            "prefix create NAME [-p VERSION] [--dedup]",
+            "prefix gc [-u AGE] [-o] [-y]",
+            "prefix clean-menus NAME [-y]",
+            "prefix dedup [NAME]...",
+        ],
+        about: "Manage the prefixes in `data`: list them, view one, rebuild one with `wineboot -u` keeping drive_c, set one up without running a program, delete unused ones, remove the menu entries Wine created for one, or share identical files between them",
+        flags: &[
+            Flag {
+                short: Some("-p"),
+                long: "--proton",
+                value: Some("VERSION"),
+                about: "Use Proton VERSION from `common`",
+            },
+            Flag {
+                short: None,
+                long: "--dedup",
+                value: None,
+                about: "With create, share identical files with the other prefixes through reflinks",
+            },
+            Flag {
+                short: Some("-u"),
+                long: "--unused-for",
+                value: Some("AGE"),
+                about: "With gc, delete prefixes unused for AGE, such as 30d or 12w, instead of 90 days",
+            },
+            Flag {
+                short: Some("-o"),
+                long: "--orphans",
+                value: None,
+                about: "With gc, also delete prefixes whose Proton or program is gone",
+            },
+            Flag {
+                short: Some("-y"),
+                long: "--yes",
+                value: None,
+                about: "With gc and clean-menus, skip confirmation",
+            },
+        ],
+        examples: &[
+            "proton-call prefix list",
+            "proton-call prefix info skyrim",
+            "proton-call prefix create skyrim -p 8.0",
+            "proton-call prefix gc -u 30d -o",
+            "proton-call prefix clean-menus skyrim",
+            "proton-call prefix dedup",
+        ],
+        config: &["data", "common", "dedup"],
+    },
+    Command {
+        name: "fonts",
+        usage: &["fonts install PACK [-P NAME] [-p VERSION]"],
+        about: "Install the font PACK (corefonts, cjk) into a prefix",
+        flags: &[
+            Flag {
+                short: Some("-P"),
+                long: "--prefix",
+                value: Some("NAME"),
+                about: "Install into the prefix NAME instead of the one of the Proton version",
+            },
+            Flag {
+                short: Some("-p"),
+                long: "--proton",
+                value: Some("VERSION"),
+                about: "Use Proton VERSION from `common`, otherwise the one which last used the prefix",
+            },
+        ],
+        examples: &["proton-call fonts install cjk -P skyrim"],
+        config: &["data", "common"],
+    },
+    Command {
+        name: "saves",
+        usage: &[
+            "saves locate TARGET",
+            "saves backup TARGET",
+            "saves restore TARGET [ARCHIVE]",
+        ],
+        about: "Find, archive or restore the save games in the prefix or for the program TARGET",
+        flags: &[],
+        examples: &[
+            "proton-call saves backup skyrim",
+            "proton-call saves restore skyrim",
+        ],
+        config: &["backups", "games.NAME.retention"],
+    },
+    Command {
+        name: "export",
+        usage: &["export NAME -o FILE [-w]"],
+        about: "Bundle the settings and DLL overrides of the program NAME into FILE",
+        flags: &[
+            Flag {
+                short: Some("-o"),
+                long: "--output",
+                value: Some("FILE"),
+                about: "Write the bundle to FILE",
+            },
+            Flag {
+                short: Some("-w"),
+                long: "--with-prefix",
+                value: None,
+                about: "Include the program's prefix",
+            },
+        ],
+        examples: &["proton-call export skyrim -o skyrim.tar.gz -w"],
+        config: &["games.NAME"],
+    },
+    Command {
+        name: "export-lutris",
+        usage: &["export-lutris PROGRAM [OPTIONS]... [-o FILE]"],
+        about: "Print the launch of PROGRAM, a name or EXE, as a Lutris game config, taking the launch options",
+        flags: &[Flag {
+            short: Some("-o"),
+            long: "--output",
+            value: Some("FILE"),
+            about: "Write the config to FILE",
+        }],
+        examples: &["proton-call export-lutris skyrim -o skyrim.yml"],
+        config: &["games.NAME"],
+    },
+    Command {
+        name: "import",
+        usage: &["import FILE", "import bottles|lutris|heroic"],
+        about: "Install a bundle made by export, or register the prefixes of Bottles, Lutris or Heroic as named prefixes",
+        flags: &[],
+        examples: &["proton-call import skyrim.tar.gz", "proton-call import lutris"],
+        config: &["data"],
+    },
+    Command {
+        name: "find",
+        usage: &["find QUERY [OPTIONS]..."],
+        about: "Search game directories for executables matching QUERY and pick one to launch, taking the launch options",
+        flags: &[],
+        examples: &["proton-call find skyrim", "proton-call find sse -p 8.0"],
+        config: &["roots"],
+    },
+    Command {
+        name: "recent",
+        usage: &["recent"],
+        about: "List recently launched programs, relaunch one with -r @N",
+        flags: &[],
+        examples: &["proton-call recent", "proton-call -r @2"],
+        config: &[],
+    },
+    Command {
+        name: "history",
+        usage: &["history [-n N]"],
+        about: "List the launches from the run log",
+        flags: &[Flag {
+            short: Some("-n"),
+            long: "--count",
+            value: Some("N"),
+            about: "List the last N launches instead of 20",
+        }],
+        examples: &["proton-call history -n 50"],
+        config: &[],
+    },
+    Command {
+        name: "stats",
+        usage: &["stats"],
+        about: "Total the launches, failures and play time of each program",
+        flags: &[],
+        examples: &["proton-call stats"],
+        config: &[],
+    },
+    Command {
+        name: "steam",
+        usage: &["steam install-tool [-p VERSION]"],
+        about: "Install proton-call into Steam's compatibilitytools.d, to pick it as a game's compatibility tool and launch Steam games through proton-call",
+        flags: &[Flag {
+            short: Some("-p"),
+            long: "--proton",
+            value: Some("VERSION"),
+            about: "Launch Steam games with Proton VERSION from `common`",
+        }],
+        examples: &["proton-call steam install-tool -p latest"],
+        config: &["steam"],
+    },
+    Command {
+        name: "install",
+        usage: &["install RELEASE"],
+        about: "Download GE-Proton RELEASE from GitHub, verify its checksum and extract it into Steam's compatibilitytools.d, ready for -p",
+        flags: &[],
+        examples: &["proton-call install GE-Proton9-5"],
+        config: &["steam"],
+    },
+    Command {
+        name: "tricks",
+        usage: &[
+            "tricks VERBS... -P NAME [-p VERSION]",
+            "tricks VERBS... --via-protontricks --appid ID",
+        ],
+        about: "Run winetricks VERBS in a prefix, or through protontricks for a Steam app",
+        flags: &[
+            Flag {
+                short: Some("-P"),
+                long: "--prefix",
+                value: Some("NAME"),
+                about: "Run in the prefix NAME in `data`",
+            },
+            Flag {
+                short: Some("-p"),
+                long: "--proton",
+                value: Some("VERSION"),
+                about: "Use the Wine of Proton VERSION from `common`",
+            },
+            Flag {
+                short: None,
+                long: "--via-protontricks",
+                value: None,
+                about: "Run through protontricks instead of winetricks",
+            },
+            Flag {
+                short: None,
+                long: "--appid",
+                value: Some("ID"),
+                about: "With --via-protontricks, the Steam app id whose prefix to use",
+            },
+        ],
+        examples: &[
+            "proton-call tricks vcrun2019 d3dx9 -P skyrim",
+            "proton-call tricks corefonts --via-protontricks --appid 489830",
+        ],
+        config: &["data", "common"],
+    },
+    Command {
+        name: "shell",
+        usage: &["shell -P NAME [-p VERSION]"],
+        about: "Read commands and run each inside a prefix with `runinprefix`, Tab completes drive_c paths",
+        flags: &[
+            Flag {
+                short: Some("-P"),
+                long: "--prefix",
+                value: Some("NAME"),
+                about: "Run in the prefix NAME in `data`",
+            },
+            Flag {
+                short: Some("-p"),
+                long: "--proton",
+                value: Some("VERSION"),
+                about: "Use Proton VERSION from `common`, otherwise the one which last used the prefix",
+            },
+        ],
+        examples: &[
+            "proton-call shell -P skyrim",
+            "echo regedit | proton-call shell -P skyrim",
+        ],
+        config: &["data", "common"],
+    },
+    Command {
+        name: "util",
+        usage: &["util TOOL [-p VERSION] [-P NAME]"],
+        about: "Run the Wine built-in TOOL, winecfg, regedit, control or taskmgr, through Proton in the compat data directory a launch with the same options uses",
+        flags: &[
+            Flag {
+                short: Some("-p"),
+                long: "--proton",
+                value: Some("VERSION"),
+                about: "Use Proton VERSION from `common`, otherwise the one which last used the prefix",
+            },
+            Flag {
+                short: Some("-P"),
+                long: "--prefix",
+                value: Some("NAME"),
+                about: "Run in the prefix NAME in `data`",
+            },
+        ],
+        examples: &[
+            "proton-call util winecfg -p 8.0",
+            "proton-call util regedit -P skyrim",
+        ],
+        config: &["data", "common"],
+    },
+    Command {
+        name: "kill",
+        usage: &["kill [-p VERSION] [-P NAME] [--force]"],
+        about: "Shut down every Wine process in the compat data directory a launch with the same options uses, through its wineserver",
+        flags: &[
+            Flag {
+                short: Some("-p"),
+                long: "--proton",
+                value: Some("VERSION"),
+                about: "Use Proton VERSION from `common`, otherwise the one which last used the prefix",
+            },
+            Flag {
+                short: Some("-P"),
+                long: "--prefix",
+                value: Some("NAME"),
+                about: "Shut down the prefix NAME in `data`",
+            },
+            Flag {
+                short: None,
+                long: "--force",
+                value: None,
+                about: "Also kill the processes still running in the prefix with SIGKILL, for a hung wineserver",
+            },
+        ],
+        examples: &[
+            "proton-call kill -p 8.0",
+            "proton-call kill -P skyrim --force",
+        ],
+        config: &["data", "common"],
+    },
+    Command {
+        name: "fixes",
+        usage: &["fixes EXE [--appid ID]"],
+        about: "Show the fix the fixes database holds for EXE, and the key to add one under",
+        flags: &[Flag {
+            short: None,
+            long: "--appid",
+            value: Some("ID"),
+            about: "Also find the fix for the Steam app ID",
+        }],
+        examples: &["proton-call fixes SkyrimSE.exe --appid 489830"],
+        config: &[],
+    },
+    Command {
+        name: "apply-fix",
+        usage: &["apply-fix FILE|URL [--name NAME] [-y]"],
+        about: "Import a shared fix file into the fixes database, after showing what it changes",
+        flags: &[
+            Flag {
+                short: None,
+                long: "--name",
+                value: Some("NAME"),
+                about: "Import as NAME.toml instead of the file name of the source",
+            },
+            Flag {
+                short: Some("-y"),
+                long: "--yes",
+                value: None,
+                about: "Skip confirmation",
+            },
+        ],
+        examples: &[
+            "proton-call apply-fix skyrim.toml",
+            "proton-call apply-fix https://example.com/fixes/skyrim.toml --name skyrim",
+        ],
+        config: &[],
+    },
+];
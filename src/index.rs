@@ -1,36 +1,347 @@
 use crate::error::{Error, Kind};
-use crate::{pass, throw, Version};
-use lliw::Fg::LightYellow as Yellow;
-use lliw::Reset;
+use crate::util::{dir_size, format_size, format_time};
+use crate::{pass, throw, Config, Keyword, Prefix, Version, Warnings};
+use lliw::Fg::Green;
+use lliw::{Reset, Style};
 use std::collections::BTreeMap;
-use std::ffi::OsString;
 use std::fmt::{Display, Formatter};
 use std::fs::DirEntry;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::SystemTime;
+
+/// Directories Steam reads system-wide compatibility tools from, such as distribution packages
+/// of GE-Proton
+static SYSTEM_TOOLS: &[&str] = &[
+    "/usr/share/steam/compatibilitytools.d",
+    "/usr/local/share/steam/compatibilitytools.d",
+];
+
+/// Anti-cheat runtimes Steam installs next to Proton, and the variables Proton finds them through
+static ANTICHEAT: &[(&str, &str)] = &[
+    ("PROTON_EAC_RUNTIME", "Proton EasyAntiCheat Runtime"),
+    ("PROTON_BATTLEYE_RUNTIME", "Proton BattlEye Runtime"),
+];
 
 /// Index type to Index Proton versions in common
 #[derive(Debug)]
 pub struct Index {
     dir: PathBuf,
+    sources: Vec<(Source, PathBuf)>,
     map: BTreeMap<Version, PathBuf>,
+    candidates: BTreeMap<Version, Vec<Candidate>>,
+    order: Option<Vec<Version>>,
+    runners: BTreeMap<String, PathBuf>,
+    warnings: Vec<String>,
 }
 
 impl Display for Index {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let mut str: String = format!(
-            "Indexed Directory: {}\n\nIndexed {} Proton Versions:\n",
-            self.dir.to_string_lossy(),
-            self.len()
-        );
+        let mut str: String = format!("Indexed Directory: {}", self.dir.to_string_lossy());
 
-        for (version, path) in &self.map {
-            str = format!("{}\nProton {} `{}`", str, version, path.to_string_lossy());
+        for (_, dir) in self.sources.iter().filter(|(_, dir)| *dir != self.dir) {
+            str = format!("{}\nIndexed Directory: {}", str, dir.to_string_lossy());
+        }
+
+        str = format!("{}\n\nIndexed {} Proton Versions:\n", str, self.len());
+
+        for (version, path) in self.entries() {
+            str = format!("{}\n{} `{}`", str, label(version), path.to_string_lossy());
+        }
+
+        if !self.runners.is_empty() {
+            str = format!("{}\n\nIndexed {} Wine Runners:\n", str, self.runners.len());
+
+            for (name, path) in &self.runners {
+                str = format!("{}\nWine {} `{}`", str, name, path.to_string_lossy());
+            }
         }
 
         write!(f, "{}", str)
     }
 }
 
+/// Serializes every indexed version, then every Wine runner, in the order `Index::entries` lists
+/// them
+impl serde::Serialize for Index {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeSeq;
+
+        /// One entry of the index as serialized
+        #[derive(serde::Serialize)]
+        struct Entry<'a> {
+            name: String,
+            version: Option<Version>,
+            path: &'a Path,
+            source: String,
+        }
+
+        let mut seq = serializer.serialize_seq(Some(self.len() + self.runners.len()))?;
+
+        for (version, path) in self.entries() {
+            seq.serialize_element(&Entry {
+                name: label(version),
+                version: Some(version),
+                path,
+                source: self.source(version).to_string(),
+            })?;
+        }
+
+        for (name, path) in &self.runners {
+            seq.serialize_element(&Entry {
+                name: format!("Wine {}", name),
+                version: None,
+                path,
+                source: String::from("runners"),
+            })?;
+        }
+
+        seq.end()
+    }
+}
+
+/// Where an indexed Proton version was installed, ordered by the precedence of builds sharing a
+/// version, least preferred first
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub enum Source {
+    /// Steam's `steamapps/common` directory
+    Common,
+    /// A system-wide `compatibilitytools.d` directory, for packaged custom builds
+    SystemTools,
+    /// Steam's `compatibilitytools.d` directory, for custom builds
+    CompatibilityTools,
+}
+
+impl serde::Serialize for Source {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl Source {
+    #[must_use]
+    /// Tells which source an indexed directory is
+    pub fn of(dir: &Path) -> Source {
+        match dir.file_name() {
+            Some(_) if SYSTEM_TOOLS.iter().any(|system| dir == Path::new(system)) => {
+                Source::SystemTools
+            }
+            Some(name) if name == "compatibilitytools.d" => Source::CompatibilityTools,
+            _ => Source::Common,
+        }
+    }
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Source::Common => write!(f, "common"),
+            Source::SystemTools => write!(f, "system"),
+            Source::CompatibilityTools => write!(f, "compatibilitytools.d"),
+        }
+    }
+}
+
+/// A directory holding an indexed Proton version, which may share its version with others
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Candidate {
+    path: PathBuf,
+    stable: bool,
+    source: Source,
+    modified: Option<SystemTime>,
+}
+
+impl Candidate {
+    #[must_use]
+    /// Returns the path of the Proton directory
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    #[must_use]
+    /// Returns false for qualified releases such as `Proton 8.0 (Beta)`
+    pub fn stable(&self) -> bool {
+        self.stable
+    }
+
+    #[must_use]
+    /// Returns where the directory was installed
+    pub fn source(&self) -> Source {
+        self.source
+    }
+
+    #[must_use]
+    /// Returns when the directory was last modified
+    pub fn modified(&self) -> Option<SystemTime> {
+        self.modified
+    }
+
+    /// Orders candidates by precedence, most preferred last: stable releases over betas, then
+    /// by source, builds the user installed over packaged ones over Steam's, then the newest,
+    /// then by path
+    fn precedence(&self) -> (bool, Source, Option<SystemTime>, &Path) {
+        (self.stable, self.source, self.modified, &self.path)
+    }
+}
+
+/// Orders in which an `Index` can be listed
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum IndexSort {
+    /// By version number, the default
+    Version,
+    /// By install date, oldest first
+    Date,
+    /// By size on disk, smallest first
+    Size,
+}
+
+impl FromStr for IndexSort {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "version" => pass!(IndexSort::Version),
+            "date" => pass!(IndexSort::Date),
+            "size" => pass!(IndexSort::Size),
+            _ => throw!(
+                Kind::ArgumentParse,
+                "sort '{}', expected version, date or size",
+                s
+            ),
+        }
+    }
+}
+
+/// Detailed, table formatted listing of an `Index`, see `Index::long`
+#[derive(Debug)]
+pub struct LongIndex<'a> {
+    index: &'a Index,
+    prefixes: &'a [Prefix],
+}
+
+impl Display for LongIndex<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut rows: Vec<[String; 6]> = vec![[
+            String::from("VERSION"),
+            String::from("SIZE"),
+            String::from("INSTALLED"),
+            String::from("SOURCE"),
+            String::from("PREFIXES"),
+            String::from("PATH"),
+        ]];
+
+        for (version, path) in self.index.entries() {
+            let installed: Option<SystemTime> = self.index.installed(version);
+
+            let prefixes: usize = self
+                .prefixes
+                .iter()
+                .filter(|p| p.version() == Some(version))
+                .count();
+
+            rows.push([
+                version.to_string(),
+                format_size(dir_size(path)),
+                installed.map_or_else(|| String::from("unknown"), format_time),
+                self.index.source(version).to_string(),
+                prefixes.to_string(),
+                path.to_string_lossy().to_string(),
+            ]);
+        }
+
+        let mut widths: [usize; 6] = [0; 6];
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+
+        for (i, row) in rows.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+
+            let line: Vec<String> = row
+                .iter()
+                .zip(widths)
+                .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+                .collect();
+
+            write!(f, "{}", line.join("  ").trim_end())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Aligned table listing of an `Index` for terminals, see `Index::pretty`
+#[derive(Debug)]
+pub struct PrettyIndex<'a> {
+    index: &'a Index,
+    color: bool,
+}
+
+impl Display for PrettyIndex<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut rows: Vec<[String; 3]> = vec![[
+            String::from("VERSION"),
+            String::from("SOURCE"),
+            String::from("PATH"),
+        ]];
+
+        for (version, path) in self.index.entries() {
+            rows.push([
+                label(version),
+                self.index.source(version).to_string(),
+                path.to_string_lossy().to_string(),
+            ]);
+        }
+
+        for (name, path) in &self.index.runners {
+            rows.push([
+                format!("Wine {}", name),
+                String::from("runners"),
+                path.to_string_lossy().to_string(),
+            ]);
+        }
+
+        let mut widths: [usize; 3] = [0; 3];
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+
+        // the last column is not padded, so color codes never follow trailing spaces
+        widths[2] = 0;
+
+        for (i, row) in rows.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+
+            let line: Vec<String> = row
+                .iter()
+                .zip(widths)
+                .enumerate()
+                .map(|(column, (cell, width))| {
+                    let cell: String = format!("{:<width$}", cell, width = width);
+
+                    match (self.color, i, column) {
+                        (true, 0, _) => format!("{}{}{}", Style::Bold, cell, Reset),
+                        (true, _, 0) => format!("{}{}{}", Green, cell, Reset),
+                        _ => cell,
+                    }
+                })
+                .collect();
+
+            write!(f, "{}", line.join("  "))?;
+        }
+
+        Ok(())
+    }
+}
+
 impl Index {
     /// Creates an index of Proton versions in given path
     ///
@@ -40,7 +351,12 @@ impl Index {
     pub fn new(index: &Path) -> Result<Index, Error> {
         let mut idx = Index {
             dir: index.to_path_buf(),
+            sources: Vec::new(),
             map: BTreeMap::new(),
+            candidates: BTreeMap::new(),
+            order: None,
+            runners: BTreeMap::new(),
+            warnings: Vec::new(),
         };
 
         idx.index()?;
@@ -48,6 +364,152 @@ impl Index {
         Ok(idx)
     }
 
+    /// Creates an index of the Proton versions and Wine runners set in `config`, merging
+    /// `common` with Steam's and the system's `compatibilitytools.d` directories
+    ///
+    /// # Errors
+    ///
+    /// Will fail if Indexing fails to read the common directory
+    pub fn from_config(config: &Config) -> Result<Index, Error> {
+        let mut idx: Index = Index::new(&config.common())?;
+
+        if let Some(runners) = config.runners() {
+            idx.index_runners(&runners);
+        }
+
+        idx.add_source(&config.steam().join("compatibilitytools.d"));
+        for system in SYSTEM_TOOLS {
+            idx.add_source(Path::new(system));
+        }
+
+        Ok(idx)
+    }
+
+    /// Merges the Proton versions in `dir` into the index, where builds sharing a version with
+    /// ones already indexed are used by the precedence of their source, see `Source`
+    pub fn add_source(&mut self, dir: &Path) {
+        if self.sources.iter().any(|(_, indexed)| indexed == dir) {
+            return;
+        }
+
+        match self.index_dir(dir) {
+            Ok(()) => (),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => (),
+            Err(e) => self.warn(format!(
+                "skipped Proton versions in {}: {}",
+                dir.to_string_lossy(),
+                e
+            )),
+        }
+    }
+
+    #[must_use]
+    /// Returns the directories indexed and which source each is, `common` first
+    pub fn sources(&self) -> &[(Source, PathBuf)] {
+        &self.sources
+    }
+
+    /// Adds the Wine builds in `dir`, such as Lutris' Wine-GE runners, to the index
+    pub fn index_runners(&mut self, dir: &Path) {
+        let entries: Vec<DirEntry> = match dir.read_dir() {
+            Ok(rd) => rd.flatten().collect(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return,
+            Err(e) => {
+                self.warn(format!(
+                    "skipped Wine runners in {}: {}",
+                    dir.to_string_lossy(),
+                    e
+                ));
+                return;
+            }
+        };
+
+        self.runners.extend(scan(&entries, |entry| {
+            let path: PathBuf = entry.path();
+
+            if path.join("bin/wine").is_file() {
+                Some((entry.file_name().to_string_lossy().to_lowercase(), path))
+            } else {
+                None
+            }
+        }));
+    }
+
+    #[must_use]
+    /// Finds Proton `version` in `dir` by the names Steam installs it under, without indexing
+    /// the whole directory, `None` if it is not under one of them
+    pub fn find(dir: &Path, version: Version) -> Option<PathBuf> {
+        let names: Vec<String> = match version {
+            Version::Mainline(..) => vec![format!("Proton {}", version)],
+            Version::Ge(..) => vec![version.to_string()],
+            Version::Experimental => vec![
+                String::from("Proton - Experimental"),
+                String::from("Proton Experimental"),
+            ],
+            Version::Hotfix => vec![String::from("Proton Hotfix")],
+            Version::Custom => Vec::new(),
+        };
+
+        names
+            .into_iter()
+            .map(|name| dir.join(name))
+            .find(|path| path.is_dir())
+    }
+
+    #[must_use]
+    /// Resolves a version keyword to the version and path it currently stands for, GE-Proton
+    /// builds are only indexed by `Index::from_config`, from Steam's `compatibilitytools.d`
+    pub fn resolve(&self, keyword: Keyword) -> Option<(Version, PathBuf)> {
+        match keyword {
+            Keyword::Latest => self.newest(|v| matches!(v, Version::Mainline(..))),
+            Keyword::Experimental => Index::find(&self.dir, Version::Experimental)
+                .or_else(|| self.get(Version::Experimental))
+                .map(|p| (Version::Experimental, p)),
+            Keyword::Hotfix => Index::find(&self.dir, Version::Hotfix)
+                .or_else(|| self.get(Version::Hotfix))
+                .map(|p| (Version::Hotfix, p)),
+            Keyword::GeLatest => self.newest(|v| matches!(v, Version::Ge(..))),
+        }
+    }
+
+    /// Returns the newest indexed version matching `filter`
+    fn newest(&self, filter: impl Fn(&Version) -> bool) -> Option<(Version, PathBuf)> {
+        self.map
+            .iter()
+            .rev()
+            .find(|(v, _)| filter(v))
+            .map(|(v, p)| (*v, p.clone()))
+    }
+
+    #[must_use]
+    /// Retrieves the path of a Wine runner by name, `wine-ge-8-26` also matches `wine-ge-8-26-x86_64`
+    pub fn runner(&self, name: &str) -> Option<PathBuf> {
+        let name: String = name.to_lowercase();
+
+        self.runners
+            .get(&name)
+            .or_else(|| self.runners.get(&format!("{}-x86_64", name)))
+            .or_else(|| self.runners.get(&format!("lutris-{}-x86_64", name)))
+            .cloned()
+    }
+
+    #[must_use]
+    /// Returns the anti-cheat runtimes installed in `common`, with the variable Proton reads
+    /// each from, without indexing the Proton versions there
+    pub fn anticheat(common: &Path) -> Vec<(&'static str, PathBuf)> {
+        ANTICHEAT
+            .iter()
+            .map(|(var, dir)| (*var, common.join(dir)))
+            .filter(|(_, path)| path.is_dir())
+            .collect()
+    }
+
+    #[must_use]
+    /// Returns the problems met while indexing, for entries which were skipped
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
     #[must_use]
     /// Returns the number of Indexed Protons
     pub fn len(&self) -> usize {
@@ -60,6 +522,83 @@ impl Index {
         self.map.is_empty()
     }
 
+    #[must_use]
+    /// Returns a detailed listing with size, install date, source and how many of `prefixes` use each version
+    pub fn long<'a>(&'a self, prefixes: &'a [Prefix]) -> LongIndex<'a> {
+        LongIndex {
+            index: self,
+            prefixes,
+        }
+    }
+
+    #[must_use]
+    /// Lists the index as an aligned table of versions, sources and paths, with a bold header
+    /// and colored versions if `color` is set
+    pub fn pretty(&self, color: bool) -> PrettyIndex<'_> {
+        PrettyIndex { index: self, color }
+    }
+
+    /// Serializes the indexed versions and Wine runners to JSON, each with its name, version,
+    /// path and source
+    ///
+    /// # Errors
+    ///
+    /// Will fail if the index can not be serialized
+    pub fn to_json(&self) -> Result<String, Error> {
+        match serde_json::to_string_pretty(self) {
+            Ok(json) => pass!(json),
+            Err(e) => throw!(Kind::Internal, "failed to serialize the index: {}", e),
+        }
+    }
+
+    #[must_use]
+    /// Returns every indexed version and its path, in the order set by `Index::sort`
+    pub fn entries(&self) -> Vec<(Version, &Path)> {
+        match &self.order {
+            Some(order) => order
+                .iter()
+                .filter_map(|v| Some((*v, self.map.get(v)?.as_path())))
+                .collect(),
+            None => self.map.iter().map(|(v, p)| (*v, p.as_path())).collect(),
+        }
+    }
+
+    #[must_use]
+    /// Returns every directory holding the requested Proton version, the one used first
+    pub fn candidates(&self, version: Version) -> &[Candidate] {
+        self.candidates.get(&version).map_or(&[], Vec::as_slice)
+    }
+
+    #[must_use]
+    /// Returns when the requested Proton version was installed
+    pub fn installed(&self, version: Version) -> Option<SystemTime> {
+        let meta = self.map.get(&version)?.metadata().ok()?;
+        meta.created().or_else(|_| meta.modified()).ok()
+    }
+
+    #[must_use]
+    /// Returns the size on disk of the requested Proton version in bytes
+    pub fn size(&self, version: Version) -> Option<u64> {
+        Some(dir_size(self.map.get(&version)?))
+    }
+
+    /// Sets the order versions are listed in by `Index::entries` and when displayed
+    pub fn sort(&mut self, by: IndexSort, reverse: bool) {
+        let mut order: Vec<Version> = self.map.keys().copied().collect();
+
+        match by {
+            IndexSort::Version => (),
+            IndexSort::Date => order.sort_by_key(|v| self.installed(*v)),
+            IndexSort::Size => order.sort_by_cached_key(|v| self.size(*v)),
+        }
+
+        if reverse {
+            order.reverse();
+        }
+
+        self.order = Some(order);
+    }
+
     #[must_use]
     /// Retrieves the path of the requested Proton version
     pub fn get(&self, version: Version) -> Option<PathBuf> {
@@ -67,33 +606,294 @@ impl Index {
         Some(path.clone())
     }
 
-    /// Indexes Proton versions
+    #[must_use]
+    /// Returns where the directory used for the requested Proton version was installed
+    pub fn source(&self, version: Version) -> Source {
+        self.candidates(version)
+            .first()
+            .map_or(Source::Common, Candidate::source)
+    }
+
+    /// Adds a directory holding `version`, used for it if it takes precedence over the others
+    fn add(&mut self, version: Version, stable: bool, path: PathBuf, source: Source) {
+        let candidate: Candidate = Candidate {
+            modified: path.metadata().and_then(|m| m.modified()).ok(),
+            source,
+            stable,
+            path,
+        };
+
+        let candidates: &mut Vec<Candidate> = self.candidates.entry(version).or_default();
+        candidates.push(candidate);
+        candidates.sort_by(|a, b| b.precedence().cmp(&a.precedence()));
+
+        self.map.insert(version, candidates[0].path.clone());
+    }
+
+    /// Notes a problem met while indexing, also collecting it in `Warnings`
+    fn warn(&mut self, message: String) {
+        Warnings::push("index", message.clone());
+        self.warnings.push(message);
+    }
+
+    /// Indexes Proton versions, noting entries which can not be indexed in `warnings`
     fn index(&mut self) -> Result<(), Error> {
-        if let Ok(rd) = self.dir.read_dir() {
-            for result_entry in rd {
-                let entry: DirEntry = if let Ok(e) = result_entry {
-                    e
-                } else {
-                    eprintln!("{}warning:{} failed indexing a directory...", Yellow, Reset);
-                    continue;
-                };
-
-                let entry_path: PathBuf = entry.path();
-
-                if entry_path.is_dir() {
-                    let name: OsString = entry.file_name();
-                    let name: String = name.to_string_lossy().to_string();
-                    if let Some(version_str) = name.split(' ').last() {
-                        if let Ok(version) = version_str.parse() {
-                            self.map.insert(version, entry_path);
-                        }
-                    }
-                }
-            }
-        } else {
+        let dir: PathBuf = self.dir.clone();
+        if self.index_dir(&dir).is_err() {
             throw!(Kind::IndexReadDir, "can not read common dir");
         }
 
         pass!()
     }
+
+    /// Indexes the Proton versions in `dir`, either `common` or a `compatibilitytools.d`
+    fn index_dir(&mut self, dir: &Path) -> std::io::Result<()> {
+        let rd = dir.read_dir()?;
+        let source: Source = Source::of(dir);
+        self.sources.push((source, dir.to_path_buf()));
+
+        let mut entries: Vec<DirEntry> = Vec::new();
+        for result_entry in rd {
+            match result_entry {
+                Ok(e) => entries.push(e),
+                Err(e) => self.warn(format!("skipped an unreadable entry in the index: {}", e)),
+            }
+        }
+
+        let scanned: Vec<Result<(Version, bool, PathBuf), String>> = scan(&entries, |entry| {
+            let entry_path: PathBuf = entry.path();
+            let name: String = entry.file_name().to_string_lossy().to_string();
+
+            match std::fs::metadata(&entry_path) {
+                Ok(meta) if meta.is_dir() => (),
+                Ok(_) => return None,
+                Err(e) => return Some(Err(format!("skipped '{}': {}", name, e))),
+            }
+
+            match parse_name(&name) {
+                Some((version, stable)) => Some(Ok((version, stable, entry_path))),
+                // Only directories named like Proton are worth a warning, not every game
+                _ if is_unparsed_proton(&name) => Some(Err(format!(
+                    "skipped '{}': could not read its version",
+                    name
+                ))),
+                _ => None,
+            }
+        });
+
+        for result in scanned {
+            match result {
+                Ok((version, stable, path)) => self.add(version, stable, path, source),
+                Err(warning) => self.warn(warning),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Reads the version of a Proton directory name, and whether it is a stable release rather
+/// than one qualified like `Proton 8.0 (Beta)`
+fn parse_name(name: &str) -> Option<(Version, bool)> {
+    if let Ok(version) = name.parse() {
+        return Some((version, true));
+    }
+
+    let (base, qualifier) = name.rsplit_once(" (")?;
+    qualifier.strip_suffix(')')?;
+    Some((base.parse().ok()?, false))
+}
+
+/// Returns true if `name` looks like a Proton install, other than the anti-cheat runtimes
+fn is_unparsed_proton(name: &str) -> bool {
+    name.starts_with("Proton ") && !ANTICHEAT.iter().any(|(_, dir)| *dir == name)
+}
+
+/// Names a version in listings, GE-Proton builds already carrying Proton in their name
+fn label(version: Version) -> String {
+    match version {
+        Version::Ge(..) => version.to_string(),
+        _ => format!("Proton {}", version),
+    }
+}
+
+/// Directories with fewer entries than this are scanned on the calling thread
+const PARALLEL_THRESHOLD: usize = 32;
+
+/// Most threads used to scan a directory
+const MAX_THREADS: usize = 8;
+
+/// Runs `inspect` over `entries`, spread over a few threads for large directories on slow disks
+///
+/// A panic in a worker is raised again, rather than leaving its entries out of the index.
+fn scan<T, F>(entries: &[DirEntry], inspect: F) -> Vec<T>
+where
+    T: Send,
+    F: Fn(&DirEntry) -> Option<T> + Sync,
+{
+    let threads: usize = std::thread::available_parallelism()
+        .map_or(1, usize::from)
+        .min(MAX_THREADS);
+
+    if entries.len() < PARALLEL_THRESHOLD || threads < 2 {
+        return entries.iter().filter_map(&inspect).collect();
+    }
+
+    let chunk: usize = entries.len().div_ceil(threads);
+    let inspect: &F = &inspect;
+
+    std::thread::scope(|scope| {
+        let workers: Vec<_> = entries
+            .chunks(chunk)
+            .map(|part| scope.spawn(move || part.iter().filter_map(inspect).collect::<Vec<T>>()))
+            .collect();
+
+        workers
+            .into_iter()
+            .flat_map(|w| {
+                w.join()
+                    .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+            })
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn empty() -> Index {
+        Index {
+            dir: PathBuf::new(),
+            sources: Vec::new(),
+            map: BTreeMap::new(),
+            candidates: BTreeMap::new(),
+            order: None,
+            runners: BTreeMap::new(),
+            warnings: Vec::new(),
+        }
+    }
+
+    const VERSION: Version = Version::Mainline(8, 0);
+
+    #[test]
+    fn stable_releases_win_over_betas() {
+        let mut index: Index = empty();
+        index.add(
+            VERSION,
+            false,
+            PathBuf::from("/tools/Proton 8.0 (Beta)"),
+            Source::CompatibilityTools,
+        );
+        index.add(
+            VERSION,
+            true,
+            PathBuf::from("/common/Proton 8.0"),
+            Source::Common,
+        );
+
+        assert_eq!(
+            index.get(VERSION),
+            Some(PathBuf::from("/common/Proton 8.0"))
+        );
+    }
+
+    #[test]
+    fn user_builds_win_over_packaged_and_steam_ones() {
+        let mut index: Index = empty();
+        index.add(
+            VERSION,
+            true,
+            PathBuf::from("/common/Proton 8.0"),
+            Source::Common,
+        );
+        index.add(
+            VERSION,
+            true,
+            PathBuf::from("/system/Proton 8.0"),
+            Source::SystemTools,
+        );
+        assert_eq!(
+            index.get(VERSION),
+            Some(PathBuf::from("/system/Proton 8.0"))
+        );
+
+        index.add(
+            VERSION,
+            true,
+            PathBuf::from("/tools/Proton 8.0"),
+            Source::CompatibilityTools,
+        );
+        assert_eq!(index.get(VERSION), Some(PathBuf::from("/tools/Proton 8.0")));
+    }
+
+    #[test]
+    fn candidates_are_listed_most_preferred_first() {
+        let mut index: Index = empty();
+        index.add(
+            VERSION,
+            true,
+            PathBuf::from("/common/Proton 8.0"),
+            Source::Common,
+        );
+        index.add(
+            VERSION,
+            false,
+            PathBuf::from("/tools/Proton 8.0 (Beta)"),
+            Source::CompatibilityTools,
+        );
+        index.add(
+            VERSION,
+            true,
+            PathBuf::from("/tools/Proton 8.0"),
+            Source::CompatibilityTools,
+        );
+
+        let sources: Vec<(bool, Source)> = index
+            .candidates(VERSION)
+            .iter()
+            .map(|c| (c.stable(), c.source()))
+            .collect();
+        assert_eq!(
+            sources,
+            [
+                (true, Source::CompatibilityTools),
+                (true, Source::Common),
+                (false, Source::CompatibilityTools),
+            ]
+        );
+        assert_eq!(index.source(VERSION), Source::CompatibilityTools);
+    }
+
+    #[test]
+    fn newest_picks_the_highest_matching_version() {
+        let mut index: Index = empty();
+        index.add(
+            Version::Mainline(7, 0),
+            true,
+            PathBuf::from("/a"),
+            Source::Common,
+        );
+        index.add(
+            Version::Mainline(9, 0),
+            true,
+            PathBuf::from("/b"),
+            Source::Common,
+        );
+        index.add(
+            Version::Ge(9, 5),
+            true,
+            PathBuf::from("/c"),
+            Source::CompatibilityTools,
+        );
+
+        assert_eq!(
+            index.resolve(Keyword::Latest),
+            Some((Version::Mainline(9, 0), PathBuf::from("/b")))
+        );
+        assert_eq!(
+            index.resolve(Keyword::GeLatest),
+            Some((Version::Ge(9, 5), PathBuf::from("/c")))
+        );
+    }
 }
@@ -0,0 +1,142 @@
+use crate::error::{Error, Kind};
+use crate::util::{cache_dir, fetch, run};
+use crate::{pass, throw, ProtonEvent, Version};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Where GE-Proton releases are published, each under a tag named after it
+static RELEASES: &str = "https://github.com/GloriousEggroll/proton-ge-custom/releases/download";
+
+/// A GE-Proton release, downloaded from GitHub into Steam's `compatibilitytools.d` directory
+#[derive(Debug, Clone)]
+pub struct GeRelease {
+    version: Version,
+    dir: PathBuf,
+}
+
+impl GeRelease {
+    /// Creates a new `GeRelease` of `version`, installed into the `compatibilitytools.d`
+    /// directory of the Steam install at `steam`
+    ///
+    /// # Errors
+    ///
+    /// Will fail if `version` is not a GE-Proton build such as `GE-Proton9-5`
+    pub fn new(steam: &Path, version: Version) -> Result<GeRelease, Error> {
+        if !matches!(version, Version::Ge(..)) {
+            throw!(
+                Kind::VersionParse,
+                "'{}' is not a GE-Proton release, such as GE-Proton9-5",
+                version
+            );
+        }
+
+        pass!(GeRelease {
+            version,
+            dir: steam.join("compatibilitytools.d").join(version.to_string()),
+        })
+    }
+
+    #[must_use]
+    /// Returns the directory the release is installed in
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    #[must_use]
+    /// Tells whether the release is installed already
+    pub fn is_installed(&self) -> bool {
+        self.dir.join("proton").is_file()
+    }
+
+    /// Downloads the release and its checksum, verifies the tarball against it and extracts it
+    /// into `compatibilitytools.d`
+    ///
+    /// Tarballs are cached, a download failing verification is removed so the next attempt
+    /// fetches it again.
+    ///
+    /// # Errors
+    ///
+    /// Will fail if...
+    /// * Downloading the release or its checksum fails
+    /// * The tarball does not match its checksum
+    /// * Extracting the tarball or moving it into place fails
+    pub fn install(&self) -> Result<(), Error> {
+        let name: String = self.version.to_string();
+        let cache: PathBuf = cache_dir("releases")?;
+        let tarball: PathBuf = cache.join(format!("{}.tar.gz", name));
+        let checksum: PathBuf = cache.join(format!("{}.sha512sum", name));
+
+        ProtonEvent::Downloading(name.clone()).emit();
+        fetch(&format!("{0}/{1}/{1}.tar.gz", RELEASES, name), &tarball)?;
+        fetch(&format!("{0}/{1}/{1}.sha512sum", RELEASES, name), &checksum)?;
+
+        if let Err(e) = verify(&tarball, &checksum) {
+            let _ = std::fs::remove_file(&tarball);
+            let _ = std::fs::remove_file(&checksum);
+            return Err(e);
+        }
+
+        let Some(tools) = self.dir.parent() else {
+            throw!(Kind::Internal, "release directory has no parent");
+        };
+
+        // Extracted aside and moved once complete, so Steam never lists a partial build
+        let partial: PathBuf = tools.join(format!(".{}.partial", name));
+        let _ = std::fs::remove_dir_all(&partial);
+        if let Err(e) = std::fs::create_dir_all(&partial) {
+            throw!(Kind::ToolInstall, "{}: {}", partial.to_string_lossy(), e);
+        }
+
+        ProtonEvent::Extracting(tarball.clone()).emit();
+        let extracted: Result<(), Error> = run(
+            Command::new("tar")
+                .arg("-xf")
+                .arg(&tarball)
+                .arg("-C")
+                .arg(&partial),
+            Kind::Extract,
+        );
+
+        let moved: Result<(), Error> = extracted.and_then(|()| {
+            if let Err(e) = std::fs::rename(partial.join(&name), &self.dir) {
+                throw!(Kind::ToolInstall, "{}: {}", self.dir.to_string_lossy(), e);
+            }
+
+            pass!()
+        });
+
+        let _ = std::fs::remove_dir_all(&partial);
+        moved
+    }
+}
+
+/// Checks `tarball` against the `sha512sum` listing published with it
+fn verify(tarball: &Path, checksum: &Path) -> Result<(), Error> {
+    let expected: String = match std::fs::read_to_string(checksum) {
+        Ok(listing) => listing
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .to_string(),
+        Err(e) => throw!(Kind::Download, "{}: {}", checksum.to_string_lossy(), e),
+    };
+
+    let output = match Command::new("sha512sum").arg(tarball).output() {
+        Ok(output) if output.status.success() => output,
+        Ok(output) => throw!(Kind::Download, "sha512sum exited with {}", output.status),
+        Err(e) => throw!(Kind::Download, "failed to run sha512sum: {}", e),
+    };
+
+    let stdout: String = String::from_utf8_lossy(&output.stdout).to_string();
+    let actual: &str = stdout.split_whitespace().next().unwrap_or_default();
+
+    if expected.is_empty() || !actual.eq_ignore_ascii_case(&expected) {
+        throw!(
+            Kind::Download,
+            "{} does not match its published checksum",
+            tarball.to_string_lossy()
+        );
+    }
+
+    pass!()
+}
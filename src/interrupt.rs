@@ -0,0 +1,183 @@
+use crate::error::{Error, Kind};
+use crate::util::run;
+use crate::{pass, throw, ProtonEvent};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// How long a program has to exit after a forwarded signal before it is killed
+const GRACE: Duration = Duration::from_secs(10);
+
+/// Set once proton-call is interrupted, after `listen` is called
+static RECEIVED: OnceLock<Arc<AtomicBool>> = OnceLock::new();
+
+/// What happens to the prefix when proton-call is interrupted during a launch
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum Interrupt {
+    /// Leave it to the program, the default
+    #[default]
+    Leave,
+    /// Shut down every Wine process in the prefix
+    KillPrefix,
+}
+
+/// Catches `SIGINT`, `SIGTERM` and `SIGHUP` instead of exiting on them, for `received` to tell
+///
+/// # Errors
+///
+/// Will fail if the signal handlers can not be registered
+pub(crate) fn listen() -> Result<(), Error> {
+    use signal_hook::consts::{SIGHUP, SIGINT, SIGTERM};
+
+    if RECEIVED.get().is_some() {
+        return pass!();
+    }
+
+    let received: Arc<AtomicBool> = Arc::new(AtomicBool::new(false));
+    for signal in [SIGINT, SIGTERM, SIGHUP] {
+        if let Err(e) = signal_hook::flag::register(signal, received.clone()) {
+            throw!(Kind::ProtonWait, "failed to catch signal {}: {}", signal, e);
+        }
+    }
+
+    let _ = RECEIVED.set(received);
+    pass!()
+}
+
+#[must_use]
+/// Tells whether proton-call was interrupted since `listen` was called
+pub(crate) fn received() -> bool {
+    RECEIVED
+        .get()
+        .is_some_and(|received| received.load(Ordering::Relaxed))
+}
+
+/// Shuts down every Wine process in the prefix at `pfx` through its `wineserver`
+///
+/// # Errors
+///
+/// Will fail if `wineserver` can not be run
+pub(crate) fn kill_prefix(wineserver: &Path, pfx: &Path) -> Result<(), Error> {
+    run(
+        Command::new(wineserver).arg("-k").env("WINEPREFIX", pfx),
+        Kind::ProtonWait,
+    )
+}
+
+/// Kills every process running in the prefix at `pfx` with `SIGKILL`, by the `WINEPREFIX` in
+/// its environment, returning how many were
+pub(crate) fn kill_processes(pfx: &Path) -> usize {
+    use signal_hook::consts::SIGKILL;
+
+    let Ok(entries) = std::fs::read_dir("/proc") else {
+        return 0;
+    };
+
+    let wanted: PathBuf = pfx.canonicalize().unwrap_or_else(|_| pfx.to_path_buf());
+    let own: String = std::process::id().to_string();
+    let mut killed: usize = 0;
+
+    for entry in entries.flatten() {
+        let pid: String = entry.file_name().to_string_lossy().to_string();
+        if pid == own || !pid.bytes().all(|b| b.is_ascii_digit()) {
+            continue;
+        }
+
+        let Ok(environ) = std::fs::read(entry.path().join("environ")) else {
+            continue;
+        };
+
+        let runs_in: bool = environ
+            .split(|b| *b == 0)
+            .filter_map(|var| var.strip_prefix(b"WINEPREFIX="))
+            .map(|value| PathBuf::from(String::from_utf8_lossy(value).to_string()))
+            .any(|value| value.canonicalize().unwrap_or(value) == wanted);
+
+        if runs_in {
+            send(SIGKILL, &pid);
+            killed += 1;
+        }
+    }
+
+    killed
+}
+
+/// Forwards the termination signals proton-call receives to a launched program until dropped,
+/// killing it if it has not exited once `GRACE` passes or a second signal arrives
+pub(crate) struct Forwarder {
+    handle: signal_hook::iterator::Handle,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Forwarder {
+    /// Starts forwarding signals to the program `pid`, or to the process group it leads when
+    /// `group` is set
+    ///
+    /// A program sharing proton-call's process group shares its terminal too, which sends it
+    /// `SIGINT` itself, so only `SIGTERM` and `SIGHUP` are forwarded to it.
+    ///
+    /// # Errors
+    ///
+    /// Will fail if the signal handlers can not be registered
+    pub(crate) fn start(pid: u32, group: bool) -> Result<Forwarder, Error> {
+        use signal_hook::consts::{SIGHUP, SIGINT, SIGKILL, SIGTERM};
+        use signal_hook::iterator::Signals;
+
+        let mut signals: Signals = match Signals::new([SIGINT, SIGTERM, SIGHUP]) {
+            Ok(s) => s,
+            Err(e) => throw!(Kind::ProtonWait, "failed to catch signals: {}", e),
+        };
+        let handle: signal_hook::iterator::Handle = signals.handle();
+
+        let target: String = if group {
+            format!("-{}", pid)
+        } else {
+            pid.to_string()
+        };
+
+        let thread: JoinHandle<()> = std::thread::spawn(move || {
+            let Some(signal) = signals.forever().find(|signal| group || *signal != SIGINT) else {
+                return;
+            };
+
+            send(signal, &target);
+            let forwarded: Instant = Instant::now();
+
+            while !signals.is_closed() {
+                if forwarded.elapsed() >= GRACE || signals.pending().next().is_some() {
+                    ProtonEvent::Killing.emit();
+                    send(SIGKILL, &target);
+                    return;
+                }
+
+                std::thread::sleep(crate::wait::POLL);
+            }
+        });
+
+        pass!(Forwarder {
+            handle,
+            thread: Some(thread),
+        })
+    }
+}
+
+impl Drop for Forwarder {
+    fn drop(&mut self) {
+        self.handle.close();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Sends `signal` to `target`, a process or a negated process group, through `kill`
+pub(crate) fn send(signal: i32, target: &str) {
+    let _ = Command::new("kill")
+        .arg(format!("-{}", signal))
+        .arg("--")
+        .arg(target)
+        .status();
+}
@@ -0,0 +1,65 @@
+use crate::error::{Error, Kind};
+use crate::{collect_log, pass, throw, ProtonEvent};
+use std::path::{Path, PathBuf};
+use std::process::{Child, ExitStatus};
+
+/// A launch started by `Proton::spawn`, still running or exited but not yet waited for
+#[derive(Debug)]
+pub struct Launched {
+    child: Child,
+    compat: PathBuf,
+    log: Option<(PathBuf, PathBuf)>,
+}
+
+impl Launched {
+    #[must_use]
+    /// Creates a new instance of `Launched`, with the directory the Proton log is staged in and
+    /// the file it is moved to
+    pub(crate) fn new(child: Child, compat: PathBuf, log: Option<(PathBuf, PathBuf)>) -> Launched {
+        Launched { child, compat, log }
+    }
+
+    #[must_use]
+    /// Returns the process ID of the program, Proton's script or Wine, which also leads the
+    /// process group of what it starts
+    pub fn id(&self) -> u32 {
+        self.child.id()
+    }
+
+    #[must_use]
+    /// Returns the compat data directory the program runs in
+    pub fn compat(&self) -> &Path {
+        &self.compat
+    }
+
+    /// Returns the exit status if the program exited, without waiting for it
+    ///
+    /// # Errors
+    ///
+    /// Will fail if the program's status can not be read
+    pub fn try_wait(&mut self) -> Result<Option<ExitStatus>, Error> {
+        match self.child.try_wait() {
+            Ok(status) => pass!(status),
+            Err(e) => throw!(Kind::ProtonWait, "'{}': {}", self.child.id(), e),
+        }
+    }
+
+    /// Waits for the program to exit, then moves the Proton log into place if one was asked for
+    ///
+    /// # Errors
+    ///
+    /// Will fail if waiting for the program fails, or Proton wrote no log
+    pub fn wait(mut self) -> Result<ExitStatus, Error> {
+        let status: ExitStatus = match self.child.wait() {
+            Ok(status) => status,
+            Err(e) => throw!(Kind::ProtonWait, "'{}': {}", self.child.id(), e),
+        };
+
+        if let Some((dir, file)) = &self.log {
+            collect_log(dir, file)?;
+            ProtonEvent::LogWritten(file.clone()).emit();
+        }
+
+        pass!(status)
+    }
+}
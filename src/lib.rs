@@ -8,24 +8,114 @@
 # Proton Caller API
 
 This defines the internal API used in `proton-call` to run Proton
+
+What a launch reports while it works is printed, unless `ProtonEvent::observe` routes it to the
+caller, and non-fatal problems are collected by `Warnings`.
+
+The `async` feature adds `Proton::spawn_async`, running a launch on tokio.
 */
 
+#[cfg(feature = "async")]
+mod asynchronous;
+mod builder;
+mod bundle;
 mod config;
+mod custom;
+mod dedup;
+mod desktop;
+mod discord;
+mod env;
+mod events;
+mod finder;
+mod fixes;
+mod fonts;
+mod foreign;
 mod index;
+mod install;
+mod interrupt;
+mod launched;
+mod menus;
+mod metrics;
+mod monitor;
+mod nvidia;
+mod outcome;
+mod plan;
+mod policy;
+mod prefix;
+mod recent;
+mod registry;
+mod retry;
+mod runtime;
+mod saves;
+mod schema;
+mod shaders;
+mod shell;
+mod steam;
+mod timing;
+mod tricks;
+mod util;
 mod version;
+mod wait;
+mod warnings;
 
 /// Contains the `Error` and `ErrorKind` types
 pub mod error;
+/// Contains the metadata of every command and flag, rendered by `proton-call help`
+pub mod help;
+/// Contains the message catalogs and the `tr` lookup for translated messages
+pub mod locale;
 
-pub use config::Config;
+pub use builder::ProtonBuilder;
+pub use bundle::Bundle;
+pub use config::{Config, Game, Origin, Settings};
+pub use custom::proton_script;
+pub use dedup::Dedup;
+pub use desktop::VirtualDesktop;
+pub use discord::Discord;
+pub use env::{EnvSource, Environment};
 use error::{Error, Kind};
-pub use index::Index;
+pub use events::ProtonEvent;
+pub use finder::{Finder, Found};
+pub use fixes::{Fix, FixImport, Fixes};
+pub use fonts::FontPack;
+pub use foreign::{ForeignPrefix, Tool};
+pub use index::{Candidate, Index, IndexSort, LongIndex, PrettyIndex, Source};
+pub use install::GeRelease;
+pub use interrupt::Interrupt;
+pub use launched::Launched;
+pub use menus::MenuEntries;
+pub use metrics::{Metrics, RunRecord, Stats};
+pub use monitor::Usage;
+pub use nvidia::Nvidia;
+pub use outcome::RunOutcome;
+pub use plan::ExecutionPlan;
+pub use policy::{CompatPolicy, Policy};
+pub use prefix::{Arch, Orphan, Prefix, PrefixInfo, PrefixListing};
+pub use recent::Recent;
+pub use runtime::Runtime;
+pub use saves::{SaveLocation, Saves, SyncHooks};
+pub use shaders::Shaders;
+pub use shell::Shell;
 use std::borrow::Cow;
+use std::cell::OnceCell;
+use std::collections::BTreeMap;
 use std::fs::create_dir;
-pub use version::Version;
+pub use steam::CompatTool;
+pub use timing::Timing;
+pub use tricks::Tricks;
+pub use util::{parse_age, windows_path};
+pub use version::{Keyword, Version};
+pub use wait::Wait;
+pub use warnings::{Warning, Warnings};
 
-use std::path::PathBuf;
-use std::process::ExitStatus;
+use discord::Presence;
+use monitor::Monitor;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, ExitStatus};
+use std::time::{Duration, Instant};
+use timing::FirstOutput;
+use util::{cache_dir, shell_quote};
 
 /// Type to handle executing Proton
 #[derive(Debug)]
@@ -37,10 +127,118 @@ pub struct Proton {
     log: bool,
     compat: PathBuf,
     steam: PathBuf,
+    verb: String,
+    builtin: bool,
+    prefix: Option<PathBuf>,
+    sync: SyncHooks,
+    wine: Option<String>,
+    env: Vec<(String, String, EnvSource)>,
+    appid: Option<u32>,
+    overlay: Option<bool>,
+    template: Option<String>,
+    force: bool,
+    log_file: Option<PathBuf>,
+    output: Output,
+    inherit: Inherit,
+    retries: u32,
+    policy: CompatPolicy,
+    runtime: Option<Runtime>,
+    arch: Option<Arch>,
+    tty: Option<bool>,
+    wait: Wait,
+    interrupt: Interrupt,
+    monitor: Option<Duration>,
+    fixes: Option<Fixes>,
+    fix: OnceCell<Option<Fix>>,
+    time_limit: Option<Duration>,
+    overrides: Vec<(String, String)>,
+    discord: Option<Discord>,
+    desktop: Option<VirtualDesktop>,
+}
+
+/// A finished launch: the command line run, its exit status, timing and Proton log
+type Executed = (Vec<String>, ExitStatus, Timing, Option<PathBuf>);
+
+/// gamescope running a precache without a window
+static HEADLESS: &str = "gamescope --backend headless --";
+
+/// Open file limit esync needs to work reliably, as recommended by Proton
+const ESYNC_FILE_LIMIT: u64 = 524_288;
+
+/// Where Proton's Wine build keeps its Wayland driver, relative to `files` or `dist`
+static WAYLAND_DRIVER: &[&str] = &[
+    "lib/wine/x86_64-unix/winewayland.so",
+    "lib64/wine/x86_64-unix/winewayland.so",
+];
+
+/// Variables kept by `--clean-env`, besides `LC_*` locale variables
+static CLEAN_ENV: &[&str] = &[
+    "HOME",
+    "USER",
+    "LOGNAME",
+    "PATH",
+    "SHELL",
+    "LANG",
+    "LANGUAGE",
+    "TZ",
+    "DISPLAY",
+    "XAUTHORITY",
+    "WAYLAND_DISPLAY",
+    "XDG_RUNTIME_DIR",
+    "XDG_SESSION_TYPE",
+    "XDG_CURRENT_DESKTOP",
+    "XDG_DATA_DIRS",
+    "XDG_CONFIG_DIRS",
+    "DBUS_SESSION_BUS_ADDRESS",
+    "PULSE_SERVER",
+];
+
+/// Which of the caller's environment variables reach the program
+#[derive(Debug, Default)]
+struct Inherit {
+    clean: bool,
+    allow: Vec<String>,
+    deny: Vec<String>,
+}
+
+impl Inherit {
+    /// Returns true if the caller's variable `name` should reach the program
+    fn keeps(&self, name: &str) -> bool {
+        if self.deny.iter().any(|p| env_match(p, name)) {
+            return false;
+        }
+
+        // The session basics survive an allow list too, the program can not start without them
+        if self.clean || !self.allow.is_empty() {
+            self.allow.iter().any(|p| env_match(p, name))
+                || CLEAN_ENV.contains(&name)
+                || name.starts_with("LC_")
+        } else {
+            true
+        }
+    }
+}
+
+/// Matches a variable name against a pattern, where a trailing `*` matches any suffix
+fn env_match(pattern: &str, name: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => name.starts_with(prefix),
+        None => pattern == name,
+    }
+}
+
+/// Where the program's output streams go, and whether they are watched for timing
+#[derive(Debug, Default)]
+struct Output {
+    stdout: Option<PathBuf>,
+    stderr: Option<PathBuf>,
+    append: bool,
+    timed: bool,
 }
 
 impl Proton {
     #[must_use]
+    #[deprecated(note = "use `ProtonBuilder`, which names each setting and checks them")]
     /// Creates a new instance of `Proton`
     pub fn new(
         version: Version,
@@ -50,6 +248,19 @@ impl Proton {
         log: bool,
         compat: PathBuf,
         steam: PathBuf,
+    ) -> Proton {
+        Proton::create(version, path, program, args, log, compat, steam)
+    }
+
+    /// Creates a new instance of `Proton` from its required settings, see `ProtonBuilder`
+    fn create(
+        version: Version,
+        path: PathBuf,
+        program: PathBuf,
+        args: Vec<String>,
+        log: bool,
+        compat: PathBuf,
+        steam: PathBuf,
     ) -> Proton {
         Proton {
             version,
@@ -59,93 +270,1662 @@ impl Proton {
             log,
             compat,
             steam,
+            verb: String::from("run"),
+            builtin: false,
+            prefix: None,
+            sync: SyncHooks::default(),
+            wine: None,
+            env: Vec::new(),
+            appid: None,
+            overlay: None,
+            template: None,
+            force: false,
+            log_file: None,
+            output: Output::default(),
+            inherit: Inherit::default(),
+            retries: 0,
+            policy: CompatPolicy::default(),
+            runtime: None,
+            arch: None,
+            tty: None,
+            wait: Wait::Program,
+            interrupt: Interrupt::Leave,
+            monitor: None,
+            fixes: None,
+            fix: OnceCell::new(),
+            time_limit: None,
+            overrides: Vec::new(),
+            discord: None,
+            desktop: None,
         }
         .update_path()
     }
 
+    #[must_use]
+    /// Creates a new instance of `Proton` which runs through the plain Wine build `name` at `path`
+    ///
+    /// The Wine prefix is kept in a `pfx` directory of the compat data directory, like Proton's.
+    pub fn wine(
+        name: &str,
+        path: PathBuf,
+        program: PathBuf,
+        args: Vec<String>,
+        compat: PathBuf,
+        steam: PathBuf,
+    ) -> Proton {
+        let wine: PathBuf = path.join("bin/wine");
+        let mut proton: Proton =
+            Proton::create(Version::Custom, path, program, args, false, compat, steam);
+
+        proton.wine = Some(name.to_string());
+        proton.path = wine;
+        proton
+    }
+
+    /// Creates a new instance of `Proton` running `program` with what `proton` names, as `-p`
+    /// does: a version number, a keyword such as `latest`, or an indexed Wine runner
+    ///
+    /// When `None`, the newest installed mainline Proton is used, as `latest` resolves, falling
+    /// back to `Version::default()` only when no mainline Proton is installed.
+    ///
+    /// # Errors
+    ///
+    /// Will fail if indexing `common` fails or nothing installed matches `proton`
+    pub fn from_config(
+        config: &Config,
+        program: PathBuf,
+        args: Vec<String>,
+        proton: Option<&str>,
+    ) -> Result<Proton, Error> {
+        let requested: &str = match proton {
+            Some(requested) => requested,
+            None => return Proton::from_latest(config, program, args),
+        };
+
+        if let Ok(version) = requested.parse::<Version>() {
+            return Proton::from_version(config, version, program, args);
+        }
+
+        if let Ok(keyword) = requested.parse::<Keyword>() {
+            let Some((version, path)) = Index::from_config(config)?.resolve(keyword) else {
+                throw!(
+                    Kind::ProtonMissing,
+                    "no installed Proton matches '{}'",
+                    keyword
+                );
+            };
+
+            return pass!(Proton::create(
+                version,
+                path,
+                program,
+                args,
+                false,
+                config.data(),
+                config.steam(),
+            ));
+        }
+
+        let Some(wine_path) = Index::from_config(config)?.runner(requested) else {
+            throw!(
+                Kind::ProtonMissing,
+                "'{}' is neither a Proton version nor an indexed Wine runner",
+                requested
+            );
+        };
+
+        pass!(Proton::wine(
+            requested,
+            wine_path,
+            program,
+            args,
+            config.data(),
+            config.steam(),
+        ))
+    }
+
+    /// Creates a new instance of `Proton` with the newest installed mainline Proton, or the
+    /// default version when there is none
+    fn from_latest(config: &Config, program: PathBuf, args: Vec<String>) -> Result<Proton, Error> {
+        let Some((version, path)) = Index::from_config(config)?.resolve(Keyword::Latest) else {
+            return Proton::from_version(config, Version::default(), program, args);
+        };
+
+        pass!(Proton::create(
+            version,
+            path,
+            program,
+            args,
+            false,
+            config.data(),
+            config.steam(),
+        ))
+    }
+
+    /// Creates a new instance of `Proton` with the Proton `version` in `common`
+    fn from_version(
+        config: &Config,
+        version: Version,
+        program: PathBuf,
+        args: Vec<String>,
+    ) -> Result<Proton, Error> {
+        // The usual directory names are checked first, sparing most launches indexing all of common
+        let found: Option<PathBuf> = if let Some(pp) = Index::find(&config.common(), version) {
+            Some(pp)
+        } else {
+            Index::from_config(config)?.get(version)
+        };
+
+        let Some(proton_path) = found else {
+            throw!(Kind::ProtonMissing, "Proton {} does not exist", version);
+        };
+
+        pass!(Proton::create(
+            version,
+            proton_path,
+            program,
+            args,
+            false,
+            config.data(),
+            config.steam(),
+        ))
+    }
+
+    #[must_use]
+    /// Passes the `PROTON_LOG` variable to Proton, writing a log of the launch
+    pub fn log(mut self) -> Proton {
+        self.log = true;
+        self
+    }
+
+    #[must_use]
+    /// Sets the verb passed to the Proton script, defaults to `run`
+    pub fn verb(mut self, verb: &str) -> Proton {
+        self.verb = verb.to_string();
+        self
+    }
+
+    #[must_use]
+    /// Treats `program` as a Wine built-in such as `wineboot`, skipping the existence check
+    pub fn builtin(mut self) -> Proton {
+        self.builtin = true;
+        self
+    }
+
+    #[must_use]
+    /// Runs in the given compat data directory instead of the per version one in `compat`
+    pub fn prefix(mut self, prefix: PathBuf) -> Proton {
+        self.prefix = Some(prefix);
+        self
+    }
+
     /// Appends the executable to the path
     fn update_path(mut self) -> Proton {
-        let str: Cow<str> = self.path.to_string_lossy();
-        let str: String = format!("{}/proton", str);
-        self.path = PathBuf::from(str);
+        // A path to the script itself is kept, a Proton directory holds it as `proton`
+        if !self.path.is_file() {
+            self.path = self.path.join("proton");
+        }
+
         self
     }
 
-    fn create_p_dir(&mut self) -> Result<(), Error> {
-        let name: Cow<str> = self.compat.to_string_lossy();
-        let newdir: PathBuf = PathBuf::from(format!("{}/Proton {}", name, self.version));
+    #[must_use]
+    /// Sets the Steam app id of the program, otherwise it is found from its Steam library or generated
+    pub fn appid(mut self, appid: u32) -> Proton {
+        self.appid = Some(appid);
+        self
+    }
 
-        if !newdir.exists() {
-            if let Err(e) = create_dir(&newdir) {
-                throw!(Kind::ProtonDir, "failed to create Proton directory: {}", e);
-            }
-        }
+    #[must_use]
+    /// Loads the Steam overlay into the program, or strips an inherited one when `false`
+    pub fn overlay(mut self, enable: bool) -> Proton {
+        self.overlay = Some(enable);
+        self
+    }
 
-        self.compat = newdir;
+    #[must_use]
+    /// Runs the program on a pseudo-terminal, or never when `false`, instead of only for
+    /// console programs launched from a terminal
+    pub fn tty(mut self, enable: bool) -> Proton {
+        self.tty = Some(enable);
+        self
+    }
 
-        pass!()
+    #[must_use]
+    /// Runs the program on Wine's native Wayland driver instead of `XWayland`, for Proton builds
+    /// which ship it, checked before launching
+    pub fn wayland_native(self) -> Proton {
+        self.env("PROTON_ENABLE_WAYLAND", "1", EnvSource::Flag)
     }
 
-    fn check_proton(&self) -> Result<(), Error> {
-        if !self.path.exists() {
-            throw!(Kind::ProtonMissing, "{}", self.version);
-        }
+    #[must_use]
+    /// Sets what the launch waits for before it is reported as exited
+    pub fn wait(mut self, wait: Wait) -> Proton {
+        self.wait = wait;
+        self
+    }
 
-        pass!()
+    #[must_use]
+    /// Sets what happens to the prefix when proton-call is interrupted during the launch
+    pub fn interrupt(mut self, interrupt: Interrupt) -> Proton {
+        self.interrupt = interrupt;
+        self
     }
 
-    fn check_program(&self) -> Result<(), Error> {
-        if !self.program.exists() {
-            throw!(Kind::ProgramMissing, "{}", self.program.to_string_lossy());
-        }
+    #[must_use]
+    /// Samples the CPU, memory and GPU memory use of the launch's processes every `interval`
+    pub fn monitor(mut self, interval: Duration) -> Proton {
+        self.monitor = Some(interval);
+        self
+    }
 
-        pass!()
+    #[must_use]
+    /// Applies the fix `fixes` holds for the program, found by its app id or executable
+    pub fn fixes(mut self, fixes: Fixes) -> Proton {
+        self.fixes = Some(fixes);
+        self
     }
 
-    /// Changes `compat` path to the version of Proton in use, creates the directory if doesn't already exist
+    #[must_use]
+    /// Runs the program for `duration` to warm its shader caches, then shuts the prefix down,
+    /// headless through gamescope when it is installed, around any command template
     ///
-    /// # Errors
+    /// Reaching the time limit is reported by `RunOutcome::time_limit_reached`.
+    pub fn precache(mut self, duration: Duration) -> Proton {
+        self.time_limit = Some(duration);
+        self
+    }
+
+    #[must_use]
+    /// Runs the launch through a template such as `gamemoderun %command% -novid`
+    pub fn command_template(mut self, template: &str) -> Proton {
+        self.template = Some(template.to_string());
+        self
+    }
+
+    #[must_use]
+    /// Writes the Proton log to `file` instead of `$HOME/steam-<appid>.log`, enabling logging
+    pub fn log_file(mut self, file: PathBuf) -> Proton {
+        self.log = true;
+        self.log_file = Some(file);
+        self
+    }
+
+    #[must_use]
+    /// Redirects the program's standard output to `file`
+    pub fn stdout(mut self, file: PathBuf) -> Proton {
+        self.output.stdout = Some(file);
+        self
+    }
+
+    #[must_use]
+    /// Redirects the program's standard error to `file`
+    pub fn stderr(mut self, file: PathBuf) -> Proton {
+        self.output.stderr = Some(file);
+        self
+    }
+
+    #[must_use]
+    /// Appends to the files set by `Proton::stdout` and `Proton::stderr` instead of truncating them
+    pub fn append_output(mut self) -> Proton {
+        self.output.append = true;
+        self
+    }
+
+    #[must_use]
+    /// Reports how long the launch took to spawn, to write its first output and to exit
     ///
-    /// Will fail on:
-    /// * Creating a Proton compat env directory fails
-    /// * Executing Proton fails
-    pub fn run(mut self) -> Result<ExitStatus, Error> {
-        self.create_p_dir()?;
-        self.check_proton()?;
-        self.check_program()?;
-        self.execute()
+    /// Output which is not redirected to a file is passed through proton-call to time it.
+    pub fn time(mut self) -> Proton {
+        self.output.timed = true;
+        self
+    }
+
+    #[must_use]
+    /// Starts from a minimal environment instead of inheriting the caller's
+    ///
+    /// Only the session basics, such as `HOME`, `PATH` and the display variables, are kept,
+    /// with the variables proton-call sets on top.
+    pub fn clean_env(mut self) -> Proton {
+        self.inherit.clean = true;
+        self
+    }
+
+    #[must_use]
+    /// Limits the caller's variables reaching the program to those matching `allow`, when given,
+    /// and never passes those matching `deny`
+    ///
+    /// Patterns are variable names, a trailing `*` matches any suffix such as `LC_*`. The session
+    /// basics `clean_env` keeps pass along with `allow` unless `deny` matches them, and variables
+    /// proton-call sets itself are not affected.
+    pub fn inherit_env(mut self, allow: Vec<String>, deny: Vec<String>) -> Proton {
+        self.inherit.allow = allow;
+        self.inherit.deny = deny;
+        self
+    }
+
+    #[must_use]
+    /// Launches again, up to `retries` times, when the program fails while starting up in a
+    /// way which usually succeeds on a second try, such as the display not being ready
+    pub fn retries(mut self, retries: u32) -> Proton {
+        self.retries = retries;
+        self
+    }
+
+    #[must_use]
+    /// Launches Proton through the Steam Linux Runtime container `runtime`, or none, instead of
+    /// the one the Proton version requires
+    pub fn runtime(mut self, runtime: Runtime) -> Proton {
+        self.runtime = Some(runtime);
+        self
+    }
+
+    #[must_use]
+    /// Runs the program through the 32-bit Wine loader of Proton's dist, for 16 and 32-bit
+    /// installers which fail under the default 64-bit path
+    ///
+    /// The prefix must already be set up by a regular launch.
+    pub fn win32(mut self) -> Proton {
+        self.arch = Some(Arch::Win32);
+        self
+    }
+
+    #[must_use]
+    /// Sets what to do when launching into a prefix another Proton version last used
+    pub fn compat_policy(mut self, policy: CompatPolicy) -> Proton {
+        self.policy = policy;
+        self
+    }
+
+    #[must_use]
+    /// Launches even when the prefix architecture does not fit the launch, only warning
+    pub fn force(mut self) -> Proton {
+        self.force = true;
+        self
     }
 
-    /// Executes Proton
-    fn execute(self) -> Result<ExitStatus, Error> {
-        use std::process::{Child, Command};
+    #[must_use]
+    /// Sets an extra environment variable for the launch, noting where it comes from
+    pub fn env(mut self, key: &str, value: &str, source: EnvSource) -> Proton {
+        self.env.push((key.to_string(), value.to_string(), source));
+        self
+    }
 
-        println!(
-            "Running Proton {} for {}",
-            self.version,
-            self.program.to_string_lossy()
+    #[must_use]
+    /// Sets the variables `vars` for the program as if given with `-e`, over any set before
+    pub fn envs<I, K, V>(mut self, vars: I) -> Proton
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.env.extend(
+            vars.into_iter()
+                .map(|(key, value)| (key.into(), value.into(), EnvSource::Flag)),
         );
+        self
+    }
 
-        let log: &str = if self.log { "1" } else { "0" };
+    #[must_use]
+    /// Overrides how Wine loads `dll`, such as `d` to disable it, added to any
+    /// `WINEDLLOVERRIDES` the launch inherits or its fix sets
+    pub fn dll_override(mut self, dll: &str, mode: &str) -> Proton {
+        self.overrides.push((dll.to_string(), mode.to_string()));
+        self
+    }
 
-        let mut child: Child = match Command::new(&self.path)
-            .arg("run")
-            .arg(&self.program)
-            .args(&self.args)
-            .env("PROTON_LOG", log)
-            .env("STEAM_COMPAT_DATA_PATH", &self.compat)
-            .env("STEAM_COMPAT_CLIENT_INSTALL_PATH", &self.steam)
-            .spawn()
-        {
-            Ok(c) => c,
-            Err(e) => throw!(Kind::ProtonSpawn, "{}\nDebug:\n{:#?}", e, self),
+    #[must_use]
+    /// Disables winedbg, so a crashing program exits instead of waiting on Wine's crash dialog
+    pub fn no_crash_dialog(self) -> Proton {
+        self.dll_override("winedbg.exe", "d")
+    }
+
+    #[must_use]
+    /// Disables winemenubuilder, so installers do not add `.desktop` entries and file
+    /// associations for the prefix to the host's menus
+    pub fn no_menus(self) -> Proton {
+        self.dll_override("winemenubuilder.exe", "d")
+    }
+
+    #[must_use]
+    /// Runs the program inside a Wine virtual desktop, which helps older programs which break
+    /// on alt-tab or change the screen's resolution
+    pub fn virtual_desktop(mut self, desktop: VirtualDesktop) -> Proton {
+        self.desktop = Some(desktop);
+        self
+    }
+
+    #[must_use]
+    /// Bridges the host's Discord client into the launch, showing the program as played
+    pub fn discord(mut self, discord: Discord) -> Proton {
+        self.discord = Some(discord);
+        self
+    }
+
+    #[must_use]
+    /// Sets commands which sync the program's saves before and after running
+    pub fn sync_hooks(mut self, hooks: SyncHooks) -> Proton {
+        self.sync = hooks;
+        self
+    }
+
+    /// Returns the compat data directory the launch runs in
+    fn compat_dir(&self) -> PathBuf {
+        if let Some(prefix) = &self.prefix {
+            return prefix.clone();
+        }
+
+        let name: Cow<str> = self.compat.to_string_lossy();
+        match &self.wine {
+            Some(wine) => PathBuf::from(format!("{}/{}", name, wine)),
+            None => PathBuf::from(format!("{}/Proton {}", name, self.version)),
+        }
+    }
+
+    fn create_p_dir(&mut self) -> Result<(), Error> {
+        let newdir: PathBuf = self.compat_dir();
+
+        let created: std::io::Result<()> = if self.prefix.take().is_some() {
+            std::fs::create_dir_all(&newdir)
+        } else if newdir.exists() {
+            Ok(())
+        } else {
+            create_dir(&newdir)
         };
 
-        let status: ExitStatus = match child.wait() {
-            Ok(e) => e,
-            Err(e) => throw!(Kind::ProtonWait, "'{}': {}", child.id(), e),
+        if let Err(e) = created {
+            throw!(Kind::ProtonDir, "failed to create Proton directory: {}", e);
+        }
+
+        self.compat = newdir;
+
+        pass!()
+    }
+
+    #[must_use]
+    /// Renders the launch as a Lutris game config, without creating or running anything
+    pub fn lutris(&self) -> String {
+        let compat: PathBuf = self.compat_dir();
+        let mut env: Vec<(&str, String)> = Vec::new();
+
+        let version: String = if let Some(wine) = &self.wine {
+            wine.clone()
+        } else {
+            let log: &str = if self.log { "1" } else { "0" };
+            env.push(("PROTON_LOG", log.to_string()));
+            env.push((
+                "STEAM_COMPAT_DATA_PATH",
+                compat.to_string_lossy().to_string(),
+            ));
+            env.push((
+                "STEAM_COMPAT_CLIENT_INSTALL_PATH",
+                self.steam.to_string_lossy().to_string(),
+            ));
+
+            match self.path.parent().and_then(Path::file_name) {
+                Some(dir) => dir.to_string_lossy().to_string(),
+                None => format!("Proton {}", self.version),
+            }
         };
 
-        pass!(status)
+        foreign::lutris_game(
+            &self.program,
+            &self.args,
+            &compat.join("pfx"),
+            &version,
+            &env,
+        )
+    }
+
+    fn check_proton(&self) -> Result<(), Error> {
+        if !self.path.exists() {
+            match &self.wine {
+                Some(wine) => throw!(Kind::ProtonMissing, "Wine {}", wine),
+                None => throw!(Kind::ProtonMissing, "{}", self.version),
+            }
+        }
+
+        pass!()
     }
+
+    /// Checks that a win32 prefix is not used by Proton or a 64-bit program
+    fn check_arch(&self, prefix: &Prefix) -> Result<(), Error> {
+        if prefix.arch() != Some(Arch::Win32) {
+            return pass!();
+        }
+
+        let problem: &str = if self.wine.is_none() {
+            "Proton only runs win64 prefixes"
+        } else if !self.builtin && util::pe_arch(&self.program) == Some(Arch::Win64) {
+            "the program is 64-bit"
+        } else {
+            return pass!();
+        };
+
+        if self.force {
+            Warnings::push(
+                "launch",
+                format!("'{}' is a win32 prefix, {}", prefix.name(), problem),
+            );
+            return pass!();
+        }
+
+        throw!(
+            Kind::ArchMismatch,
+            "'{}' is a win32 prefix, {}, use --force to launch anyway",
+            prefix.name(),
+            problem
+        )
+    }
+
+    /// Checks launching into a prefix another Proton version last used against the compat policy
+    fn check_compat(&self, prefix: &Prefix) -> Result<(), Error> {
+        match prefix.last_version() {
+            Some(last) if self.wine.is_none() => {
+                self.policy
+                    .check(prefix.name(), last, self.version, self.force)
+            }
+            _ => pass!(),
+        }
+    }
+
+    /// Finds the program's fix in the fixes database once, by the app id it was given or its
+    /// Steam library's, or by its executable
+    fn fix(&self) -> Option<&Fix> {
+        self.fix
+            .get_or_init(|| {
+                if self.builtin {
+                    return None;
+                }
+
+                let appid: Option<u32> = self.appid.or_else(|| steam::library_appid(&self.program));
+                self.fixes.as_ref()?.find(appid, &self.program)
+            })
+            .as_ref()
+    }
+
+    /// Installs the winetricks verbs of the program's fix which the prefix does not have yet
+    fn install_fix_verbs(&self, prefix: &Prefix) -> Result<(), Error> {
+        let Some(fix) = self.fix() else {
+            return pass!();
+        };
+
+        let installed: Vec<String> = prefix.fix_verbs();
+        let missing: Vec<String> = fix
+            .verbs()
+            .iter()
+            .filter(|verb| !installed.contains(verb))
+            .cloned()
+            .collect();
+
+        if missing.is_empty() {
+            return pass!();
+        }
+
+        if self.wine.is_some() || !prefix.is_initialized() {
+            Warnings::push(
+                "launch",
+                format!(
+                    "the fix's winetricks verbs {} are installed on a launch with Proton, once the prefix is set up",
+                    missing.join(" ")
+                ),
+            );
+            return pass!();
+        }
+
+        let proton_dir: &Path = self.path.parent().unwrap_or(&self.path);
+        Tricks::new(missing.clone())?.in_prefix(prefix, proton_dir)?;
+        prefix.record_fix_verbs(&missing)
+    }
+
+    /// Warns when the open file limit is too low for esync, which Proton uses unless disabled
+    fn check_limits(&self) {
+        let env: Environment = self.environment_in(&self.compat, None);
+        let disabled: bool =
+            matches!(env.get("PROTON_NO_ESYNC"), Some(v) if !v.is_empty() && v != "0");
+
+        match util::open_file_limit() {
+            Some(limit) if !disabled && self.wine.is_none() && limit < ESYNC_FILE_LIMIT => {
+                Warnings::push(
+                    "launch",
+                    format!(
+                        "the open file limit is {}, esync needs {} or more, see `ulimit -Hn`",
+                        limit, ESYNC_FILE_LIMIT
+                    ),
+                );
+            }
+            _ => (),
+        }
+    }
+
+    /// Warns when precaching without gamescope, which shows the program's window
+    fn check_precache(&self) {
+        if self.time_limit.is_some() && !util::in_path("gamescope") {
+            Warnings::push(
+                "launch",
+                String::from("gamescope is not installed, precaching in a window"),
+            );
+        }
+    }
+
+    /// Checks that the Proton in use ships the Wayland driver, and a Wayland session is running,
+    /// when the launch enables it
+    fn check_wayland(&self) -> Result<(), Error> {
+        let enabled: bool = self
+            .env
+            .iter()
+            .any(|(key, value, _)| key == "PROTON_ENABLE_WAYLAND" && value == "1");
+        if !enabled {
+            return pass!();
+        }
+
+        if self.wine.is_some() {
+            throw!(
+                Kind::Unsupported,
+                "the native Wayland driver is enabled through Proton, not Wine {}",
+                self.wine.as_deref().unwrap_or_default()
+            );
+        }
+
+        let shipped: bool = self.dist().is_some_and(|dist| {
+            WAYLAND_DRIVER
+                .iter()
+                .any(|driver| dist.join(driver).is_file())
+        });
+        if !shipped {
+            throw!(
+                Kind::Unsupported,
+                "Proton {} does not ship Wine's Wayland driver, try a GE-Proton or experimental build",
+                self.version
+            );
+        }
+
+        if std::env::var_os("WAYLAND_DISPLAY").is_none() {
+            throw!(
+                Kind::Environment,
+                "the native Wayland driver needs a Wayland session, WAYLAND_DISPLAY is not set"
+            );
+        }
+
+        pass!()
+    }
+
+    fn check_program(&self) -> Result<(), Error> {
+        if !self.builtin && !self.program.exists() {
+            throw!(Kind::ProgramMissing, "{}", self.program.to_string_lossy());
+        }
+
+        pass!()
+    }
+
+    /// Changes `compat` path to the version of Proton in use, creates the directory if doesn't already exist
+    ///
+    /// # Errors
+    ///
+    /// Will fail on:
+    /// * Waiting for the children of a program using the terminal
+    /// * Creating a Proton compat env directory fails
+    /// * The prefix architecture does not fit the launch
+    /// * The save sync hook before the launch fails
+    /// * Executing Proton fails
+    ///
+    /// The hook after the launch and the resource monitor run even when Proton fails, a failing
+    /// after hook is reported as a warning.
+    pub fn run(mut self) -> Result<RunOutcome, Error> {
+        self.check_wait()?;
+        let prefix: Prefix = self.prepare()?;
+
+        let sync: SyncHooks = std::mem::take(&mut self.sync);
+        let compat: PathBuf = self.compat.clone();
+        let proton: PathBuf = self.path.clone();
+        let version: String = match &self.wine {
+            Some(wine) => wine.clone(),
+            None => self.version.to_string(),
+        };
+
+        sync.before(&compat)?;
+        let start: Instant = Instant::now();
+        let monitor: Option<Monitor> = self.monitor.map(Monitor::start);
+        let presence: Option<Presence> = self.presence();
+        let executed: Result<Executed, Error> = self.execute_waiting(start);
+
+        drop(presence);
+        let usage: Option<Usage> = monitor.map(Monitor::stop);
+        if let Err(e) = sync.after(&compat) {
+            Warnings::push(
+                "launch",
+                format!("the save sync hook after the launch: {}", e),
+            );
+        }
+
+        let (command_line, status, timing, log): Executed = executed?;
+
+        prefix.record_arch()?;
+
+        if self.output.timed {
+            ProtonEvent::Timed(timing).emit();
+        }
+
+        if let Some(usage) = usage {
+            ProtonEvent::Used(usage).emit();
+        }
+
+        pass!(RunOutcome::new(status, timing, compat, proton, version)
+            .with_usage(usage)
+            .with_launch(command_line, log))
+    }
+
+    /// Starts the launch without waiting for it, returning a handle to the running program
+    ///
+    /// The program leads a process group of its own, so it keeps running once proton-call
+    /// exits. Save sync hooks, retries, waiting modes, time limits and resource monitoring only
+    /// apply to `Proton::run`, and the Proton log is collected by `Launched::wait`.
+    ///
+    /// # Errors
+    ///
+    /// Will fail on:
+    /// * Creating a Proton compat env directory fails
+    /// * The prefix architecture does not fit the launch
+    /// * Spawning Proton fails
+    pub fn spawn(mut self) -> Result<Launched, Error> {
+        let (mut command, log): (Command, Option<(PathBuf, PathBuf)>) = self.detached()?;
+
+        let child: Child = match command.spawn() {
+            Ok(c) => c,
+            Err(e) => throw!(Kind::ProtonSpawn, "{}\nDebug:\n{:#?}", e, self),
+        };
+        self.record_use();
+
+        pass!(Launched::new(child, self.compat, log))
+    }
+
+    /// Prepares the launch and builds its command in a process group of its own, with the
+    /// directory the Proton log is staged in and the file it is moved to
+    fn detached(&mut self) -> Result<(Command, Option<(PathBuf, PathBuf)>), Error> {
+        use std::os::unix::process::CommandExt;
+
+        self.prepare()?;
+
+        let (mut command, log_dir): (Command, Option<PathBuf>) = self.launch_command()?;
+        command.process_group(0);
+
+        pass!((command, log_dir.zip(self.log_file.clone())))
+    }
+
+    /// Checks the launch and sets up its compat data directory and prefix, returning the prefix
+    fn prepare(&mut self) -> Result<Prefix, Error> {
+        self.create_p_dir()?;
+        self.check_proton()?;
+        self.check_program()?;
+        self.check_wayland()?;
+        self.check_limits();
+        self.check_precache();
+
+        let prefix: Prefix = Prefix::new(self.compat.clone());
+        self.check_arch(&prefix)?;
+        self.check_compat(&prefix)?;
+        self.install_fix_verbs(&prefix)?;
+
+        pass!(prefix)
+    }
+
+    /// Records the prefix as used, and by which Proton, once the launch has spawned
+    ///
+    /// The program is running by then, so failing to record is a warning rather than an error.
+    pub(crate) fn record_use(&self) {
+        let prefix: Prefix = Prefix::new(self.compat.clone());
+        let mut recorded: Result<(), Error> = prefix.mark_used();
+
+        if recorded.is_ok() && self.wine.is_none() && self.version != Version::Custom {
+            recorded = prefix.record_version(self.version);
+        }
+
+        if let Err(e) = recorded {
+            Warnings::push(
+                "launch",
+                format!("recording the use of '{}': {}", prefix.name(), e),
+            );
+        }
+    }
+
+    /// Runs like `Proton::run`, capturing the program's standard output and error instead of
+    /// passing them through, for frontends to show
+    ///
+    /// The output is staged in proton-call's cache directory until the launch exits, replacing
+    /// any file set by `Proton::stdout` and `Proton::stderr`.
+    ///
+    /// # Errors
+    ///
+    /// Will fail as `Proton::run` does, or if the captured output can not be staged
+    pub fn run_captured(self) -> Result<RunOutcome, Error> {
+        let dir: PathBuf = cache_dir("capture")?;
+        let stamp: u128 = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or_default();
+        let name: String = format!("{}-{}", std::process::id(), stamp);
+        let stdout: PathBuf = dir.join(format!("{}.stdout", name));
+        let stderr: PathBuf = dir.join(format!("{}.stderr", name));
+
+        let outcome: Result<RunOutcome, Error> =
+            self.stdout(stdout.clone()).stderr(stderr.clone()).run();
+
+        let read = |path: &Path| -> String {
+            let text: String = std::fs::read(path)
+                .map(|bytes| String::from_utf8_lossy(&bytes).to_string())
+                .unwrap_or_default();
+            let _ = std::fs::remove_file(path);
+            text
+        };
+        let captured: (String, String) = (read(&stdout), read(&stderr));
+
+        pass!(outcome?.with_output(captured.0, captured.1))
+    }
+
+    #[must_use]
+    /// Returns the environment the launch will run with, and where each variable comes from
+    pub fn environment(&self) -> Environment {
+        let log_dir: Option<PathBuf> = self.log_file.as_ref().and_then(|_| cache_dir("logs").ok());
+
+        self.environment_in(&self.compat_dir(), log_dir.as_deref())
+    }
+
+    /// Resolves the launch into a plan to run elsewhere, without running it
+    ///
+    /// # Errors
+    ///
+    /// Will fail if the Proton version or the Steam Linux Runtime it was asked to use is missing
+    pub fn plan(&self) -> Result<ExecutionPlan, Error> {
+        let compat: PathBuf = self.compat_dir();
+        let invocation: Vec<OsString> = self.invocation()?;
+        let command: Command = self.wrap(&invocation);
+
+        let argv: Vec<String> = argv(&command);
+
+        let env: BTreeMap<String, String> = self
+            .environment_in(&compat, None)
+            .vars()
+            .map(|(k, v, _)| {
+                (
+                    k.to_string_lossy().to_string(),
+                    v.to_string_lossy().to_string(),
+                )
+            })
+            .collect();
+
+        pass!(ExecutionPlan::new(
+            self.program.clone(),
+            argv,
+            env,
+            std::env::current_dir().ok(),
+            compat,
+        ))
+    }
+
+    /// Builds the command the launch runs, with its complete environment, without running it
+    ///
+    /// # Errors
+    ///
+    /// Will fail if the Proton version or the Steam Linux Runtime it was asked to use is missing
+    pub fn command(&self) -> Result<Command, Error> {
+        self.plan()?.command()
+    }
+
+    /// Describes what the launch resolves to, for reading: the Proton or Wine build and verb,
+    /// the compat data directory and prefix, the command line, and every variable with where it
+    /// comes from
+    ///
+    /// # Errors
+    ///
+    /// Will fail if the Proton version or the Steam Linux Runtime it was asked to use is missing
+    pub fn describe(&self) -> Result<String, Error> {
+        let compat: PathBuf = self.compat_dir();
+        let command: Command = self.wrap(&self.invocation()?);
+
+        let line: Vec<String> = std::iter::once(command.get_program())
+            .chain(command.get_args())
+            .map(|a| shell_quote(&a.to_string_lossy()))
+            .collect();
+
+        let mut lines: Vec<String> = match &self.wine {
+            Some(wine) => vec![format!("Wine {}: {}", wine, self.path.to_string_lossy())],
+            None => vec![
+                format!("Proton {}: {}", self.version, self.path.to_string_lossy()),
+                format!("Verb: {}", self.verb),
+            ],
+        };
+
+        lines.push(format!("Compat data: {}", compat.to_string_lossy()));
+        lines.push(format!(
+            "Wine prefix: {}",
+            compat.join("pfx").to_string_lossy()
+        ));
+        lines.push(format!("Command: {}", line.join(" ")));
+        lines.push(format!("\n{}", self.environment()));
+
+        pass!(lines.join("\n"))
+    }
+
+    /// Shuts down every Wine process in the compat data directory a launch uses through its
+    /// `wineserver`, returning how many processes were killed
+    ///
+    /// With `force`, processes left running by a hung `wineserver` are killed with `SIGKILL`,
+    /// found by the `WINEPREFIX` they run in.
+    ///
+    /// # Errors
+    ///
+    /// Will fail if...
+    /// * The Proton version has no `wineserver`
+    /// * `wineserver -k` fails, unless `force` is set
+    pub fn kill(&self, force: bool) -> Result<usize, Error> {
+        let pfx: PathBuf = self.compat_dir().join("pfx");
+        let shutdown: Result<(), Error> = interrupt::kill_prefix(&self.wineserver()?, &pfx);
+
+        if !force {
+            return shutdown.map(|()| 0);
+        }
+
+        if let Err(e) = shutdown {
+            Warnings::push("kill", e.to_string());
+        }
+
+        pass!(interrupt::kill_processes(&pfx))
+    }
+
+    /// Builds the environment for a launch in `compat`, with the Proton log staged in `log_dir`
+    fn environment_in(&self, compat: &Path, log_dir: Option<&Path>) -> Environment {
+        let mut env: Environment = Environment::inherited(|name| self.inherit.keeps(name));
+
+        if self.wine.is_some() {
+            env.set("WINEPREFIX", compat.join("pfx"), EnvSource::ProtonCall);
+            self.extra_env(&mut env);
+            return env;
+        }
+
+        // Proton fixups look for the game here, and the runtime container exposes it
+        if !self.builtin {
+            if let Some(dir) = install_dir(&self.program) {
+                env.set("STEAM_COMPAT_INSTALL_PATH", dir, EnvSource::ProtonCall);
+            }
+        }
+
+        // Some games and protonfixes check the app id, so one is always given for programs
+        let appid: Option<(u32, EnvSource)> = match self.appid {
+            Some(appid) => Some((appid, EnvSource::Flag)),
+            None if self.builtin => None,
+            None => Some((
+                steam::library_appid(&self.program)
+                    .unwrap_or_else(|| steam::synthetic_appid(&self.program)),
+                EnvSource::ProtonCall,
+            )),
+        };
+
+        if let Some((appid, source)) = appid {
+            let appid: String = appid.to_string();
+            for key in ["SteamAppId", "SteamGameId", "STEAM_COMPAT_APP_ID"] {
+                env.set(key, &appid, source);
+            }
+        }
+
+        if let Some(enable) = self.overlay {
+            let current: String = env
+                .get("LD_PRELOAD")
+                .map(|p| p.to_string_lossy().to_string())
+                .unwrap_or_default();
+            env.set(
+                "LD_PRELOAD",
+                steam::overlay_preload(&current, &self.steam, enable),
+                EnvSource::Flag,
+            );
+        }
+
+        if let Some(dir) = log_dir {
+            env.set("PROTON_LOG_DIR", dir, EnvSource::Flag);
+        }
+
+        let log: &str = if self.log { "1" } else { "0" };
+        let source: EnvSource = if self.log {
+            EnvSource::Flag
+        } else {
+            EnvSource::ProtonCall
+        };
+        env.set("PROTON_LOG", log, source);
+
+        env.set("STEAM_COMPAT_DATA_PATH", compat, EnvSource::ProtonCall);
+        env.set(
+            "STEAM_COMPAT_CLIENT_INSTALL_PATH",
+            &self.steam,
+            EnvSource::ProtonCall,
+        );
+        for (key, value) in steam::library_env(&self.program, &self.steam) {
+            env.set(key, value, EnvSource::ProtonCall);
+        }
+
+        if self.arch == Some(Arch::Win32) {
+            self.loader_env(&mut env, compat);
+        }
+
+        self.extra_env(&mut env);
+        env
+    }
+
+    /// Adds the variables of the program's fix, then those set with `Proton::env`, which
+    /// override everything else
+    fn extra_env(&self, env: &mut Environment) {
+        if let Some(fix) = self.fix() {
+            for (key, value) in fix.env() {
+                env.set(key, value, EnvSource::Fix);
+            }
+
+            if let Some(overrides) = fix.dll_overrides() {
+                add_overrides(env, &overrides, EnvSource::Fix);
+            }
+        }
+
+        for (key, value, source) in &self.env {
+            env.set(key, value, *source);
+        }
+
+        if !self.overrides.is_empty() {
+            let overrides: Vec<String> = self
+                .overrides
+                .iter()
+                .map(|(dll, mode)| format!("{}={}", dll, mode))
+                .collect();
+            add_overrides(env, &overrides.join(";"), EnvSource::Flag);
+        }
+    }
+
+    /// Executes Proton and waits as the launch asks, returning the command line run along with
+    /// what `execute` returns
+    fn execute_waiting(&self, start: Instant) -> Result<Executed, Error> {
+        let command_line: Vec<String> = argv(&self.wrap(&self.invocation()?));
+        let (status, mut timing, log): (ExitStatus, Timing, Option<PathBuf>) =
+            self.execute(start)?;
+
+        if self.wait == Wait::Prefix && !interrupt::received() {
+            self.wait_prefix()?;
+            timing = timing.until(start, Instant::now());
+        }
+
+        pass!((command_line, status, timing, log))
+    }
+
+    /// Executes Proton, returning the file the Proton log was written to, if one was
+    fn execute(&self, start: Instant) -> Result<(ExitStatus, Timing, Option<PathBuf>), Error> {
+        let (mut command, log_dir): (Command, Option<PathBuf>) = self.launch_command()?;
+        let (status, timing): (ExitStatus, Timing) = self.spawn_retrying(&mut command, start)?;
+
+        if let (Some(dir), Some(file)) = (&log_dir, &self.log_file) {
+            collect_log(dir, file)?;
+            ProtonEvent::LogWritten(file.clone()).emit();
+            return pass!((status, timing, Some(file.clone())));
+        }
+
+        pass!((status, timing, None))
+    }
+
+    /// Builds the command launching the program through Proton, Wine or the 32-bit loader,
+    /// with the directory the Proton log is staged in
+    fn launch_command(&self) -> Result<(Command, Option<PathBuf>), Error> {
+        if self.wine.is_some() {
+            return pass!((self.wine_command()?, None));
+        }
+
+        if self.arch == Some(Arch::Win32) {
+            return pass!((self.win32_command()?, None));
+        }
+
+        ProtonEvent::Launching {
+            build: format!("Proton {}", self.version),
+            program: self.program.clone(),
+        }
+        .emit();
+
+        let invocation: Vec<OsString> = self.invocation()?;
+
+        // Proton picks the log's name itself, so it is written aside and moved afterwards
+        let log_dir: Option<PathBuf> = match &self.log_file {
+            Some(_) => Some(cache_dir("logs")?),
+            None => None,
+        };
+
+        let mut command: Command = self.wrap(&invocation);
+        self.environment_in(&self.compat, log_dir.as_deref())
+            .apply(&mut command);
+        self.redirect(&mut command)?;
+
+        pass!((command, log_dir))
+    }
+
+    /// Finds the entry point of the Steam Linux Runtime container to launch Proton through,
+    /// launching directly with a warning when the one the Proton version requires is missing
+    fn container(&self) -> Result<Option<PathBuf>, Error> {
+        let proton_dir: &Path = self.path.parent().unwrap_or(&self.path);
+        let runtime: Runtime = self
+            .runtime
+            .unwrap_or_else(|| Runtime::required(proton_dir));
+
+        if runtime == Runtime::None {
+            return pass!(None);
+        }
+
+        match runtime.entry_point(proton_dir, &self.steam) {
+            Some(entry) => pass!(Some(entry)),
+            None if self.runtime.is_some() => throw!(
+                Kind::ProtonMissing,
+                "the Steam Linux Runtime {} is not installed",
+                runtime
+            ),
+            None => {
+                Warnings::push(
+                    "launch",
+                    format!(
+                        "Proton {} runs in the Steam Linux Runtime {}, which is not installed, launched without it",
+                        self.version, runtime
+                    ),
+                );
+                pass!(None)
+            }
+        }
+    }
+
+    /// Builds the bare command running `invocation`, applying the command template
+    ///
+    /// `%command%` in the template is replaced with the quoted invocation, without it the
+    /// template is appended to the invocation, like Steam's launch options.
+    fn wrap(&self, invocation: &[OsString]) -> Command {
+        let quoted = || -> String {
+            let quoted: Vec<String> = invocation
+                .iter()
+                .map(|a| shell_quote(&a.to_string_lossy()))
+                .collect();
+            quoted.join(" ")
+        };
+
+        let mut line: Option<String> = self.template.as_ref().map(|template| {
+            if template.contains("%command%") {
+                template.replace("%command%", &quoted())
+            } else {
+                format!("{} {}", quoted(), template)
+            }
+        });
+
+        // Precaching runs headless, around the template so wrappers such as gamemoderun stay
+        if self.time_limit.is_some() && util::in_path("gamescope") {
+            line = Some(match line {
+                Some(line) => format!("{} sh -c {}", HEADLESS, shell_quote(&line)),
+                None => format!("{} {}", HEADLESS, quoted()),
+            });
+        }
+
+        // `script` runs the line on a pseudo-terminal, passing the terminal's input and output
+        // through, and exits with its status
+        if self.uses_tty() {
+            let mut command: Command = Command::new("script");
+            command
+                .arg("-qefc")
+                .arg(line.unwrap_or_else(quoted))
+                .arg("/dev/null");
+            return command;
+        }
+
+        if let Some(line) = line {
+            let mut command: Command = Command::new("sh");
+            command.arg("-c").arg(line);
+            return command;
+        }
+
+        let mut command: Command = Command::new(&invocation[0]);
+        command.args(&invocation[1..]);
+        command
+    }
+
+    /// Tells whether to run on a pseudo-terminal, by default for console programs launched
+    /// from a terminal with their output not redirected
+    fn uses_tty(&self) -> bool {
+        use std::io::IsTerminal;
+
+        self.tty.unwrap_or_else(|| {
+            !self.builtin
+                && self.output.stdout.is_none()
+                && std::io::stdin().is_terminal()
+                && util::pe_console(&self.program)
+        })
+    }
+
+    /// Tells whether the program is launched into a process group of its own, which signals
+    /// are forwarded to
+    ///
+    /// Programs using the terminal stay in proton-call's group, the terminal's foreground one.
+    fn own_group(&self) -> bool {
+        !self.uses_tty()
+    }
+
+    /// Checks waiting for the program's children, found by the process group the program leads,
+    /// is not asked of a program using the terminal, which stays in proton-call's group
+    fn check_wait(&self) -> Result<(), Error> {
+        if self.wait == Wait::Children && self.uses_tty() {
+            throw!(
+                Kind::Unsupported,
+                "the children of a program using the terminal can not be waited for, use --no-tty"
+            );
+        }
+
+        pass!()
+    }
+
+    /// Spawns `command` and waits for it, launching again after transient startup failures
+    fn spawn_retrying(
+        &self,
+        command: &mut Command,
+        start: Instant,
+    ) -> Result<(ExitStatus, Timing), Error> {
+        use std::process::Stdio;
+
+        // Error output is watched for a failure's signature, and output for its timing,
+        // through pipes unless redirected
+        if (self.retries > 0 || self.output.timed) && self.output.stderr.is_none() {
+            command.stderr(Stdio::piped());
+        }
+
+        if self.output.timed && self.output.stdout.is_none() {
+            command.stdout(Stdio::piped());
+        }
+
+        if self.own_group() {
+            use std::os::unix::process::CommandExt;
+
+            command.process_group(0);
+        }
+
+        let mut attempt: u32 = 0;
+
+        loop {
+            let (status, failure, timing) = self.spawn_watched(command, start)?;
+
+            match failure {
+                Some(line) if attempt < self.retries => {
+                    attempt += 1;
+                    ProtonEvent::Retrying {
+                        failure: line.trim().to_string(),
+                        attempt,
+                        retries: self.retries,
+                    }
+                    .emit();
+                }
+                _ => return pass!((status, timing)),
+            }
+        }
+    }
+
+    /// Spawns `command` and waits for it, returning the line of error output showing a
+    /// transient startup failure, if it failed with one
+    fn spawn_watched(
+        &self,
+        command: &mut Command,
+        start: Instant,
+    ) -> Result<(ExitStatus, Option<String>, Timing), Error> {
+        let offset: u64 = match &self.output.stderr {
+            Some(path) => std::fs::metadata(path).map(|m| m.len()).unwrap_or_default(),
+            None => 0,
+        };
+
+        let mut child: Child = match command.spawn() {
+            Ok(c) => c,
+            Err(e) => throw!(Kind::ProtonSpawn, "{}\nDebug:\n{:#?}", e, self),
+        };
+        let spawned: Instant = Instant::now();
+        self.record_use();
+        let forwarder: interrupt::Forwarder =
+            interrupt::Forwarder::start(child.id(), self.own_group())?;
+
+        let first: FirstOutput = FirstOutput::default();
+        let watcher = child
+            .stderr
+            .take()
+            .map(|stderr| retry::watch(stderr, first.clone()));
+        if let Some(stdout) = child.stdout.take() {
+            timing::forward(stdout, std::io::stdout(), first.clone(), |_| ());
+        }
+
+        let (status, limited): (ExitStatus, bool) = self.wait_child(&mut child)?;
+
+        // The program leads its own process group, which what it starts stays in
+        if self.wait == Wait::Children {
+            wait::group_idle(child.id());
+            self.kill_if_interrupted()?;
+        }
+        drop(forwarder);
+        let mut timing: Timing = Timing::new(start, spawned, &first, Instant::now());
+        if limited {
+            timing = timing.limited();
+        }
+
+        if self.retries == 0 || status.success() || spawned.elapsed() > retry::STARTUP_WINDOW {
+            return pass!((status, None, timing));
+        }
+
+        let failure: Option<String> = match (&watcher, &self.output.stderr) {
+            (Some(watcher), _) => retry::received(watcher),
+            (None, Some(path)) => retry::written(path, offset),
+            (None, None) => None,
+        };
+
+        pass!((status, failure, timing))
+    }
+
+    /// Waits for the program to exit, shutting down the prefix if proton-call is interrupted
+    /// meanwhile and `Interrupt::KillPrefix` is set, or once the time limit passes
+    ///
+    /// Returns whether the time limit was reached too.
+    fn wait_child(&self, child: &mut Child) -> Result<(ExitStatus, bool), Error> {
+        let started: Instant = Instant::now();
+        let mut limited: bool = false;
+
+        if self.interrupt == Interrupt::KillPrefix || self.time_limit.is_some() {
+            if self.interrupt == Interrupt::KillPrefix {
+                interrupt::listen()?;
+            }
+
+            loop {
+                match child.try_wait() {
+                    Ok(Some(status)) => return pass!((status, false)),
+                    Ok(None) if interrupt::received() => {
+                        self.kill_if_interrupted()?;
+                        break;
+                    }
+                    Ok(None) if self.time_limit.is_some_and(|l| started.elapsed() >= l) => {
+                        ProtonEvent::TimeUp.emit();
+                        interrupt::kill_prefix(&self.wineserver()?, &self.compat.join("pfx"))?;
+                        limited = true;
+                        break;
+                    }
+                    Ok(None) => std::thread::sleep(wait::POLL),
+                    Err(e) => throw!(Kind::ProtonWait, "'{}': {}", child.id(), e),
+                }
+            }
+        }
+
+        match child.wait() {
+            Ok(status) => pass!((status, limited)),
+            Err(e) => throw!(Kind::ProtonWait, "'{}': {}", child.id(), e),
+        }
+    }
+
+    /// Shuts down every Wine process in the prefix if proton-call was interrupted and
+    /// `Interrupt::KillPrefix` is set
+    fn kill_if_interrupted(&self) -> Result<(), Error> {
+        if self.interrupt != Interrupt::KillPrefix || !interrupt::received() {
+            return pass!();
+        }
+
+        ProtonEvent::Interrupted.emit();
+        interrupt::kill_prefix(&self.wineserver()?, &self.compat.join("pfx"))
+    }
+
+    /// Points the command's output streams at the files set by `Proton::stdout` and `Proton::stderr`
+    fn redirect(&self, command: &mut Command) -> Result<(), Error> {
+        use std::fs::{File, OpenOptions};
+        use std::process::Stdio;
+
+        let open = |path: &Path| -> Result<File, Error> {
+            match OpenOptions::new()
+                .create(true)
+                .write(true)
+                .append(self.output.append)
+                .truncate(!self.output.append)
+                .open(path)
+            {
+                Ok(f) => pass!(f),
+                Err(e) => throw!(Kind::Log, "{}: {}", path.to_string_lossy(), e),
+            }
+        };
+
+        if let Some(path) = &self.output.stdout {
+            command.stdout(Stdio::from(open(path)?));
+        }
+
+        if let Some(path) = &self.output.stderr {
+            command.stderr(Stdio::from(open(path)?));
+        }
+
+        pass!()
+    }
+
+    /// Returns the command line running the program: through a Wine runner, Proton's 32-bit
+    /// loader or Proton's script, in the Steam Linux Runtime when it needs one
+    fn invocation(&self) -> Result<Vec<OsString>, Error> {
+        let mut invocation: Vec<OsString> = Vec::new();
+
+        if self.wine.is_some() {
+            invocation.push(self.path.clone().into_os_string());
+        } else if self.arch == Some(Arch::Win32) {
+            let Some(dist) = self.dist() else {
+                throw!(
+                    Kind::ProtonMissing,
+                    "Proton {} has no 32-bit Wine loader",
+                    self.version
+                );
+            };
+
+            invocation.push(dist.join("bin/wine").into_os_string());
+        } else {
+            if let Some(entry) = self.container()? {
+                invocation.push(entry.into_os_string());
+                invocation.push(OsString::from(format!("--verb={}", self.verb)));
+                invocation.push(OsString::from("--"));
+            }
+
+            invocation.push(self.path.clone().into_os_string());
+            invocation.push(OsString::from(&self.verb));
+        }
+
+        invocation.extend(self.command_line());
+        pass!(invocation)
+    }
+
+    /// Returns the program and its arguments as Wine runs them, inside `explorer` opening a
+    /// virtual desktop if asked for
+    fn command_line(&self) -> Vec<OsString> {
+        let mut line: Vec<OsString> = Vec::new();
+
+        match &self.desktop {
+            Some(desktop) => {
+                line.push(OsString::from("explorer"));
+                line.push(OsString::from(desktop.argument()));
+
+                // explorer starts programs by their Windows path, builtins by their name
+                if self.builtin {
+                    line.push(self.program.clone().into_os_string());
+                } else {
+                    let program: PathBuf = self
+                        .program
+                        .canonicalize()
+                        .unwrap_or_else(|_| self.program.clone());
+                    line.push(OsString::from(windows_path(&program)));
+                }
+            }
+            None => line.push(self.program.clone().into_os_string()),
+        }
+
+        line.extend(self.args.iter().map(OsString::from));
+        line
+    }
+
+    /// Builds the command for a plain Wine runner with `WINEPREFIX` in the compat data directory
+    fn wine_command(&self) -> Result<Command, Error> {
+        ProtonEvent::Launching {
+            build: format!("Wine {}", self.wine.as_deref().unwrap_or_default()),
+            program: self.program.clone(),
+        }
+        .emit();
+
+        let pfx: PathBuf = self.compat.join("pfx");
+        if let Err(e) = std::fs::create_dir_all(&pfx) {
+            throw!(Kind::ProtonDir, "failed to create Wine prefix: {}", e);
+        }
+
+        let invocation: Vec<OsString> = self.invocation()?;
+
+        let mut command: Command = self.wrap(&invocation);
+        self.environment_in(&self.compat, None).apply(&mut command);
+        self.redirect(&mut command)?;
+
+        pass!(command)
+    }
+
+    /// Builds the command running the program through the 32-bit Wine loader of Proton's dist
+    fn win32_command(&self) -> Result<Command, Error> {
+        if !self.compat.join("pfx").is_dir() {
+            throw!(
+                Kind::PrefixMissing,
+                "'{}' has no Wine prefix yet, launch once without --win32 to set it up",
+                self.compat.to_string_lossy()
+            );
+        }
+
+        ProtonEvent::Launching {
+            build: format!("Proton {} (32-bit loader)", self.version),
+            program: self.program.clone(),
+        }
+        .emit();
+
+        let invocation: Vec<OsString> = self.invocation()?;
+
+        let mut command: Command = self.wrap(&invocation);
+        self.environment_in(&self.compat, None).apply(&mut command);
+        self.redirect(&mut command)?;
+
+        pass!(command)
+    }
+
+    /// Waits for every process left running in the prefix, such as a game its launcher started
+    fn wait_prefix(&self) -> Result<(), Error> {
+        let waited: Result<(), Error> =
+            wait::prefix_idle(&self.wineserver()?, &self.compat.join("pfx"));
+
+        // An interrupt stops the wait too, which is not a failure when the prefix is shut down
+        if self.interrupt == Interrupt::KillPrefix && interrupt::received() {
+            return self.kill_if_interrupted();
+        }
+
+        waited
+    }
+
+    /// Starts the Discord bridge, if asked for, with the program's name as the presence
+    fn presence(&self) -> Option<Presence> {
+        let name: String = self
+            .program
+            .file_stem()
+            .unwrap_or(self.program.as_os_str())
+            .to_string_lossy()
+            .to_string();
+
+        self.discord.as_ref()?.start(&name)
+    }
+
+    /// Finds the `wineserver` of the Wine in use, which manages the processes of the prefix
+    fn wineserver(&self) -> Result<PathBuf, Error> {
+        match (&self.wine, self.dist()) {
+            (Some(_), _) => pass!(self.path.with_file_name("wineserver")),
+            (None, Some(dist)) => pass!(dist.join("bin/wineserver")),
+            (None, None) => throw!(
+                Kind::ProtonMissing,
+                "Proton {} has no wineserver",
+                self.version
+            ),
+        }
+    }
+
+    /// Finds the Wine build inside the Proton directory, `files` in newer versions, `dist` before
+    fn dist(&self) -> Option<PathBuf> {
+        let proton_dir: &Path = self.path.parent()?;
+
+        ["files", "dist"]
+            .iter()
+            .map(|name| proton_dir.join(name))
+            .find(|dist| dist.join("bin/wine").is_file())
+    }
+
+    /// Sets the variables Wine needs to run from Proton's dist without the Proton script
+    fn loader_env(&self, env: &mut Environment, compat: &Path) {
+        let Some(dist) = self.dist() else {
+            return;
+        };
+
+        let library_path: String = [dist.join("lib"), dist.join("lib64")]
+            .iter()
+            .map(|p| p.to_string_lossy().to_string())
+            .chain(
+                env.get("LD_LIBRARY_PATH")
+                    .map(|p| p.to_string_lossy().to_string()),
+            )
+            .filter(|p| !p.is_empty())
+            .collect::<Vec<String>>()
+            .join(":");
+
+        let dll_path: String = format!(
+            "{}:{}",
+            dist.join("lib/wine").to_string_lossy(),
+            dist.join("lib64/wine").to_string_lossy()
+        );
+
+        env.set("WINEPREFIX", compat.join("pfx"), EnvSource::Flag);
+        env.set("WINEARCH", "win32", EnvSource::Flag);
+        env.set("WINELOADER", dist.join("bin/wine"), EnvSource::Flag);
+        env.set("WINESERVER", dist.join("bin/wineserver"), EnvSource::Flag);
+        env.set("WINEDLLPATH", dll_path, EnvSource::Flag);
+        env.set("LD_LIBRARY_PATH", library_path, EnvSource::Flag);
+    }
+}
+
+/// Adds `overrides` to the `WINEDLLOVERRIDES` of `env`, after those it holds already
+fn add_overrides(env: &mut Environment, overrides: &str, source: EnvSource) {
+    let overrides: String = match env.get("WINEDLLOVERRIDES") {
+        Some(current) if !current.is_empty() => {
+            format!("{};{}", current.to_string_lossy(), overrides)
+        }
+        _ => overrides.to_string(),
+    };
+    env.set("WINEDLLOVERRIDES", overrides, source);
+}
+
+/// Returns the directory a program is installed in, for `STEAM_COMPAT_INSTALL_PATH`
+fn install_dir(program: &Path) -> Option<PathBuf> {
+    let program: PathBuf = program.canonicalize().ok()?;
+    Some(program.parent()?.to_path_buf())
+}
+
+/// Returns the program and arguments `command` runs
+fn argv(command: &Command) -> Vec<String> {
+    std::iter::once(command.get_program())
+        .chain(command.get_args())
+        .map(|a| a.to_string_lossy().to_string())
+        .collect()
+}
+
+/// Moves the newest Proton log in `dir` to `file`
+pub(crate) fn collect_log(dir: &Path, file: &Path) -> Result<(), Error> {
+    let newest: Option<PathBuf> = dir
+        .read_dir()
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.to_string_lossy().ends_with(".log"))
+        .max_by_key(|p| p.metadata().and_then(|m| m.modified()).ok());
+
+    let log: PathBuf = match newest {
+        Some(l) => l,
+        None => throw!(Kind::Log, "Proton wrote no log"),
+    };
+
+    // Copying works across filesystems, unlike renaming
+    if let Err(e) = std::fs::copy(&log, file) {
+        throw!(Kind::Log, "{}: {}", file.to_string_lossy(), e);
+    }
+    let _ = std::fs::remove_file(&log);
+
+    pass!()
 }
@@ -0,0 +1,83 @@
+use std::fmt::{Display, Formatter};
+use std::sync::OnceLock;
+
+mod en;
+mod es;
+
+/// Languages proton-call's messages are available in
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Locale {
+    /// English, the language messages are written in and fall back to
+    English,
+    /// Spanish
+    Spanish,
+}
+
+impl Locale {
+    #[must_use]
+    /// Picks the language from `LC_ALL`, `LC_MESSAGES` or `LANG`, like gettext, English if
+    /// none of them name an available language
+    pub fn from_env() -> Locale {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|tag| !tag.is_empty())
+            .and_then(|tag| Locale::from_tag(&tag))
+            .unwrap_or(Locale::English)
+    }
+
+    #[must_use]
+    /// Reads a POSIX locale tag such as `es_MX.UTF-8`, `None` if its language is not available
+    pub fn from_tag(tag: &str) -> Option<Locale> {
+        let language: &str = tag.split(['_', '.', '@', '-']).next()?;
+
+        match language.to_ascii_lowercase().as_str() {
+            "en" | "c" | "posix" => Some(Locale::English),
+            "es" => Some(Locale::Spanish),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    /// Returns the language of this process, read from the environment once
+    pub fn current() -> Locale {
+        static CURRENT: OnceLock<Locale> = OnceLock::new();
+        *CURRENT.get_or_init(Locale::from_env)
+    }
+
+    /// Returns the messages of the language, by id
+    fn catalog(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Locale::English => en::MESSAGES,
+            Locale::Spanish => es::MESSAGES,
+        }
+    }
+
+    #[must_use]
+    /// Looks up the message `id` in this language, falling back to English and then the id
+    pub fn message(self, id: &'static str) -> &'static str {
+        lookup(self.catalog(), id)
+            .or_else(|| lookup(en::MESSAGES, id))
+            .unwrap_or(id)
+    }
+}
+
+impl Display for Locale {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Locale::English => write!(f, "en"),
+            Locale::Spanish => write!(f, "es"),
+        }
+    }
+}
+
+/// Finds the message `id` in `catalog`
+fn lookup(catalog: &'static [(&'static str, &'static str)], id: &str) -> Option<&'static str> {
+    catalog.iter().find(|(i, _)| *i == id).map(|(_, m)| *m)
+}
+
+#[must_use]
+/// Translates the message `id` into the language of this process
+pub fn tr(id: &'static str) -> &'static str {
+    Locale::current().message(id)
+}
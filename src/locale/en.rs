@@ -0,0 +1,97 @@
+/// English messages by id, which every other language falls back to
+pub(super) static MESSAGES: &[(&str, &str)] = &[
+    ("warning", "warning"),
+    ("nothing-launched", "Nothing has been launched yet"),
+    ("no-prefixes-to-delete", "No prefixes to delete"),
+    ("launch-which", "Launch which?"),
+    ("kind-internal", "internal error"),
+    ("kind-environment", "failed to read environment"),
+    ("kind-config-open", "failed to open config"),
+    ("kind-config-read", "failed to read config"),
+    ("kind-config-parse", "failed to parse config"),
+    ("kind-proton-dir", "failed to create Proton directory"),
+    ("kind-proton-spawn", "failed to spawn Proton"),
+    ("kind-proton-wait", "failed to wait for Proton child"),
+    ("kind-index-read-dir", "failed to Index"),
+    ("kind-version-parse", "failed to parse version"),
+    ("kind-proton-missing", "cannot find Proton"),
+    ("kind-program-missing", "cannot find program"),
+    ("kind-proton-exit", "proton exited with"),
+    ("kind-argument-missing", "missing command line argument"),
+    ("kind-jargon-internal", "jargon args internal error"),
+    ("kind-unknown-command", "unknown command"),
+    ("kind-prefix-missing", "cannot find prefix"),
+    ("kind-prefix-read", "failed to read prefix"),
+    ("kind-prefix-write", "failed to modify prefix"),
+    ("kind-prefix-exists", "prefix already exists"),
+    ("kind-download", "failed to download"),
+    ("kind-extract", "failed to extract"),
+    ("kind-font-pack", "unknown font pack"),
+    ("kind-backup", "save backup error"),
+    ("kind-hook", "hook failed"),
+    ("kind-game-missing", "no settings in config for"),
+    ("kind-config-write", "failed to write config"),
+    ("kind-bundle", "bundle error"),
+    ("kind-argument-parse", "failed to parse argument"),
+    ("kind-tricks", "tricks failed"),
+    ("kind-arch-mismatch", "prefix architecture mismatch"),
+    ("kind-log", "failed to write log"),
+    ("kind-version-pinned", "version is pinned"),
+    ("kind-prefix-mismatch", "prefix version mismatch"),
+    ("kind-tool-install", "failed to install the compatibility tool"),
+    ("kind-fixes", "failed to read the fixes database"),
+    ("kind-unsupported", "not supported"),
+    ("help-more", "Run `proton-call help COMMAND` for the options, examples and config keys of a command."),
+    (
+        "help-config",
+        "\
+Config:
+    The config file should be located at '$XDG_CONFIG_HOME/proton.conf' or '$HOME/.config/proton.conf'
+    The config requires two values.
+    Data: a location to any directory to contain Proton's runtime files.
+    Steam: the directory to where steam is installed (the one which contains the steamapps directory).
+    Common: the directory to where your proton versions are stored, usually Steam's steamapps/common directory.
+    Schema: optional version of the config format, 1 for now, older files are migrated when read.
+        Unknown keys are warned about, naming the closest known key.
+    Backups: optional directory to keep save backups in.
+    Runners: optional directory of Wine builds, defaults to Lutris' runners/wine directory.
+    Retries: optional number of times to launch again when a program fails while starting up
+        because the display or the Steam runtime was not ready, defaults to 0.
+    Downgrade, major_jump: optional policies, allow, warn or block, for launching an older Proton,
+        or a newer major version, into a prefix another version last used, default warn and allow.
+    Env_allow, env_deny: optional lists of inherited variables which may or may not reach programs,
+        a trailing * matches any suffix, such as [\"LD_PRELOAD\", \"WINE*\"].
+    Dxvk_async, gpl, radv_perftest: optional shader compilation options, compiling in the background
+        with DXVK builds which support it, forcing DXVK's graphics pipeline library on or off, and
+        options for Mesa's RADV_PERFTEST, such as \"gpl\".
+    Nvapi, dlss_indicator: optional NVIDIA options, exposing NVAPI and the GPU so games offer DLSS,
+        or hiding both, and showing DLSS' on-screen indicator. Both can be set per program too.
+    Allow_menus: optionally let Wine add menu entries and file associations to the host, which
+        launches disable by default.
+    Dedup: optionally make `prefix create` share the identical files of new prefixes with the
+        others through reflinks, on btrfs and XFS.
+    Discord, discord_client_id: optional bridge of the host's Discord client into launches, and the
+        id of a Discord application to show the program as played under, without which only the
+        socket is exposed.
+    Roots: optional list of directories games are installed in, searched by `find`, defaults to Common.
+    Aliases: an optional [aliases] table of names standing for executables, such as
+        factorio = \"/games/Factorio/bin/x64/factorio.exe\", for use with `-r factorio`.
+    Programs can have their own settings in a [games.NAME] table, where NAME is used in place of a prefix:
+        path: the program's executable, used to apply these settings to `-r` launches, which also
+            launch it by NAME, such as `proton-call run skyrim`.
+        prefix: the prefix in `data` the program uses.
+        proton: the version launches use, `-p` with another version needs `--force`.
+        retention: how many save backups to keep.
+        sync_before, sync_after: commands syncing saves around a launch, `%saves%` is replaced
+            with the save directory and `%prefix%` with the prefix, both quoted.
+        command_template: a shell command to launch through, such as \"gamemoderun %command% -novid\",
+            where `%command%` is the Proton invocation.
+        nvapi, dlss_indicator: the program's own NVIDIA options, over the config's.
+        env: a table of variables for the program, such as { DXVK_HUD = \"fps\" }.
+        args: the program's arguments when the launch passes none.
+    Example:
+        data = \"/home/avery/Documents/Proton/env/\"
+        steam = \"/home/avery/.steam/steam/\"
+        common = \"/home/avery/.steam/steam/steamapps/common/\"",
+    ),
+];
@@ -0,0 +1,260 @@
+/// Spanish messages by id
+pub(super) static MESSAGES: &[(&str, &str)] = &[
+    ("warning", "aviso"),
+    ("nothing-launched", "Todavía no se ha ejecutado nada"),
+    ("no-prefixes-to-delete", "No hay prefijos que borrar"),
+    ("launch-which", "¿Cuál ejecutar?"),
+    ("kind-internal", "error interno"),
+    ("kind-environment", "no se pudo leer el entorno"),
+    ("kind-config-open", "no se pudo abrir la configuración"),
+    ("kind-config-read", "no se pudo leer la configuración"),
+    ("kind-config-parse", "no se pudo analizar la configuración"),
+    ("kind-proton-dir", "no se pudo crear el directorio de Proton"),
+    ("kind-proton-spawn", "no se pudo iniciar Proton"),
+    ("kind-proton-wait", "no se pudo esperar al proceso de Proton"),
+    ("kind-index-read-dir", "no se pudo indexar"),
+    ("kind-version-parse", "no se pudo analizar la versión"),
+    ("kind-proton-missing", "no se encuentra Proton"),
+    ("kind-program-missing", "no se encuentra el programa"),
+    ("kind-proton-exit", "proton terminó con"),
+    ("kind-argument-missing", "falta un argumento"),
+    ("kind-jargon-internal", "error interno de jargon args"),
+    ("kind-unknown-command", "orden desconocida"),
+    ("kind-prefix-missing", "no se encuentra el prefijo"),
+    ("kind-prefix-read", "no se pudo leer el prefijo"),
+    ("kind-prefix-write", "no se pudo modificar el prefijo"),
+    ("kind-prefix-exists", "el prefijo ya existe"),
+    ("kind-download", "no se pudo descargar"),
+    ("kind-extract", "no se pudo extraer"),
+    ("kind-font-pack", "paquete de fuentes desconocido"),
+    ("kind-backup", "error en la copia de partidas"),
+    ("kind-hook", "falló la orden auxiliar"),
+    ("kind-game-missing", "no hay ajustes en la configuración para"),
+    ("kind-config-write", "no se pudo escribir la configuración"),
+    ("kind-bundle", "error del paquete"),
+    ("kind-argument-parse", "no se pudo analizar el argumento"),
+    ("kind-tricks", "falló winetricks"),
+    ("kind-arch-mismatch", "la arquitectura del prefijo no coincide"),
+    ("kind-log", "no se pudo escribir el registro"),
+    ("kind-version-pinned", "la versión está fijada"),
+    ("kind-prefix-mismatch", "la versión del prefijo no coincide"),
+    ("kind-tool-install", "no se pudo instalar la herramienta de compatibilidad"),
+    ("kind-fixes", "no se pudo leer la base de datos de arreglos"),
+    ("kind-unsupported", "no admitido"),
+    // help texts, by their English text
+    ("Usage:", "Uso:"),
+    ("Options:", "Opciones:"),
+    ("Commands:", "Órdenes:"),
+    ("Examples:", "Ejemplos:"),
+    ("Config keys:", "Claves de configuración:"),
+    ("Bundle the settings and DLL overrides of the program NAME into FILE", "Empaquetar los ajustes y overrides de DLL del programa NAME en FILE"),
+    ("Do not pass the installed BattlEye runtime to Proton", "No pasar a Proton el runtime de BattlEye instalado"),
+    ("Do not pass the installed EasyAntiCheat runtime to Proton", "No pasar a Proton el runtime de EasyAntiCheat instalado"),
+    ("Find, archive or restore the save games in the prefix or for the program TARGET", "Buscar, archivar o restaurar las partidas guardadas en el prefijo o del programa TARGET"),
+    ("Include the program's prefix", "Incluir el prefijo del programa"),
+    ("Install a bundle made by export, or register the prefixes of Bottles, Lutris or Heroic as named prefixes", "Instalar un paquete creado con export, o registrar los prefijos de Bottles, Lutris o Heroic como prefijos con nombre"),
+    ("Install into the prefix NAME instead of the one of the Proton version", "Instalar en el prefijo NAME en lugar del de la versión de Proton"),
+    (
+        "Install proton-call into Steam's compatibilitytools.d, to pick it as a game's compatibility tool and launch Steam games through proton-call",
+        "Instalar proton-call en compatibilitytools.d de Steam, para elegirlo como herramienta de compatibilidad de un juego y ejecutar los juegos de Steam mediante proton-call",
+    ),
+    (
+        "Launch Steam games with Proton VERSION from `common`",
+        "Ejecutar los juegos de Steam con Proton VERSION de `common`",
+    ),
+    ("Install the font PACK (corefonts, cjk) into a prefix", "Instalar el paquete de fuentes PACK (corefonts, cjk) en un prefijo"),
+    (
+        "Launch even when the prefix architecture does not fit, the program is pinned to another version or the compat policy blocks it",
+        "Ejecutar aunque la arquitectura del prefijo no encaje, el programa esté fijado a otra versión o la política de compatibilidad lo bloquee",
+    ),
+    ("Launch with a minimal environment instead of inheriting this one", "Ejecutar con un entorno mínimo en lugar de heredar este"),
+    ("List recently launched programs, relaunch one with -r @N", "Listar los programas ejecutados recientemente, repetir uno con -r @N"),
+    ("List the last N launches instead of 20", "Listar las últimas N ejecuciones en lugar de 20"),
+    ("List the launches from the run log", "Listar las ejecuciones del registro"),
+    ("Load the Steam overlay from `steam`, for screenshots and the FPS counter", "Cargar el overlay de Steam desde `steam`, para capturas y el contador de FPS"),
+    ("Manage the prefixes in `data`: list them, view one, rebuild one with `wineboot -u` keeping drive_c, set one up without running a program, delete unused ones, remove the menu entries Wine created for one, or share identical files between them", "Gestionar los prefijos de `data`: listarlos, ver uno, reconstruir uno con `wineboot -u` conservando drive_c, preparar uno sin ejecutar ningún programa, borrar los que no se usan, quitar las entradas de menú que Wine creó para uno, o compartir los archivos idénticos entre ellos"),
+    ("With create, share identical files with the other prefixes through reflinks", "Con create, compartir los archivos idénticos con los demás prefijos mediante reflinks"),
+    ("Pass PROTON_LOG variable to Proton", "Pasar la variable PROTON_LOG a Proton"),
+    ("Path to the proton script to use, a directory containing it, or a Proton tarball", "Ruta al script proton a usar, a un directorio que lo contenga, o a un tarball de Proton"),
+    ("Print every variable the launch runs with, and where it comes from", "Mostrar cada variable con la que se ejecuta, y de dónde viene"),
+    ("Print the launch of PROGRAM, a name or EXE, as a Lutris game config, taking the launch options", "Mostrar la ejecución de PROGRAM, un nombre o EXE, como configuración de juego de Lutris, con las opciones de ejecución"),
+    ("Report the time to spawn, time to first output and total runtime", "Informar del tiempo hasta el inicio, hasta la primera salida y total"),
+    (
+        "Launch through the Steam Linux Runtime sniper, soldier or none, instead of the one Proton requires",
+        "Ejecutar mediante el Steam Linux Runtime sniper, soldier o ninguno, en lugar del que requiere Proton",
+    ),
+    (
+        "Run through the 32-bit Wine loader of Proton, for 16 and 32-bit installers which fail otherwise",
+        "Ejecutar mediante el cargador de Wine de 32 bits de Proton, para instaladores de 16 y 32 bits que fallan de otro modo",
+    ),
+    (
+        "Run EXE in proton, an alias, @N for the Nth recent program, or - to read EXE and its arguments from stdin, one per line",
+        "Ejecutar EXE en proton, un alias, @N para el N-ésimo programa reciente, o - para leer EXE y sus argumentos de la entrada estándar, uno por línea",
+    ),
+    ("Read commands and run each inside a prefix with `runinprefix`, Tab completes drive_c paths", "Leer órdenes y ejecutar cada una dentro de un prefijo con `runinprefix`, Tab completa rutas de drive_c"),
+    ("Run EXE through Proton, passing EXTRA to it, the default when no command is given", "Ejecutar EXE mediante Proton pasándole EXTRA, lo que se hace cuando no se da ninguna orden"),
+    ("Run in the prefix NAME in `data`", "Ejecutar en el prefijo NAME de `data`"),
+    ("Run in verbose mode", "Modo detallado"),
+    ("Run through protontricks instead of winetricks", "Ejecutar mediante protontricks en lugar de winetricks"),
+    ("Run winetricks VERBS in a prefix, or through protontricks for a Steam app", "Ejecutar winetricks VERBS en un prefijo, o mediante protontricks para una aplicación de Steam"),
+    ("Search game directories for executables matching QUERY and pick one to launch, taking the launch options", "Buscar en los directorios de juegos ejecutables que coincidan con QUERY y elegir uno para ejecutar, con las opciones de ejecución"),
+    ("Steam app id given to the program, otherwise found or generated", "Id de Steam que recibe el programa, si no se busca o se genera"),
+    ("Run on a pseudo-terminal, for interactive console programs such as server consoles", "Ejecutar en un pseudoterminal, para programas de consola interactivos como consolas de servidor"),
+    ("Never run on a pseudo-terminal, which console programs get when launched from one", "No ejecutar nunca en un pseudoterminal, que reciben los programas de consola ejecutados desde uno"),
+    ("Wait for every program in the prefix to exit, for launchers which start the game and exit", "Esperar a que terminen todos los programas del prefijo, para lanzadores que inician el juego y terminan"),
+    ("Wait for every process the program started to exit, not only the program", "Esperar a que terminen todos los procesos que inició el programa, no solo el programa"),
+    ("Shut down every Wine process in the prefix when interrupted, not only the program", "Cerrar todos los procesos de Wine del prefijo al interrumpirse, no solo el programa"),
+    ("Sample the CPU, memory and GPU memory use of the launch and report it once done", "Medir el uso de CPU, memoria y memoria de GPU de la ejecución e informarlo al terminar"),
+    ("Launch without the program's fix from the fixes database", "Ejecutar sin el arreglo del programa de la base de datos de arreglos"),
+    ("Show the fix the fixes database holds for EXE, and the key to add one under", "Mostrar el arreglo que la base de datos de arreglos tiene para EXE, y la clave bajo la que añadir uno"),
+    ("Also find the fix for the Steam app ID", "Buscar también el arreglo de la aplicación de Steam ID"),
+    ("Import a shared fix file into the fixes database, after showing what it changes", "Importar un archivo de arreglos compartido a la base de datos de arreglos, tras mostrar lo que cambia"),
+    ("Import as NAME.toml instead of the file name of the source", "Importar como NAME.toml en lugar del nombre de archivo del origen"),
+    ("Skip confirmation", "Omitir la confirmación"),
+    ("Compile shaders in the background, with DXVK builds which support it", "Compilar shaders en segundo plano, con builds de DXVK que lo permiten"),
+    ("Force DXVK's graphics pipeline library on, --no-gpl forces it off", "Forzar la graphics pipeline library de DXVK, --no-gpl la desactiva"),
+    ("Add comma separated options to Mesa's RADV_PERFTEST", "Añadir opciones separadas por comas a RADV_PERFTEST de Mesa"),
+    ("Run the program for 90 seconds, headless through gamescope if installed, to warm its shader caches", "Ejecutar el programa durante 90 segundos, sin pantalla mediante gamescope si está instalado, para llenar sus cachés de shaders"),
+    ("Like --precache, for SECS seconds", "Como --precache, durante SECS segundos"),
+    ("Expose NVAPI and the NVIDIA GPU so games offer DLSS, --no-nvapi hides both", "Exponer NVAPI y la GPU de NVIDIA para que los juegos ofrezcan DLSS, --no-nvapi oculta ambas"),
+    ("Show DLSS' on-screen indicator, to check DLSS and frame generation are running", "Mostrar el indicador de DLSS en pantalla, para comprobar que DLSS y la generación de fotogramas funcionan"),
+    ("Run on Wine's native Wayland driver instead of XWayland, with Proton builds which ship it", "Ejecutar con el driver nativo de Wayland de Wine en lugar de XWayland, con builds de Proton que lo incluyen"),
+    ("Disable Wine's crash dialog, so a crashing program exits instead of waiting on a click", "Desactivar el diálogo de fallos de Wine, para que un programa que falla salga en lugar de esperar un clic"),
+    ("Bridge the host's Discord client into the launch, showing the program as played", "Conectar el cliente de Discord del sistema con la ejecución, mostrando el programa como jugado"),
+    ("Let installers add menu entries and file associations to the host, which is off by default", "Permitir que los instaladores añadan entradas de menú y asociaciones de archivos al sistema, desactivado por defecto"),
+    ("Run inside a Wine virtual desktop of WxH pixels, for older programs which break on alt-tab or resolution changes", "Ejecutar dentro de un escritorio virtual de Wine de WxH píxeles, para programas antiguos que fallan con alt-tab o cambios de resolución"),
+    ("Print the resolved launch as a JSON plan, with its command line, environment and directories, instead of running it", "Mostrar la ejecución resuelta como un plan JSON, con su línea de órdenes, entorno y directorios, en lugar de ejecutarla"),
+    (
+        "Download GE-Proton RELEASE from GitHub, verify its checksum and extract it into Steam's compatibilitytools.d, ready for -p",
+        "Descargar GE-Proton RELEASE de GitHub, verificar su suma de comprobación y extraerlo en compatibilitytools.d de Steam, listo para -p",
+    ),
+    (
+        "Set the variable KEY to VALUE for the program, over the config and fixes, repeatable",
+        "Dar el valor VALUE a la variable KEY para el programa, sobre la configuración y los arreglos, repetible",
+    ),
+    (
+        "Show the Proton build, verb, compat data directory, command line and environment of the launch instead of running it",
+        "Mostrar la versión de Proton, el verbo, el directorio de datos de compatibilidad, la línea de órdenes y el entorno de la ejecución en lugar de ejecutarla",
+    ),
+    (
+        "Run the Wine built-in TOOL, winecfg, regedit, control or taskmgr, through Proton in the compat data directory a launch with the same options uses",
+        "Ejecutar la herramienta integrada de Wine TOOL, winecfg, regedit, control o taskmgr, con Proton en el directorio de datos de compatibilidad que usa una ejecución con las mismas opciones",
+    ),
+    (
+        "Shut down every Wine process in the compat data directory a launch with the same options uses, through its wineserver",
+        "Cerrar todos los procesos de Wine del directorio de datos de compatibilidad que usa una ejecución con las mismas opciones, mediante su wineserver",
+    ),
+    ("Shut down the prefix NAME in `data`", "Cerrar el prefijo NAME de `data`"),
+    (
+        "Also kill the processes still running in the prefix with SIGKILL, for a hung wineserver",
+        "Matar también con SIGKILL los procesos que siguen en el prefijo, para un wineserver colgado",
+    ),
+    ("Start the program and exit without waiting for it", "Iniciar el programa y salir sin esperarlo"),
+    (
+        "With --detach, print the process ID of the program started",
+        "Con --detach, mostrar el ID de proceso del programa iniciado",
+    ),
+    (
+        "With --index, print each version's name, version, path and source as JSON",
+        "Con --index, mostrar el nombre, la versión, la ruta y el origen de cada versión como JSON",
+    ),
+    (
+        "Read the config from PATH instead of proton.conf, also set by PROTON_CALL_CONFIG",
+        "Leer la configuración de PATH en lugar de proton.conf, también fijado por PROTON_CALL_CONFIG",
+    ),
+    ("Use DIR as `data` for this run, over the config", "Usar DIR como `data` en esta ejecución, por encima de la configuración"),
+    ("Use DIR as `steam` for this run, over the config", "Usar DIR como `steam` en esta ejecución, por encima de la configuración"),
+    ("Use DIR as `common` for this run, over the config", "Usar DIR como `common` en esta ejecución, por encima de la configuración"),
+    ("With init, take the proposed directories without asking", "Con init, aceptar los directorios propuestos sin preguntar"),
+    ("With init, replace an existing config", "Con init, reemplazar una configuración existente"),
+    ("Strip the Steam overlay from an inherited LD_PRELOAD", "Quitar el overlay de Steam de un LD_PRELOAD heredado"),
+    ("Total the launches, failures and play time of each program", "Sumar ejecuciones, fallos y tiempo de juego de cada programa"),
+    ("Use Proton VERSION from `common`, otherwise the one which last used the prefix", "Usar Proton VERSION de `common`, si no el que usó el prefijo por última vez"),
+    ("Use Proton VERSION from `common`", "Usar Proton VERSION de `common`"),
+    (
+        "Use Proton VERSION from `common`, a GE-Proton build, latest, experimental, hotfix, ge-latest, or a Wine runner such as wine-ge-8-26",
+        "Usar Proton VERSION de `common`, una versión de GE-Proton, latest, experimental, hotfix, ge-latest, o un runner de Wine como wine-ge-8-26",
+    ),
+    ("Use the Wine of Proton VERSION from `common`", "Usar el Wine de Proton VERSION de `common`"),
+    ("Use the prefix NAME in `data` instead of one per version", "Usar el prefijo NAME de `data` en lugar de uno por versión"),
+    ("View an index of installed Proton versions and Wine runners", "Ver un índice de las versiones de Proton y runners de Wine instalados"),
+    (
+        "View every config value in use and the file it was read from, or whether it is a default, or write a new config from the Steam install found",
+        "Ver cada valor de configuración en uso y el archivo del que se leyó, o si es un valor por defecto, o escribir una configuración nueva a partir de la instalación de Steam encontrada",
+    ),
+    ("View this help message", "Ver esta ayuda"),
+    ("View version information", "Ver la información de versión"),
+    (
+        "With --index, print the plain listing even on a terminal",
+        "Con --index, mostrar el listado simple también en una terminal",
+    ),
+    ("With --index, reverse the order", "Con --index, invertir el orden"),
+    ("With --index, sort by version, date or size", "Con --index, ordenar por version, date o size"),
+    ("With --index, view size, install date, source and prefix use", "Con --index, ver tamaño, fecha de instalación, origen y uso en prefijos"),
+    ("With --stdout or --stderr, append to the files instead of truncating", "Con --stdout o --stderr, añadir a los archivos en lugar de vaciarlos"),
+    ("With --via-protontricks, the Steam app id whose prefix to use", "Con --via-protontricks, el id de Steam cuyo prefijo usar"),
+    ("With gc, also delete prefixes whose Proton or program is gone", "Con gc, borrar también prefijos cuyo Proton o programa ya no está"),
+    ("With gc, delete prefixes unused for AGE, such as 30d or 12w, instead of 90 days", "Con gc, borrar los prefijos sin usar durante AGE, como 30d o 12w, en lugar de 90 días"),
+    ("With gc and clean-menus, skip confirmation", "Con gc y clean-menus, omitir la confirmación"),
+    ("Write the Proton log to PATH instead of $HOME/steam-<appid>.log", "Escribir el registro de Proton en PATH en lugar de $HOME/steam-<appid>.log"),
+    ("Write the bundle to FILE", "Escribir el paquete en FILE"),
+    ("Write the config to FILE", "Escribir la configuración en FILE"),
+    ("Write the program's standard error to PATH", "Escribir la salida de errores del programa en PATH"),
+    ("Write the program's standard output to PATH", "Escribir la salida estándar del programa en PATH"),
+    ("help-more", "Ejecuta `proton-call help ORDEN` para ver las opciones, ejemplos y claves de configuración de una orden."),
+    (
+        "help-config",
+        "\
+Configuración:
+    El archivo de configuración debe estar en '$XDG_CONFIG_HOME/proton.conf' o '$HOME/.config/proton.conf'
+    La configuración necesita dos valores.
+    Data: cualquier directorio donde guardar los archivos de ejecución de Proton.
+    Steam: el directorio donde está instalado steam (el que contiene el directorio steamapps).
+    Common: el directorio donde están tus versiones de proton, normalmente steamapps/common de Steam.
+    Schema: versión opcional del formato de la configuración, 1 por ahora, los archivos antiguos se
+        migran al leerlos. Se avisa de las claves desconocidas, nombrando la conocida más parecida.
+    Backups: directorio opcional donde guardar las copias de partidas.
+    Runners: directorio opcional de builds de Wine, por defecto runners/wine de Lutris.
+    Retries: número opcional de veces que volver a ejecutar un programa que falla al arrancar
+        porque la pantalla o el runtime de Steam no estaban listos, por defecto 0.
+    Downgrade, major_jump: políticas opcionales, allow, warn o block, para ejecutar un Proton más antiguo,
+        o una versión mayor más nueva, en un prefijo que usó otra versión, por defecto warn y allow.
+    Env_allow, env_deny: listas opcionales de variables heredadas que pueden o no llegar a los
+        programas, un * final coincide con cualquier sufijo, como [\"LD_PRELOAD\", \"WINE*\"].
+    Dxvk_async, gpl, radv_perftest: opciones opcionales de compilación de shaders, para compilar en
+        segundo plano con builds de DXVK que lo permiten, forzar la graphics pipeline library de DXVK
+        y dar opciones a RADV_PERFTEST de Mesa, como \"gpl\".
+    Nvapi, dlss_indicator: opciones opcionales de NVIDIA, para exponer NVAPI y la GPU de modo que
+        los juegos ofrezcan DLSS, u ocultar ambas, y mostrar el indicador de DLSS en pantalla.
+        Ambas pueden ajustarse también por programa.
+    Allow_menus: permite opcionalmente que Wine añada entradas de menú y asociaciones de archivos
+        al sistema, algo que las ejecuciones desactivan por defecto.
+    Dedup: opcionalmente, hace que `prefix create` comparta los archivos idénticos de los prefijos
+        nuevos con los demás mediante reflinks, en btrfs y XFS.
+    Discord, discord_client_id: puente opcional del cliente de Discord del sistema a las ejecuciones,
+        y el id de una aplicación de Discord con la que mostrar el programa como jugado, sin el que
+        solo se expone el socket.
+    Roots: lista opcional de directorios con juegos instalados, donde busca `find`, por defecto Common.
+    Aliases: una tabla [aliases] opcional de nombres que representan ejecutables, como
+        factorio = \"/games/Factorio/bin/x64/factorio.exe\", para usar con `-r factorio`.
+    Los programas pueden tener sus propios ajustes en una tabla [games.NOMBRE], donde NOMBRE se usa
+    en lugar de un prefijo:
+        path: el ejecutable del programa, para aplicar estos ajustes a las ejecuciones con `-r`, que
+            también lo ejecutan por NOMBRE, como `proton-call run skyrim`.
+        prefix: el prefijo de `data` que usa el programa.
+        proton: la versión que usan las ejecuciones, `-p` con otra versión necesita `--force`.
+        retention: cuántas copias de partidas conservar.
+        sync_before, sync_after: órdenes que sincronizan las partidas alrededor de una ejecución,
+            `%saves%` se sustituye por el directorio de partidas y `%prefix%` por el prefijo,
+            ambos entre comillas.
+        command_template: una orden de shell a través de la que ejecutar, como
+            \"gamemoderun %command% -novid\", donde `%command%` es la llamada a Proton.
+        nvapi, dlss_indicator: las opciones de NVIDIA propias del programa, sobre las de la configuración.
+        env: una tabla de variables para el programa, como { DXVK_HUD = \"fps\" }.
+        args: los argumentos del programa cuando la ejecución no le pasa ninguno.
+    Ejemplo:
+        data = \"/home/avery/Documents/Proton/env/\"
+        steam = \"/home/avery/.steam/steam/\"
+        common = \"/home/avery/.steam/steam/steamapps/common/\"",
+    ),
+];
@@ -33,17 +33,49 @@ proton-call -c '/path/to/Proton version' -r foo.exe
  */
 
 use proton_call::error::{Error, Kind};
-use proton_call::{pass, throw, Config, Index, Proton, Version};
-use std::path::PathBuf;
+use proton_call::help::{Command, Overview};
+use proton_call::locale::tr;
+use proton_call::{
+    parse_age, pass, proton_script, throw, windows_path, Bundle, CompatTool, Config, Dedup,
+    Discord, EnvSource, Finder, FixImport, Fixes, FontPack, ForeignPrefix, Game, GeRelease, Index,
+    IndexSort, Interrupt, Launched, MenuEntries, Metrics, Nvidia, Orphan, Prefix, PrefixListing,
+    Proton, ProtonBuilder, Recent, RunOutcome, RunRecord, Runtime, Saves, Shaders, Shell, Tool,
+    Tricks, Version, VirtualDesktop, Wait, Warnings,
+};
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
 use std::process::exit;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// Wine built-ins `util` runs
+static UTILITIES: &[&str] = &["winecfg", "regedit", "control", "taskmgr"];
+
+/// How long `--precache` runs the program for when not given
+const PRECACHE: Duration = Duration::from_secs(90);
+
+/// Directories given by `--data`, `--steam` and `--common`, over the config's for this run
+#[derive(Debug, Default)]
+struct Dirs {
+    data: Option<PathBuf>,
+    steam: Option<PathBuf>,
+    common: Option<PathBuf>,
+}
+
+/// The directories taken from the arguments by `global_flags`
+static DIRS: OnceLock<Dirs> = OnceLock::new();
+
+/// The config file given by `--config`, taken from the arguments by `global_flags`
+static CONFIG: OnceLock<PathBuf> = OnceLock::new();
 
 /// Type to handle and parse command line arguments with `Jargon`
 #[derive(Debug)]
 struct Args {
     program: PathBuf,
-    version: Version,
+    proton: Option<String>,
     log: bool,
     custom: Option<PathBuf>,
+    prefix: Option<String>,
     args: Vec<String>,
 }
 
@@ -51,7 +83,15 @@ struct Args {
 fn main() {
     let args: Vec<String> = std::env::args().collect();
     let program: String = args[0].split('/').last().unwrap_or(&args[0]).to_string();
-    if let Err(e) = proton_caller(args) {
+    let result: Result<(), Error> = proton_caller(args);
+
+    // Non-fatal problems are shown together once done, rather than between other output
+    let warnings: Warnings = Warnings::take();
+    if !warnings.is_empty() {
+        eprintln!("{}", warnings);
+    }
+
+    if let Err(e) = result {
         eprintln!("{}: {}", program, e);
         let code = e.kind() as i32;
         exit(code);
@@ -59,122 +99,1758 @@ fn main() {
 }
 
 /// Effective main function which parses arguments
-fn proton_caller(args: Vec<String>) -> Result<(), Error> {
+fn proton_caller(mut args: Vec<String>) -> Result<(), Error> {
     use jargon_args::Jargon;
 
+    global_flags(&mut args)?;
+
+    if let Some(command) = subcommand(&mut args) {
+        return match command.as_str() {
+            "run" => run_command(args),
+            "index" => list_index(Jargon::from_vec(args)),
+            "config" => config_command(args),
+            "prefix" => prefix_command(args),
+            "fonts" => fonts_command(args),
+            "saves" => saves_command(args),
+            "export" => export_command(args),
+            "export-lutris" => export_lutris_command(args),
+            "import" => import_command(args),
+            "tricks" => tricks_command(args),
+            "shell" => shell_command(args),
+            "fixes" => fixes_command(args),
+            "apply-fix" => apply_fix_command(args),
+            "recent" => recent_command(),
+            "history" => history_command(args),
+            "stats" => stats_command(),
+            "steam" => steam_command(args),
+            "install" => install_command(args),
+            "util" => util_command(args),
+            "kill" => kill_command(args),
+            "find" => find_command(args),
+            "help" => help_command(args),
+            _ => throw!(Kind::UnknownCommand, "'{}'", command),
+        };
+    }
+
+    stdin_program(&mut args)?;
     let mut parser: Jargon = Jargon::from_vec(args);
 
+    // `-i` and `-r` are the flags the `index` and `run` commands were before they existed
     if parser.contains(["-h", "--help"]) {
         help();
     } else if parser.contains(["-v", "--version"]) {
         version();
     } else if parser.contains(["-i", "--index"]) {
-        let config: Config = Config::open()?;
-        let common_index = Index::new(&config.common())?;
+        return list_index(parser);
+    } else {
+        return launch_program(parser);
+    }
+
+    Ok(())
+}
+
+/// Runs the `run` subcommand, taking the program as its first argument as `-r` would
+fn run_command(mut args: Vec<String>) -> Result<(), Error> {
+    if args.get(1).is_some_and(|arg| !arg.starts_with('-')) {
+        args.insert(1, String::from("-r"));
+    }
+
+    stdin_program(&mut args)?;
+    launch_program(jargon_args::Jargon::from_vec(args))
+}
+
+/// Launches the program given to `-r`, or prints its environment or plan when asked to
+fn launch_program(mut parser: jargon_args::Jargon) -> Result<(), Error> {
+    let config: Config = open_config()?;
+    let program: String = parser.result_arg(["-r", "--run"])?;
+    let program: PathBuf = resolve_program(&config, &program)?;
+    let print_env: bool = parser.contains("--print-env");
+    let plan: bool = parser.contains("--plan");
+    let dry_run: bool = parser.contains("--dry-run");
+    let detach: bool = parser.contains("--detach");
+    let print_pid: bool = parser.contains("--print-pid");
+    let proton: Proton = launch(&config, parser, program.clone())?;
+
+    if print_env {
+        println!("{}", proton.environment());
+    }
+
+    if plan {
+        println!("{}", proton.plan()?.to_json()?);
+        return pass!();
+    }
+
+    if dry_run {
+        println!("{}", proton.describe()?);
+        return pass!();
+    }
+
+    if detach {
+        return detach_program(proton, &program, print_pid);
+    }
+
+    run_program(proton, &program)
+}
+
+/// Runs the `index` subcommand, also `-i`, listing installed Proton versions and Wine runners
+fn list_index(mut parser: jargon_args::Jargon) -> Result<(), Error> {
+    let config: Config = open_config()?;
+    let mut common_index = Index::from_config(&config)?;
+
+    let sort: Option<String> = parser.option_arg("--sort");
+    let sort: Option<IndexSort> = match sort {
+        Some(s) => Some(s.parse()?),
+        None => None,
+    };
+    let reverse: bool = parser.contains("--reverse");
+
+    if sort.is_some() || reverse {
+        common_index.sort(sort.unwrap_or(IndexSort::Version), reverse);
+    }
+
+    if parser.contains("--json") {
+        println!("{}", common_index.to_json()?);
+    } else if parser.contains("--long") {
+        let prefixes: Vec<Prefix> = Prefix::list(&config.data()).unwrap_or_default();
+        println!("{}", common_index.long(&prefixes));
+    } else if parser.contains("--plain") || !std::io::stdout().is_terminal() {
         println!("{}", common_index);
     } else {
-        let config: Config = Config::open()?;
-        let args = Args {
-            program: parser.result_arg(["-r", "--run"])?,
-            version: parser.option_arg(["-p", "--proton"]).unwrap_or_default(),
-            log: parser.contains(["-l", "--log"]),
-            custom: parser.option_arg(["-c", "--custom"]),
-            args: parser.finish(),
+        let color: bool = std::env::var_os("NO_COLOR").is_none();
+        println!("{}", common_index.pretty(color));
+    }
+
+    pass!()
+}
+
+/// Resolves `-r` to a program, from `@N` for the Nth recent program, an alias, the name of a
+/// `[games.NAME]` table or a path
+fn resolve_program(config: &Config, program: &str) -> Result<PathBuf, Error> {
+    if let Some(n) = program.strip_prefix('@') {
+        let n: usize = match n.parse() {
+            Ok(n) => n,
+            Err(e) => throw!(Kind::ArgumentParse, "'{}': {}", program, e),
         };
 
-        let proton = if args.custom.is_some() {
-            custom_mode(&config, args)?
-        } else {
-            normal_mode(&config, args)?
+        return match Recent::open()?.get(n) {
+            Some(path) => pass!(path.to_path_buf()),
+            None => throw!(Kind::ProgramMissing, "no recent program {}", program),
         };
+    }
+
+    let path: PathBuf = PathBuf::from(program);
+
+    // Aliases and names only apply when no such file exists, so relative paths keep working
+    if path.exists() {
+        return pass!(path);
+    }
+
+    if let Some(alias) = config.alias(program) {
+        return pass!(alias.to_path_buf());
+    }
+
+    match config.game(program) {
+        Some(game) => match game.path() {
+            Some(path) => pass!(path.to_path_buf()),
+            None => throw!(
+                Kind::ProgramMissing,
+                "[games.{}] has no path to launch",
+                program
+            ),
+        },
+        None => pass!(path),
+    }
+}
+
+/// Replaces the program of `-r -` with the first line read from stdin, appending any further
+/// lines as the program's arguments, one per line
+///
+/// Only the first `-r` is looked at, what follows its program belongs to the program.
+fn stdin_program(args: &mut Vec<String>) -> Result<(), Error> {
+    use std::io::BufRead;
+
+    let Some(i) = args
+        .iter()
+        .position(|arg| matches!(arg.as_str(), "-r" | "--run"))
+    else {
+        return pass!();
+    };
+    if args.get(i + 1).map(String::as_str) != Some("-") {
+        return pass!();
+    }
+
+    let mut lines: Vec<String> = Vec::new();
+    for line in std::io::stdin().lock().lines() {
+        match line {
+            Ok(line) => lines.push(line),
+            Err(e) => throw!(Kind::ArgumentParse, "failed to read stdin: {}", e),
+        }
+    }
+
+    let mut lines = lines.into_iter().skip_while(|line| line.trim().is_empty());
+    let Some(program) = lines.next() else {
+        throw!(Kind::ArgumentMissing, "program path on stdin");
+    };
+
+    args[i + 1] = program.trim().to_string();
+    args.extend(lines.filter(|line| !line.is_empty()));
+    pass!()
+}
+
+/// Sets up Proton to run `program` from the launch options left in `parser`
+fn launch(
+    config: &Config,
+    mut parser: jargon_args::Jargon,
+    program: PathBuf,
+) -> Result<Proton, Error> {
+    let force: bool = parser.contains("--force");
+    let game: Option<(&str, &Game)> = config.game_for(&program);
+    let proton: Option<String> = pinned(game, parser.option_arg(["-p", "--proton"]), force)?;
+
+    let appid: Option<u32> = parser.option_arg("--appid");
+    let log_file: Option<PathBuf> = parser.option_arg("--log-file");
+    let stdout: Option<PathBuf> = parser.option_arg("--stdout");
+    let stderr: Option<PathBuf> = parser.option_arg("--stderr");
+    let append: bool = parser.contains("--append");
+    let clean_env: bool = parser.contains("--clean-env");
+    let time: bool = parser.contains("--time");
+    let monitor: bool = parser.contains("--monitor");
+    let no_fixes: bool = parser.contains("--no-fixes");
+    let graphics: Graphics = Graphics::read(config, game, &mut parser)?;
+    let integration: Integration = Integration::read(config, &mut parser)?;
+    let win32: bool = parser.contains("--win32");
+    let runtime: Option<String> = parser.option_arg("--runtime");
+    let runtime: Option<Runtime> = runtime.map(|r| r.parse()).transpose()?;
+    let overlay: Option<bool> = toggle(&mut parser, "--overlay", "--no-overlay");
+    let tty: Option<bool> = toggle(&mut parser, "--tty", "--no-tty");
+    let (wait, interrupt): (Wait, Interrupt) = exit_handling(&mut parser);
+    let no_eac: bool = parser.contains("--no-eac");
+    let no_battleye: bool = parser.contains("--no-battleye");
+    let env_vars: Vec<(String, String)> = env_flags(&mut parser)?;
+
+    let args = Args {
+        program,
+        proton,
+        log: parser.contains(["-l", "--log"]),
+        custom: parser.option_arg(["-c", "--custom"]),
+        prefix: parser.option_arg(["-P", "--prefix"]),
+        args: default_args(game, parser.finish()),
+    };
+
+    let game: Option<&Game> = game.map(|(_, game)| game);
+
+    let prefix: Option<PathBuf> = args
+        .prefix
+        .as_deref()
+        .or_else(|| game.and_then(Game::prefix))
+        .map(|name| config.data().join(name));
+
+    let mut proton = if args.custom.is_some() {
+        custom_mode(config, args)?
+    } else {
+        normal_mode(config, args)?
+    };
+
+    if let Some(prefix) = prefix {
+        proton = proton.prefix(prefix);
+    }
+
+    if let Some(game) = game {
+        proton = game_settings(game, proton);
+    }
+
+    if let Some(appid) = appid {
+        proton = proton.appid(appid);
+    }
+
+    if force {
+        proton = proton.force();
+    }
+
+    if let Some(file) = log_file {
+        proton = proton.log_file(file);
+    }
+
+    if let Some(file) = stdout {
+        proton = proton.stdout(file);
+    }
+
+    if let Some(file) = stderr {
+        proton = proton.stderr(file);
+    }
+
+    if append {
+        proton = proton.append_output();
+    }
+
+    if clean_env {
+        proton = proton.clean_env();
+    }
+
+    if time {
+        proton = proton.time();
+    }
+
+    if monitor {
+        proton = proton.monitor(Duration::from_secs(1));
+    }
+
+    if !no_fixes {
+        proton = proton.fixes(Fixes::open_skipping()?);
+    }
+
+    proton = integration.apply(graphics.apply(config, game, proton));
+
+    if win32 {
+        proton = proton.win32();
+    }
+
+    if let Some(runtime) = runtime {
+        proton = proton.runtime(runtime);
+    }
+
+    proton = proton
+        .inherit_env(config.env_allow().to_vec(), config.env_deny().to_vec())
+        .retries(config.retries())
+        .compat_policy(config.compat_policy())
+        .wait(wait)
+        .interrupt(interrupt);
+
+    if let Some(enable) = overlay {
+        proton = proton.overlay(enable);
+    }
+
+    if let Some(enable) = tty {
+        proton = proton.tty(enable);
+    }
+
+    pass!(anticheat(config, proton, no_eac, no_battleye).envs(env_vars))
+}
 
-        let exit = proton.run()?;
+/// Reads the `KEY=VALUE` variables of every `-e` given
+fn env_flags(parser: &mut jargon_args::Jargon) -> Result<Vec<(String, String)>, Error> {
+    let mut vars: Vec<(String, String)> = Vec::new();
 
-        if !exit.success() {
-            if let Some(code) = exit.code() {
-                throw!(Kind::ProtonExit, "code: {}", code);
+    while let Some(var) = parser.option_arg::<String, _>(["-e", "--env"]) {
+        match var.split_once('=') {
+            Some((key, value)) if !key.is_empty() => {
+                vars.push((key.to_string(), value.to_string()));
             }
-            throw!(Kind::ProtonExit, "an error");
+            _ => throw!(Kind::ArgumentParse, "-e '{}', expected KEY=VALUE", var),
         }
     }
 
-    Ok(())
+    pass!(vars)
+}
+
+/// Returns the program's arguments, the ones in its `[games.NAME]` table when `args` is empty
+fn default_args(game: Option<(&str, &Game)>, args: Vec<String>) -> Vec<String> {
+    match game {
+        Some((_, game)) if args.is_empty() => game.args().to_vec(),
+        _ => args,
+    }
+}
+
+/// Sets the save sync hooks, variables and command template of a program's `[games.NAME]` table
+fn game_settings(game: &Game, mut proton: Proton) -> Proton {
+    proton = proton.sync_hooks(game.sync_hooks());
+
+    for (key, value) in game.env() {
+        proton = proton.env(key, value, EnvSource::Config);
+    }
+
+    if let Some(template) = game.command_template() {
+        proton = proton.command_template(template);
+    }
+
+    proton
+}
+
+/// Passes the installed anti-cheat runtimes to Proton, unless disabled or one is set already,
+/// since `EasyAntiCheat` and `BattlEye` titles fail silently without them
+fn anticheat(config: &Config, mut proton: Proton, no_eac: bool, no_battleye: bool) -> Proton {
+    for (var, path) in Index::anticheat(&config.common()) {
+        let disabled: bool = match var {
+            "PROTON_EAC_RUNTIME" => no_eac,
+            _ => no_battleye,
+        };
+
+        if !disabled && std::env::var_os(var).is_none() {
+            proton = proton.env(var, &path.to_string_lossy(), EnvSource::ProtonCall);
+        }
+    }
+
+    proton
+}
+
+/// Graphics options of a launch, from the config and flags
+struct Graphics {
+    shaders: Shaders,
+    nvidia: Nvidia,
+    wayland: bool,
+    precache: Option<Duration>,
+}
+
+impl Graphics {
+    /// Reads the shader compilation, NVIDIA and display options of the config, `game`'s settings
+    /// and flags, and how long `--precache` runs
+    fn read(
+        config: &Config,
+        game: Option<(&str, &Game)>,
+        parser: &mut jargon_args::Jargon,
+    ) -> Result<Graphics, Error> {
+        let mut shaders: Shaders = config.shaders();
+
+        if parser.contains("--dxvk-async") {
+            shaders = shaders.dxvk_async(true);
+        }
+
+        if let Some(enable) = toggle(parser, "--gpl", "--no-gpl") {
+            shaders = shaders.gpl(enable);
+        }
+
+        let radv: Option<String> = parser.option_arg("--radv-perftest");
+        if let Some(options) = radv {
+            shaders = shaders.radv_perftest(&options);
+        }
+
+        let mut nvidia: Nvidia = config.nvidia(game.map(|(_, game)| game));
+
+        if let Some(enable) = toggle(parser, "--nvapi", "--no-nvapi") {
+            nvidia = nvidia.nvapi(enable);
+        }
+
+        if parser.contains("--dlss-indicator") {
+            nvidia = nvidia.dlss_indicator(true);
+        }
+
+        let wayland: bool = parser.contains("--wayland-native");
+
+        let seconds: Option<String> = parser.option_arg("--precache-for");
+        let precache: Option<Duration> = match seconds {
+            Some(s) => match s.parse() {
+                Ok(secs) => Some(Duration::from_secs(secs)),
+                Err(e) => throw!(Kind::ArgumentParse, "--precache-for '{}': {}", s, e),
+            },
+            None if parser.contains("--precache") => Some(PRECACHE),
+            None => None,
+        };
+
+        pass!(Graphics {
+            shaders,
+            nvidia,
+            wayland,
+            precache,
+        })
+    }
+
+    /// Sets the variables of the options, tagged as from flags if any changed what the config
+    /// sets, and precaches if asked to
+    fn apply(&self, config: &Config, game: Option<&Game>, mut proton: Proton) -> Proton {
+        if let Some(duration) = self.precache {
+            proton = proton.precache(duration);
+        }
+
+        if self.wayland {
+            proton = proton.wayland_native();
+        }
+
+        let source = |changed: bool| -> EnvSource {
+            if changed {
+                EnvSource::Flag
+            } else {
+                EnvSource::Config
+            }
+        };
+
+        let shaders: EnvSource = source(self.shaders != config.shaders());
+        for (key, value) in self.shaders.env(|key| std::env::var(key).ok()) {
+            proton = proton.env(key, &value, shaders);
+        }
+
+        let nvidia: EnvSource = source(self.nvidia != config.nvidia(game));
+        for (key, value) in self.nvidia.env() {
+            proton = proton.env(key, &value, nvidia);
+        }
+
+        proton
+    }
+}
+
+/// How a launch's Wine deals with the host desktop, from the config and flags
+struct Integration {
+    crash_dialog: bool,
+    menus: bool,
+    discord: Option<Discord>,
+    virtual_desktop: Option<VirtualDesktop>,
+}
+
+impl Integration {
+    /// Reads the host integration options of the config and flags
+    fn read(config: &Config, parser: &mut jargon_args::Jargon) -> Result<Integration, Error> {
+        let size: Option<String> = parser.option_arg("--virtual-desktop");
+
+        pass!(Integration {
+            crash_dialog: !parser.contains("--no-crash-dialog"),
+            menus: parser.contains("--allow-menus") || config.allow_menus(),
+            discord: config.discord(parser.contains("--discord")),
+            virtual_desktop: size.map(|s| s.parse()).transpose()?,
+        })
+    }
+
+    /// Sets the options on `proton`
+    fn apply(self, mut proton: Proton) -> Proton {
+        if !self.crash_dialog {
+            proton = proton.no_crash_dialog();
+        }
+
+        if !self.menus {
+            proton = proton.no_menus();
+        }
+
+        if let Some(discord) = self.discord {
+            proton = proton.discord(discord);
+        }
+
+        if let Some(desktop) = self.virtual_desktop {
+            proton = proton.virtual_desktop(desktop);
+        }
+
+        proton
+    }
+}
+
+/// Runs a launch of `program`, remembering it in the recent list and the run log
+fn run_program(proton: Proton, program: &Path) -> Result<(), Error> {
+    // Remembering the program is a convenience, a failure must not stop the launch
+    if let Err(e) = Recent::open().and_then(|mut r| r.record(program)) {
+        Warnings::push("recent", e.to_string());
+    }
+
+    let outcome: RunOutcome = proton.run()?;
+    println!(
+        "{} {}",
+        program.file_name().unwrap_or_default().to_string_lossy(),
+        outcome
+    );
+
+    if let Err(e) = Metrics::record(&RunRecord::new(program, &outcome)) {
+        Warnings::push("history", e.to_string());
+    }
+
+    check_exit(&outcome)
+}
+
+/// Starts the program and returns without waiting for it, printing its process ID if asked
+fn detach_program(proton: Proton, program: &Path, print_pid: bool) -> Result<(), Error> {
+    if let Err(e) = Recent::open().and_then(|mut r| r.record(program)) {
+        Warnings::push("recent", e.to_string());
+    }
+
+    let launched: Launched = proton.spawn()?;
+
+    if print_pid {
+        println!("{}", launched.id());
+    }
+
+    pass!()
+}
+
+/// Turns an unsuccessful Proton exit into an error
+fn check_exit(outcome: &RunOutcome) -> Result<(), Error> {
+    // A launch shut down at its time limit ran for as long as it was meant to
+    if !outcome.success() && !outcome.time_limit_reached() {
+        if let Some(code) = outcome.code() {
+            throw!(Kind::ProtonExit, "code: {}", code);
+        }
+        if let Some(signal) = outcome.signal() {
+            throw!(Kind::ProtonExit, "signal: {}", signal);
+        }
+        throw!(Kind::ProtonExit, "an error");
+    }
+
+    pass!()
 }
 
 /// Runs caller in normal mode, running indexed Proton versions
 fn normal_mode(config: &Config, args: Args) -> Result<Proton, Error> {
-    let common_index: Index = Index::new(&config.common())?;
+    let proton: Proton =
+        Proton::from_config(config, args.program, args.args, args.proton.as_deref())?;
 
-    let proton_path: PathBuf = match common_index.get(args.version) {
-        Some(pp) => pp,
-        None => throw!(
-            Kind::ProtonMissing,
-            "Proton {} does not exist",
-            args.version
-        ),
+    if args.log {
+        return pass!(proton.log());
+    }
+
+    pass!(proton)
+}
+
+/// Picks the Proton version a launch asks for, holding a program to the version pinned in its
+/// `[games.NAME]` table unless the launch is forced
+fn pinned(
+    game: Option<(&str, &Game)>,
+    requested: Option<String>,
+    force: bool,
+) -> Result<Option<String>, Error> {
+    let Some((name, pin)) = game.and_then(|(name, game)| Some((name, game.proton()?))) else {
+        return pass!(requested);
     };
 
-    let proton: Proton = Proton::new(
-        args.version,
-        proton_path,
-        args.program,
-        args.args,
-        args.log,
-        config.data(),
-        config.steam(),
-    );
+    match requested {
+        None => pass!(Some(pin.to_string())),
+        Some(version) if same_version(&version, pin) => pass!(Some(version)),
+        Some(version) if force => {
+            Warnings::push(
+                "launch",
+                format!(
+                    "'{}' is pinned to Proton {}, launching with {} as forced",
+                    name, pin, version
+                ),
+            );
+            pass!(Some(version))
+        }
+        Some(version) => throw!(
+            Kind::VersionPinned,
+            "'{}' is pinned to Proton {}, use --force to launch with {}",
+            name,
+            pin,
+            version
+        ),
+    }
+}
 
-    pass!(proton)
+/// Tells whether two `-p` values name the same version, such as `7.0` and `7.00`
+fn same_version(a: &str, b: &str) -> bool {
+    match (a.parse::<Version>(), b.parse::<Version>()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a.eq_ignore_ascii_case(b),
+    }
 }
 
 /// Runs caller in custom mode, using a custom Proton path
 fn custom_mode(config: &Config, args: Args) -> Result<Proton, Error> {
     if let Some(custom) = args.custom {
-        let proton: Proton = Proton::new(
-            Version::from_custom(custom.as_path()),
-            custom,
-            args.program,
-            args.args,
-            args.log,
-            config.data(),
-            config.steam(),
-        );
+        let script: PathBuf = proton_script(&custom)?;
+        let dir: &Path = script.parent().unwrap_or(&custom);
 
-        return pass!(proton);
+        return ProtonBuilder::new()
+            .config(config)
+            .version(Version::from_custom(dir))
+            .path(script.clone())
+            .program(args.program)
+            .args(args.args)
+            .log(args.log)
+            .build();
     }
 
     throw!(Kind::Internal, "failed to run custom mode")
 }
 
-#[doc(hidden)]
-static HELP: &str = "\
-Usage: proton-call [OPTIONS]... EXE [EXTRA]...
-
-Options:
-    -c, --custom [PATH]     Path to a directory containing Proton to use
-    -h, --help              View this help message
-    -i, --index             View an index of installed Proton versions
-    -l, --log               Pass PROTON_LOG variable to Proton
-    -p, --proton [VERSION]  Use Proton VERSION from `common`
-    -r, --run EXE           Run EXE in proton
-    -V, --verbose           Run in verbose mode
-    -v, --version           View version information
-
-Config:
-    The config file should be located at '$XDG_CONFIG_HOME/proton.conf' or '$HOME/.config/proton.conf'
-    The config requires two values.
-    Data: a location to any directory to contain Proton's runtime files.
-    Steam: the directory to where steam is installed (the one which contains the steamapps directory).
-    Common: the directory to where your proton versions are stored, usually Steam's steamapps/common directory.
-    Example:
-        data = \"/home/avery/Documents/Proton/env/\"
-        steam = \"/home/avery/.steam/steam/\"
-        common = \"/home/avery/.steam/steam/steamapps/common/\"
-";
+/// Removes and returns the subcommand directly following the program name
+fn subcommand(args: &mut Vec<String>) -> Option<String> {
+    match args.get(1) {
+        Some(arg) if !arg.starts_with('-') => Some(args.remove(1)),
+        _ => None,
+    }
+}
 
-#[doc(hidden)]
-fn help() {
-    println!("{}", HELP);
+/// Takes the flags choosing the config and its directories from the arguments before the
+/// subcommand or `-r`, so the program's own arguments are left alone: `--config PATH` points
+/// `open_config` at PATH, and `--data`, `--steam` and `--common` are kept for it
+fn global_flags(args: &mut Vec<String>) -> Result<(), Error> {
+    let mut config: Option<PathBuf> = None;
+    let mut dirs: Dirs = Dirs::default();
+    let mut i: usize = 1;
+
+    while let Some(arg) = args.get(i) {
+        let slot: &mut Option<PathBuf> = match arg.as_str() {
+            "--config" => &mut config,
+            "--data" => &mut dirs.data,
+            "--steam" => &mut dirs.steam,
+            "--common" => &mut dirs.common,
+            "-r" | "--run" => break,
+            // The subcommand, once the global flags before it are taken
+            arg if i == 1 && !arg.starts_with('-') => break,
+            _ => {
+                i += 1;
+                continue;
+            }
+        };
+
+        if i + 1 >= args.len() {
+            throw!(Kind::ArgumentMissing, "{} value", args[i]);
+        }
+
+        *slot = Some(PathBuf::from(args.remove(i + 1)));
+        args.remove(i);
+    }
+
+    if let Some(config) = config {
+        let _ = CONFIG.set(config);
+    }
+    let _ = DIRS.set(dirs);
+
+    pass!()
+}
+
+/// Finds the config `open_config` reads, given by `--config` or the default location
+fn config_location() -> Result<PathBuf, Error> {
+    match CONFIG.get() {
+        Some(path) => pass!(path.clone()),
+        None => Config::config_location(),
+    }
+}
+
+/// Opens the config, with the directories given on the command line over its own
+fn open_config() -> Result<Config, Error> {
+    let mut config: Config = Config::open_from(&config_location()?)?;
+
+    if let Some(dirs) = DIRS.get() {
+        if let Some(data) = &dirs.data {
+            config = config.with_data(data.clone());
+        }
+
+        if let Some(steam) = &dirs.steam {
+            config = config.with_steam(steam.clone());
+        }
+
+        if let Some(common) = &dirs.common {
+            config = config.with_common(common.clone());
+        }
+    }
+
+    pass!(config)
+}
+
+/// Runs the `config` subcommands, inspecting the config in use
+fn config_command(mut args: Vec<String>) -> Result<(), Error> {
+    let action: String = match subcommand(&mut args) {
+        Some(a) => a,
+        None => throw!(Kind::ArgumentMissing, "config command"),
+    };
+
+    match action.as_str() {
+        "show" => println!("{}", open_config()?.settings()),
+        "init" => init_config(jargon_args::Jargon::from_vec(args))?,
+        _ => throw!(Kind::UnknownCommand, "'config {}'", action),
+    }
+
+    pass!()
+}
+
+/// Runs the `prefix` subcommands, managing compat data directories
+fn prefix_command(mut args: Vec<String>) -> Result<(), Error> {
+    let config: Config = open_config()?;
+
+    let action: String = match subcommand(&mut args) {
+        Some(a) => a,
+        None => throw!(Kind::ArgumentMissing, "prefix command"),
+    };
+
+    match action.as_str() {
+        "list" => println!("{}", PrefixListing::new(&Prefix::list(&config.data())?)),
+        "info" => {
+            let name: String = match subcommand(&mut args) {
+                Some(n) => n,
+                None => throw!(Kind::ArgumentMissing, "prefix name"),
+            };
+
+            let prefix: Prefix = Prefix::find(&config.data(), &name)?;
+            println!("{}", prefix.info()?);
+        }
+        "repair" => {
+            use jargon_args::Jargon;
+
+            let mut parser: Jargon = Jargon::from_vec(args);
+            let version: Option<Version> = parser.option_arg(["-p", "--proton"]);
+            let name: String = match parser.finish().into_iter().next() {
+                Some(n) => n,
+                None => throw!(Kind::ArgumentMissing, "prefix name"),
+            };
+
+            let prefix: Prefix = Prefix::find(&config.data(), &name)?;
+            let proton: Proton = prefix_proton(&config, &prefix, version, "wineboot", &["-u"])?;
+
+            prefix.invalidate()?;
+            check_exit(&proton.run()?)?;
+        }
+        "create" => create_prefix(&config, args)?,
+        "gc" => {
+            use jargon_args::Jargon;
+
+            let mut parser: Jargon = Jargon::from_vec(args);
+            let age: String = parser
+                .option_arg(["-u", "--unused-for"])
+                .unwrap_or_else(|| String::from("90d"));
+            let orphans: bool = parser.contains(["-o", "--orphans"]);
+            let yes: bool = parser.contains(["-y", "--yes"]);
+
+            let unused: Duration = parse_age(&age)?;
+            // Plain numbers are days
+            let age: String = if age.ends_with(|c: char| c.is_ascii_digit()) {
+                format!("{}d", age)
+            } else {
+                age
+            };
+            let index: Index = Index::from_config(&config)?;
+            let mut doomed: Vec<Prefix> = Vec::new();
+
+            for prefix in Prefix::list(&config.data())? {
+                let orphan: Option<Orphan> = prefix.orphaned(&index, &config);
+
+                if prefix.unused_for(unused) {
+                    println!("unused for {}: {}", age, prefix.name());
+                } else if let Some(orphan) = orphan {
+                    println!("orphaned, {}: {}", orphan, prefix.name());
+                    if !orphans {
+                        continue;
+                    }
+                } else {
+                    continue;
+                }
+
+                doomed.push(prefix);
+            }
+
+            if doomed.is_empty() {
+                println!("{}", tr("no-prefixes-to-delete"));
+                return pass!();
+            }
+
+            if yes || confirm(&format!("Delete {} prefixes?", doomed.len()))? {
+                for prefix in doomed {
+                    prefix.remove()?;
+                }
+            }
+        }
+        "clean-menus" => clean_menus(&config, args)?,
+        "dedup" => dedup_prefixes(&config, args)?,
+        _ => throw!(Kind::UnknownCommand, "'prefix {}'", action),
+    }
+
+    pass!()
+}
+
+/// Runs `prefix create`, initializing a prefix with the Proton a launch would use, unless `-p`
+/// names another
+fn create_prefix(config: &Config, args: Vec<String>) -> Result<(), Error> {
+    use jargon_args::Jargon;
+
+    let mut parser: Jargon = Jargon::from_vec(args);
+    let version: Option<String> = parser.option_arg(["-p", "--proton"]);
+    let dedup: bool = parser.contains("--dedup") || config.dedup();
+    let name: String = match parser.finish().into_iter().next() {
+        Some(n) => n,
+        None => throw!(Kind::ArgumentMissing, "prefix name"),
+    };
+
+    let prefix: Prefix = Prefix::named(&config.data(), &name)?;
+    if prefix.is_initialized() {
+        throw!(Kind::PrefixExists, "'{}'", name);
+    }
+
+    // The version a launch would pick, the newest installed one unless -p names another
+    let args: Vec<String> = vec![String::from("/c"), String::from("exit")];
+    let proton: Proton =
+        Proton::from_config(config, PathBuf::from("cmd"), args, version.as_deref())?
+            .builtin()
+            .prefix(prefix.path().to_path_buf());
+
+    let outcome: RunOutcome = proton.run()?;
+    check_exit(&outcome)?;
+    println!(
+        "Created prefix '{}' with Proton {}",
+        name,
+        outcome.version()
+    );
+
+    if dedup {
+        let prefixes: Vec<Prefix> = Prefix::list(&config.data())?;
+        println!("{}", Dedup::run(&pfx_dirs(&prefixes))?);
+    }
+
+    pass!()
+}
+
+/// Runs `prefix dedup`, sharing identical files between the named prefixes, or every prefix
+fn dedup_prefixes(config: &Config, args: Vec<String>) -> Result<(), Error> {
+    let names: Vec<String> = args.into_iter().filter(|a| !a.starts_with('-')).collect();
+    let prefixes: Vec<Prefix> = if names.is_empty() {
+        Prefix::list(&config.data())?
+    } else {
+        names
+            .iter()
+            .map(|name| Prefix::find(&config.data(), name))
+            .collect::<Result<Vec<Prefix>, Error>>()?
+    };
+
+    println!("{}", Dedup::run(&pfx_dirs(&prefixes))?);
+    pass!()
+}
+
+/// Returns the Wine prefix directories of `prefixes`
+fn pfx_dirs(prefixes: &[Prefix]) -> Vec<PathBuf> {
+    prefixes.iter().map(Prefix::pfx).collect()
+}
+
+/// Runs `prefix clean-menus`, removing the menu entries Wine created on the host for a prefix
+fn clean_menus(config: &Config, args: Vec<String>) -> Result<(), Error> {
+    use jargon_args::Jargon;
+
+    let mut parser: Jargon = Jargon::from_vec(args);
+    let yes: bool = parser.contains(["-y", "--yes"]);
+    let name: String = match parser.finish().into_iter().next() {
+        Some(n) => n,
+        None => throw!(Kind::ArgumentMissing, "prefix name"),
+    };
+
+    let prefix: Prefix = Prefix::find(&config.data(), &name)?;
+    let entries: MenuEntries = MenuEntries::find(&prefix);
+    if entries.is_empty() {
+        println!("Wine created no menu entries for '{}'", name);
+        return pass!();
+    }
+
+    for file in entries.files() {
+        println!("{}", file.to_string_lossy());
+    }
+
+    let question: String = format!("Remove {} files?", entries.files().len());
+    if yes || confirm(&question)? {
+        entries.remove()?;
+    }
+
+    pass!()
+}
+
+/// Writes a new config from the Steam install found and proposed directories, asking to confirm
+/// or change each unless `-y` is given
+fn init_config(mut parser: jargon_args::Jargon) -> Result<(), Error> {
+    let yes: bool = parser.contains(["-y", "--yes"]);
+    let force: bool = parser.contains("--force");
+    let loc: PathBuf = config_location()?;
+
+    if loc.exists() && !force {
+        throw!(
+            Kind::ConfigWrite,
+            "'{}' exists, use --force to replace it",
+            loc.to_string_lossy()
+        );
+    }
+
+    let detected: Option<PathBuf> = Config::detect_steam();
+    if detected.is_none() {
+        println!("No Steam install found");
+    }
+
+    let proposed = |question: &str, default: Option<PathBuf>| -> Result<PathBuf, Error> {
+        let default: String = default.map_or_else(String::new, |d| d.to_string_lossy().to_string());
+
+        match (yes, default.is_empty()) {
+            (true, false) => pass!(PathBuf::from(default)),
+            (true, true) => throw!(Kind::ArgumentMissing, "{}, run without -y", question),
+            (false, _) => pass!(PathBuf::from(ask(question, &default)?)),
+        }
+    };
+
+    let steam: PathBuf = proposed("Steam directory", detected)?;
+    let data: PathBuf = proposed("Compat data directory", Config::default_data())?;
+    let common: PathBuf = proposed(
+        "Steam's common directory",
+        Some(steam.join("steamapps/common")),
+    )?;
+
+    if !common.is_dir() {
+        Warnings::push(
+            "config",
+            format!("'{}' is not a directory", common.to_string_lossy()),
+        );
+    }
+
+    if !yes && !confirm(&format!("Write {}?", loc.to_string_lossy()))? {
+        return pass!();
+    }
+
+    Config::init(&loc, &data, &steam, &common)?;
+    Config::open_from(&loc)?;
+    println!("Wrote {}", loc.to_string_lossy());
+
+    pass!()
+}
+
+/// Asks the user for a value, taking `default` when they give none
+fn ask(question: &str, default: &str) -> Result<String, Error> {
+    use std::io::Write;
+
+    if default.is_empty() {
+        print!("{}: ", question);
+    } else {
+        print!("{} [{}]: ", question, default);
+    }
+    let _ = std::io::stdout().flush();
+
+    let mut answer: String = String::new();
+    if let Err(e) = std::io::stdin().read_line(&mut answer) {
+        throw!(Kind::Internal, "failed to read answer: {}", e);
+    }
+
+    match answer.trim() {
+        "" if default.is_empty() => throw!(Kind::ArgumentMissing, "{}", question),
+        "" => pass!(default.to_string()),
+        answer => pass!(answer.to_string()),
+    }
+}
+
+/// Asks the user a yes or no question, defaulting to no
+fn confirm(question: &str) -> Result<bool, Error> {
+    use std::io::Write;
+
+    print!("{} [y/N] ", question);
+    let _ = std::io::stdout().flush();
+
+    let mut answer: String = String::new();
+    if let Err(e) = std::io::stdin().read_line(&mut answer) {
+        throw!(Kind::Internal, "failed to read answer: {}", e);
+    }
+
+    pass!(matches!(answer.trim(), "y" | "Y" | "yes"))
+}
+
+/// Asks the user to pick one of `count` numbered items, `None` if they pick nothing
+fn choose(question: &str, count: usize) -> Result<Option<usize>, Error> {
+    use std::io::Write;
+
+    print!("{} [1-{}] ", question, count);
+    let _ = std::io::stdout().flush();
+
+    let mut answer: String = String::new();
+    if let Err(e) = std::io::stdin().read_line(&mut answer) {
+        throw!(Kind::Internal, "failed to read answer: {}", e);
+    }
+
+    match answer.trim().parse::<usize>() {
+        Ok(n) if (1..=count).contains(&n) => pass!(Some(n)),
+        _ => pass!(None),
+    }
+}
+
+/// Runs the `fonts` subcommands, installing fonts into a prefix
+fn fonts_command(mut args: Vec<String>) -> Result<(), Error> {
+    use jargon_args::Jargon;
+
+    let config: Config = open_config()?;
+
+    match subcommand(&mut args).as_deref() {
+        Some("install") => (),
+        Some(action) => throw!(Kind::UnknownCommand, "'fonts {}'", action),
+        None => throw!(Kind::ArgumentMissing, "fonts command"),
+    }
+
+    let mut parser: Jargon = Jargon::from_vec(args);
+    let version: Option<Version> = parser.option_arg(["-p", "--proton"]);
+    let name: String = parser
+        .option_arg(["-P", "--prefix"])
+        .unwrap_or_else(|| format!("Proton {}", version.unwrap_or_default()));
+
+    let pack: FontPack = match parser.finish().into_iter().next() {
+        Some(p) => p.parse()?,
+        None => throw!(Kind::ArgumentMissing, "font pack"),
+    };
+
+    let prefix: Prefix = Prefix::find(&config.data(), &name)?;
+    let reg: String = windows_path(&pack.install(&prefix)?);
+
+    let proton: Proton = prefix_proton(&config, &prefix, version, "regedit", &["/S", &reg])?;
+    check_exit(&proton.run()?)?;
+
+    println!("Installed {} into '{}'", pack, prefix.name());
+
+    pass!()
+}
+
+/// Runs the `saves` subcommands, finding and backing up save games in prefixes
+fn saves_command(mut args: Vec<String>) -> Result<(), Error> {
+    let config: Config = open_config()?;
+
+    let action: String = match subcommand(&mut args) {
+        Some(a) => a,
+        None => throw!(Kind::ArgumentMissing, "saves command"),
+    };
+
+    let target: String = match subcommand(&mut args) {
+        Some(t) => t,
+        None => throw!(Kind::ArgumentMissing, "prefix or program"),
+    };
+
+    match action.as_str() {
+        "locate" => println!("{}", locate_saves(&config, target)?),
+        "backup" => {
+            let prefix: Prefix = game_prefix(&config, &target)?;
+            let retention: Option<usize> = config.game(&target).and_then(Game::retention);
+            let archive: PathBuf =
+                Saves::backup(&prefix, &config.backups()?.join(&target), retention)?;
+
+            println!("Backed up saves to {}", archive.to_string_lossy());
+        }
+        "restore" => {
+            let prefix: Prefix = game_prefix(&config, &target)?;
+            let archive: PathBuf = match subcommand(&mut args) {
+                Some(a) => PathBuf::from(a),
+                None => match Saves::backups(&config.backups()?.join(&target))
+                    .into_iter()
+                    .next()
+                {
+                    Some(a) => a,
+                    None => throw!(Kind::Backup, "no backups of '{}'", target),
+                },
+            };
+
+            Saves::restore(&prefix, &archive)?;
+            println!("Restored saves from {}", archive.to_string_lossy());
+        }
+        _ => throw!(Kind::UnknownCommand, "'saves {}'", action),
+    }
+
+    pass!()
+}
+
+/// Runs the `export` subcommand, bundling a program's setup into an archive
+fn export_command(args: Vec<String>) -> Result<(), Error> {
+    use jargon_args::Jargon;
+
+    let config: Config = open_config()?;
+
+    let mut parser: Jargon = Jargon::from_vec(args);
+    let out: PathBuf = parser.result_arg(["-o", "--output"])?;
+    let with_prefix: bool = parser.contains(["-w", "--with-prefix"]);
+    let name: String = match parser.finish().into_iter().next() {
+        Some(n) => n,
+        None => throw!(Kind::ArgumentMissing, "program name"),
+    };
+
+    Bundle::export(&config, &name, with_prefix, &out)?;
+    println!("Exported '{}' to {}", name, out.to_string_lossy());
+
+    pass!()
+}
+
+/// Runs the `export-lutris` subcommand, printing or writing a Lutris game config for a launch
+fn export_lutris_command(mut args: Vec<String>) -> Result<(), Error> {
+    use jargon_args::Jargon;
+
+    let config: Config = open_config()?;
+
+    let target: String = match subcommand(&mut args) {
+        Some(t) => t,
+        None => throw!(Kind::ArgumentMissing, "program"),
+    };
+
+    // A program's name from the config stands in for its executable
+    let program: PathBuf = match config.game(&target).and_then(Game::path) {
+        Some(path) => path.to_path_buf(),
+        None => resolve_program(&config, &target)?,
+    };
+
+    let mut parser: Jargon = Jargon::from_vec(args);
+    let out: Option<PathBuf> = parser.option_arg(["-o", "--output"]);
+
+    let yaml: String = launch(&config, parser, program)?.lutris();
+
+    match out {
+        Some(out) => {
+            if let Err(e) = std::fs::write(&out, yaml) {
+                throw!(Kind::ConfigWrite, "{}: {}", out.to_string_lossy(), e);
+            }
+            println!("Exported '{}' to {}", target, out.to_string_lossy());
+        }
+        None => print!("{}", yaml),
+    }
+
+    pass!()
+}
+
+/// Runs the `import` subcommand, installing a bundle made by `export`
+fn import_command(mut args: Vec<String>) -> Result<(), Error> {
+    let config: Config = open_config()?;
+
+    let archive: PathBuf = match subcommand(&mut args) {
+        Some(a) => match a.parse::<Tool>() {
+            Ok(tool) => return import_tool(&config, tool),
+            Err(_) => PathBuf::from(a),
+        },
+        None => throw!(Kind::ArgumentMissing, "bundle"),
+    };
+
+    let bundle: Bundle = Bundle::open(&archive)?;
+    bundle.install(&config, &archive)?;
+    println!("Imported '{}'", bundle.name());
+
+    // Overrides are already part of a bundled prefix, otherwise apply them to an existing one
+    if bundle.prefix().is_none() && !bundle.overrides().is_empty() {
+        match game_prefix(&config, bundle.game().prefix().unwrap_or(bundle.name())) {
+            Ok(prefix) if prefix.is_initialized() => {
+                let reg: String = windows_path(&bundle.overrides_reg()?);
+                let proton: Proton = prefix_proton(&config, &prefix, None, "regedit", &["/S", &reg])?;
+                check_exit(&proton.run()?)?;
+            }
+            _ => println!(
+                "The bundle's DLL overrides were not applied, the prefix for '{}' does not exist yet",
+                bundle.name()
+            ),
+        }
+    }
+
+    pass!()
+}
+
+/// Registers the prefixes of another launcher as named prefixes
+fn import_tool(config: &Config, tool: Tool) -> Result<(), Error> {
+    let found: Vec<ForeignPrefix> = ForeignPrefix::discover(tool);
+
+    if found.is_empty() {
+        println!("No {} prefixes found", tool);
+        return pass!();
+    }
+
+    let index: Index = Index::from_config(config)?;
+
+    for foreign in found {
+        let version: Option<Version> = foreign.proton(&index);
+        let runner: &str = foreign.runner().unwrap_or("an unknown runner");
+
+        let prefix: Prefix = match foreign.register(&config.data(), version) {
+            Ok(p) => p,
+            Err(e) => {
+                Warnings::push(
+                    "import",
+                    format!("skipped {} prefix '{}': {}", tool, foreign.name(), e),
+                );
+                continue;
+            }
+        };
+
+        match version {
+            Some(v) => println!(
+                "Imported {} prefix '{}' using {} as Proton {}",
+                tool,
+                prefix.name(),
+                runner,
+                v
+            ),
+            None => println!(
+                "Imported {} prefix '{}', no indexed Proton matches {}, use -p",
+                tool,
+                prefix.name(),
+                runner
+            ),
+        }
+    }
+
+    pass!()
+}
+
+/// Runs the `find` subcommand, searching game directories for an executable to launch
+fn find_command(mut args: Vec<String>) -> Result<(), Error> {
+    use jargon_args::Jargon;
+
+    let config: Config = open_config()?;
+
+    let query: String = match subcommand(&mut args) {
+        Some(q) => q,
+        None => throw!(Kind::ArgumentMissing, "query"),
+    };
+
+    let mut finder: Finder = Finder::search(&config.roots(), &query);
+    finder.truncate(10);
+
+    if finder.is_empty() {
+        println!("No executables match '{}'", query);
+        return pass!();
+    }
+
+    println!("{}", finder);
+
+    let program: PathBuf = match choose(tr("launch-which"), finder.found().len())? {
+        Some(n) => finder.found()[n - 1].path().to_path_buf(),
+        None => return pass!(),
+    };
+
+    let proton: Proton = launch(&config, Jargon::from_vec(args), program.clone())?;
+    run_program(proton, &program)
+}
+
+/// Runs the `steam` subcommands, integrating proton-call with the Steam client
+fn steam_command(mut args: Vec<String>) -> Result<(), Error> {
+    use jargon_args::Jargon;
+
+    let action: String = match subcommand(&mut args) {
+        Some(a) => a,
+        None => throw!(Kind::ArgumentMissing, "steam command"),
+    };
+
+    if action != "install-tool" {
+        throw!(Kind::UnknownCommand, "'steam {}'", action);
+    }
+
+    let config: Config = open_config()?;
+    let mut parser: Jargon = Jargon::from_vec(args);
+
+    let caller: PathBuf = match std::env::current_exe() {
+        Ok(path) => path,
+        Err(e) => throw!(
+            Kind::Environment,
+            "can not find the proton-call executable: {}",
+            e
+        ),
+    };
+
+    let mut tool: CompatTool = CompatTool::new(&config.steam(), caller);
+    if let Some(version) = parser.option_arg(["-p", "--proton"]) {
+        tool = tool.proton(version);
+    }
+
+    tool.install()?;
+
+    println!(
+        "Installed the compatibility tool into {}, restart Steam to pick proton-call for a game",
+        tool.dir().to_string_lossy()
+    );
+    pass!()
+}
+
+/// Runs the `install` subcommand, downloading a GE-Proton release into `compatibilitytools.d`
+fn install_command(mut args: Vec<String>) -> Result<(), Error> {
+    let Some(name) = subcommand(&mut args) else {
+        throw!(Kind::ArgumentMissing, "GE-Proton release");
+    };
+
+    let config: Config = open_config()?;
+    let version: Version = name.parse()?;
+    let release: GeRelease = GeRelease::new(&config.steam(), version)?;
+
+    if release.is_installed() {
+        println!(
+            "{} is already installed in {}",
+            version,
+            release.dir().to_string_lossy()
+        );
+        return pass!();
+    }
+
+    release.install()?;
+
+    // Releases are indexed from compatibilitytools.d like any other build, so -p finds it now
+    if Index::from_config(&config)?.get(version).is_none() {
+        throw!(
+            Kind::ProtonMissing,
+            "{} was installed into {} but is not indexed",
+            version,
+            release.dir().to_string_lossy()
+        );
+    }
+
+    println!(
+        "Installed {} into {}, run it with -p {}",
+        version,
+        release.dir().to_string_lossy(),
+        version
+    );
+    pass!()
+}
+
+/// Runs the `recent` subcommand, listing recently launched programs for `-r @N`
+fn recent_command() -> Result<(), Error> {
+    let recent: Recent = Recent::open()?;
+
+    if recent.is_empty() {
+        println!("{}", tr("nothing-launched"));
+    } else {
+        println!("{}", recent);
+    }
+
+    pass!()
+}
+
+/// Runs the `history` subcommand, listing the newest launches in the run log
+fn history_command(args: Vec<String>) -> Result<(), Error> {
+    use jargon_args::Jargon;
+
+    let mut parser: Jargon = Jargon::from_vec(args);
+    let count: usize = parser.option_arg(["-n", "--count"]).unwrap_or(20);
+
+    let mut metrics: Metrics = Metrics::open()?;
+
+    if metrics.is_empty() {
+        println!("{}", tr("nothing-launched"));
+    } else {
+        metrics.truncate(count);
+        println!("{}", metrics);
+    }
+
+    pass!()
+}
+
+/// Runs the `stats` subcommand, totalling the launches of each program in the run log
+fn stats_command() -> Result<(), Error> {
+    let metrics: Metrics = Metrics::open()?;
+
+    if metrics.is_empty() {
+        println!("{}", tr("nothing-launched"));
+    } else {
+        println!("{}", metrics.stats());
+    }
+
+    pass!()
+}
+
+/// Runs the `tricks` subcommand, running winetricks verbs in a prefix or through protontricks
+fn tricks_command(args: Vec<String>) -> Result<(), Error> {
+    use jargon_args::Jargon;
+
+    let config: Config = open_config()?;
+
+    let mut parser: Jargon = Jargon::from_vec(args);
+    let via_protontricks: bool = parser.contains("--via-protontricks");
+    let appid: Option<u32> = parser.option_arg("--appid");
+    let prefix: Option<String> = parser.option_arg(["-P", "--prefix"]);
+    let version: Option<Version> = parser.option_arg(["-p", "--proton"]);
+    let tricks: Tricks = Tricks::new(parser.finish())?;
+
+    if via_protontricks {
+        return match appid {
+            Some(id) => tricks.via_protontricks(id),
+            None => throw!(
+                Kind::ArgumentMissing,
+                "--appid, protontricks needs the app id"
+            ),
+        };
+    }
+
+    let prefix: Prefix = match prefix {
+        Some(name) => game_prefix(&config, &name)?,
+        None => throw!(Kind::ArgumentMissing, "-P, --prefix"),
+    };
+
+    let version: Version = match version.or_else(|| prefix.version()) {
+        Some(v) => v,
+        None => throw!(
+            Kind::ProtonMissing,
+            "can not tell which Proton created '{}', use -p",
+            prefix.name()
+        ),
+    };
+
+    let proton: PathBuf = match Index::from_config(&config)?.get(version) {
+        Some(p) => p,
+        None => throw!(Kind::ProtonMissing, "Proton {} does not exist", version),
+    };
+
+    tricks.in_prefix(&prefix, &proton)
+}
+
+/// Finds saves for a prefix, or for a program across all prefixes
+fn locate_saves(config: &Config, target: String) -> Result<Saves, Error> {
+    if let Ok(prefix) = game_prefix(config, &target) {
+        return pass!(Saves::locate(&prefix));
+    }
+
+    let prefixes: Vec<Prefix> = Prefix::list(&config.data())?;
+    let program: PathBuf = PathBuf::from(&target);
+
+    // Programs are matched by their name, then by the directory they are installed in
+    let queries: Vec<String> = if program.is_file() {
+        [
+            program.file_stem(),
+            program.parent().and_then(|p| p.file_name()),
+        ]
+        .iter()
+        .flatten()
+        .map(|n| n.to_string_lossy().to_string())
+        .collect()
+    } else {
+        vec![target]
+    };
+
+    pass!(queries
+        .iter()
+        .map(|q| Saves::search(&prefixes, q))
+        .find(|s| !s.is_empty())
+        .unwrap_or_default())
+}
+
+/// Reads what the launch waits for before exiting, and what happens to the prefix on an interrupt
+fn exit_handling(parser: &mut jargon_args::Jargon) -> (Wait, Interrupt) {
+    let wait: Wait = if parser.contains("--wait-prefix") {
+        Wait::Prefix
+    } else if parser.contains("--wait-children") {
+        Wait::Children
+    } else {
+        Wait::Program
+    };
+
+    let interrupt: Interrupt = if parser.contains("--kill-on-exit") {
+        Interrupt::KillPrefix
+    } else {
+        Interrupt::Leave
+    };
+
+    (wait, interrupt)
+}
+
+/// Reads a pair of flags turning something on or off, `None` when neither is given
+fn toggle(parser: &mut jargon_args::Jargon, on: &str, off: &str) -> Option<bool> {
+    if parser.contains(on) {
+        Some(true)
+    } else if parser.contains(off) {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+/// Runs the `fixes` subcommand, showing the fix the fixes database holds for a program
+fn fixes_command(args: Vec<String>) -> Result<(), Error> {
+    use jargon_args::Jargon;
+
+    let mut parser: Jargon = Jargon::from_vec(args);
+    let appid: Option<u32> = parser.option_arg("--appid");
+    let program: PathBuf = match parser.finish().into_iter().next() {
+        Some(p) => PathBuf::from(p),
+        None => throw!(Kind::ArgumentMissing, "program"),
+    };
+
+    let key: String = match Fixes::key(&program) {
+        Some(k) => k,
+        None => throw!(Kind::ProgramMissing, "{}", program.to_string_lossy()),
+    };
+
+    println!("Database: {}", Fixes::dir()?.to_string_lossy());
+    println!("Key:      [exes.{}]", key);
+
+    match Fixes::open()?.find(appid, &program) {
+        Some(fix) => print!("{}", fix),
+        None => println!("No fix for {}", program.to_string_lossy()),
+    }
+
+    pass!()
+}
+
+/// Runs the `apply-fix` subcommand, importing a shared fix file into the fixes database once
+/// its changes are confirmed
+fn apply_fix_command(args: Vec<String>) -> Result<(), Error> {
+    use jargon_args::Jargon;
+
+    let mut parser: Jargon = Jargon::from_vec(args);
+    let name: Option<String> = parser.option_arg("--name");
+    let yes: bool = parser.contains(["-y", "--yes"]);
+    let source: String = match parser.finish().into_iter().next() {
+        Some(s) => s,
+        None => throw!(Kind::ArgumentMissing, "fix file or URL"),
+    };
+
+    let import: FixImport = FixImport::new(&source, name.as_deref())?;
+    if import.is_unchanged() {
+        println!(
+            "{} is already imported as it is",
+            import.dest().to_string_lossy()
+        );
+        return pass!();
+    }
+
+    println!("{}", import.diff());
+
+    if yes || confirm(&format!("Import into {}?", import.dest().to_string_lossy()))? {
+        import.write()?;
+        println!("Imported {}", import.dest().to_string_lossy());
+    }
+
+    pass!()
+}
+
+/// Runs the `shell` subcommand, running each line read inside a prefix with `runinprefix`
+fn shell_command(args: Vec<String>) -> Result<(), Error> {
+    use jargon_args::Jargon;
+
+    let config: Config = open_config()?;
+
+    let mut parser: Jargon = Jargon::from_vec(args);
+    let name: Option<String> = parser.option_arg(["-P", "--prefix"]);
+    let version: Option<Version> = parser.option_arg(["-p", "--proton"]);
+
+    let prefix: Prefix = match name.or_else(|| parser.finish().into_iter().next()) {
+        Some(name) => game_prefix(&config, &name)?,
+        None => throw!(Kind::ArgumentMissing, "-P, --prefix"),
+    };
+
+    // Fails before the first line if the Proton for the prefix can not be found
+    prefix_proton(&config, &prefix, version, "cmd", &[])?;
+
+    let shell: Shell = Shell::new(&prefix);
+    println!(
+        "Running in '{}', `exit` or Ctrl-D to leave, Tab completes drive_c paths",
+        prefix.name()
+    );
+
+    while let Some(line) = shell.read_line() {
+        let words: Vec<String> = Shell::words(&line);
+        let Some((program, args)) = words.split_first() else {
+            continue;
+        };
+
+        if program == "exit" || program == "quit" {
+            break;
+        }
+
+        let program: PathBuf = shell.program(program);
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+        let result: Result<RunOutcome, Error> =
+            prefix_proton(&config, &prefix, version, &program.to_string_lossy(), &args)
+                .and_then(|proton| proton.verb("runinprefix").run());
+
+        match result {
+            Ok(outcome) if !outcome.success() => println!("{}", outcome),
+            Ok(_) => (),
+            Err(e) => eprintln!("{}", e),
+        }
+
+        let warnings: Warnings = Warnings::take();
+        if !warnings.is_empty() {
+            eprintln!("{}", warnings);
+        }
+    }
+
+    pass!()
+}
+
+/// Runs the `util` subcommand, running a Wine built-in such as `winecfg` in a prefix, or in the
+/// compat data directory of the version launches use
+fn util_command(mut args: Vec<String>) -> Result<(), Error> {
+    use jargon_args::Jargon;
+
+    let Some(tool) = subcommand(&mut args) else {
+        throw!(Kind::ArgumentMissing, "util command");
+    };
+
+    if !UTILITIES.contains(&tool.as_str()) {
+        throw!(Kind::UnknownCommand, "'util {}'", tool);
+    }
+
+    let config: Config = open_config()?;
+    let mut parser: Jargon = Jargon::from_vec(args);
+    let version: Option<String> = parser.option_arg(["-p", "--proton"]);
+    let prefix: Option<String> = parser.option_arg(["-P", "--prefix"]);
+
+    let proton: Proton = match prefix {
+        Some(name) => {
+            let prefix: Prefix = game_prefix(&config, &name)?;
+            let version: Option<Version> = version.map(|v| v.parse()).transpose()?;
+            prefix_proton(&config, &prefix, version, &tool, &[])?
+        }
+        None => Proton::from_config(
+            &config,
+            PathBuf::from(&tool),
+            Vec::new(),
+            version.as_deref(),
+        )?
+        .builtin(),
+    };
+
+    check_exit(&proton.run()?)
+}
+
+/// Shuts down the programs running in a compat data directory, killing them with `--force`
+fn kill_command(args: Vec<String>) -> Result<(), Error> {
+    use jargon_args::Jargon;
+
+    let config: Config = open_config()?;
+    let mut parser: Jargon = Jargon::from_vec(args);
+    let force: bool = parser.contains("--force");
+    let version: Option<String> = parser.option_arg(["-p", "--proton"]);
+    let prefix: Option<String> = parser.option_arg(["-P", "--prefix"]);
+
+    let proton: Proton = match prefix {
+        Some(name) => {
+            let prefix: Prefix = game_prefix(&config, &name)?;
+            let version: Option<Version> = version.map(|v| v.parse()).transpose()?;
+            prefix_proton(&config, &prefix, version, "wineserver", &[])?
+        }
+        None => Proton::from_config(
+            &config,
+            PathBuf::from("wineserver"),
+            Vec::new(),
+            version.as_deref(),
+        )?,
+    };
+
+    let killed: usize = proton.kill(force)?;
+    if force {
+        println!("Killed {} processes left running in the prefix", killed);
+    }
+
+    pass!()
+}
+
+/// Finds the prefix for a program in the config, or a prefix by name
+fn game_prefix(config: &Config, name: &str) -> Result<Prefix, Error> {
+    match config.game(name).and_then(Game::prefix) {
+        Some(prefix) => Prefix::find(&config.data(), prefix),
+        None => Prefix::find(&config.data(), name),
+    }
+}
+
+/// Builds a `Proton` running a Wine built-in inside `prefix`, using the version which last used it
+fn prefix_proton(
+    config: &Config,
+    prefix: &Prefix,
+    version: Option<Version>,
+    program: &str,
+    args: &[&str],
+) -> Result<Proton, Error> {
+    let version: Version = match version.or_else(|| prefix.version()) {
+        Some(v) => v,
+        None => throw!(
+            Kind::ProtonMissing,
+            "can not tell which Proton created '{}', use -p",
+            prefix.name()
+        ),
+    };
+
+    let common_index: Index = Index::from_config(config)?;
+
+    let proton_path: PathBuf = match common_index.get(version) {
+        Some(pp) => pp,
+        None => throw!(Kind::ProtonMissing, "Proton {} does not exist", version),
+    };
+
+    let proton: Proton = ProtonBuilder::new()
+        .config(config)
+        .version(version)
+        .path(proton_path)
+        .program(PathBuf::from(program))
+        .args(args.iter().map(ToString::to_string).collect())
+        .build()?;
+
+    pass!(proton.builtin().prefix(prefix.path().to_path_buf()))
+}
+
+#[doc(hidden)]
+fn help() {
+    println!("{}", Overview);
+}
+
+/// Runs the `help` subcommand, viewing the usage, examples and config keys of a command
+fn help_command(mut args: Vec<String>) -> Result<(), Error> {
+    let Some(name) = subcommand(&mut args) else {
+        help();
+        return pass!();
+    };
+
+    let Some(command) = Command::find(&name) else {
+        throw!(Kind::UnknownCommand, "'{}'", name);
+    };
+
+    println!("{}", command);
+    pass!()
 }
 
 #[doc(hidden)]
@@ -0,0 +1,205 @@
+use crate::error::{Error, Kind};
+use crate::util::{in_path, xdg_base};
+use crate::{pass, throw, Prefix};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Files winemenubuilder wrote to the host for a prefix: menu entries, the file associations
+/// among them, and their icons and MIME types
+#[derive(Debug, Clone, Default)]
+pub struct MenuEntries {
+    files: Vec<PathBuf>,
+    associations: Vec<String>,
+}
+
+impl MenuEntries {
+    #[must_use]
+    /// Finds the menu entries, icons and MIME types Wine created on the host for `prefix`, by
+    /// the `WINEPREFIX` their entries launch with
+    pub fn find(prefix: &Prefix) -> MenuEntries {
+        let mut pfx: Vec<String> = vec![prefix.pfx().to_string_lossy().to_string()];
+        if let Ok(canonical) = prefix.pfx().canonicalize() {
+            pfx.push(canonical.to_string_lossy().to_string());
+        }
+
+        let mut entries: MenuEntries = MenuEntries::default();
+        let Some(data) = xdg_base("XDG_DATA_HOME", ".local/share") else {
+            return entries;
+        };
+
+        for desktop in desktop_files(&data.join("applications")) {
+            let Ok(text) = std::fs::read_to_string(&desktop) else {
+                continue;
+            };
+
+            // Wine writes `Exec=env WINEPREFIX="<pfx>" wine ...`, Proton's prefix with a slash
+            let launches: bool = pfx.iter().any(|pfx| {
+                text.contains(&format!("WINEPREFIX=\"{}\"", pfx))
+                    || text.contains(&format!("WINEPREFIX=\"{}/\"", pfx))
+            });
+            if !launches {
+                continue;
+            }
+
+            if let Some(icon) = text.lines().find_map(|line| line.strip_prefix("Icon=")) {
+                entries
+                    .files
+                    .extend(icons(&data.join("icons"), icon.trim()));
+            }
+
+            let name: String = desktop
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string();
+
+            // File associations are named after their extension, with a MIME type of their own
+            if let Some(extension) = name
+                .strip_prefix("wine-extension-")
+                .and_then(|n| n.strip_suffix(".desktop"))
+            {
+                let mime: PathBuf = data
+                    .join("mime/packages")
+                    .join(format!("x-wine-extension-{}.xml", extension));
+                if mime.is_file() {
+                    entries.files.push(mime);
+                }
+                entries.associations.push(name);
+            }
+
+            entries.files.push(desktop);
+        }
+
+        entries.files.sort();
+        entries.files.dedup();
+        entries
+    }
+
+    #[must_use]
+    /// Returns every file found
+    pub fn files(&self) -> &[PathBuf] {
+        &self.files
+    }
+
+    #[must_use]
+    /// Tells whether Wine created nothing for the prefix
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Removes the files found and the prefix's file associations from `mimeapps.list`, then
+    /// updates the host's desktop and MIME databases
+    ///
+    /// # Errors
+    ///
+    /// Will fail if a file or `mimeapps.list` can not be removed or written
+    pub fn remove(&self) -> Result<(), Error> {
+        for file in &self.files {
+            if let Err(e) = std::fs::remove_file(file) {
+                throw!(Kind::PrefixWrite, "{}: {}", file.to_string_lossy(), e);
+            }
+        }
+
+        let lists: Vec<PathBuf> = [
+            xdg_base("XDG_CONFIG_HOME", ".config"),
+            xdg_base("XDG_DATA_HOME", ".local/share").map(|data| data.join("applications")),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|dir| dir.join("mimeapps.list"))
+        .collect();
+
+        for list in lists {
+            self.drop_associations(&list)?;
+        }
+
+        if let Some(data) = xdg_base("XDG_DATA_HOME", ".local/share") {
+            // The databases are only caches, stale ones are harmless
+            if in_path("update-desktop-database") {
+                let _ = Command::new("update-desktop-database")
+                    .arg(data.join("applications"))
+                    .status();
+            }
+
+            if in_path("update-mime-database") {
+                let _ = Command::new("update-mime-database")
+                    .arg(data.join("mime"))
+                    .status();
+            }
+        }
+
+        pass!()
+    }
+
+    /// Removes the prefix's file associations from the `mimeapps.list` at `list`
+    fn drop_associations(&self, list: &Path) -> Result<(), Error> {
+        if self.associations.is_empty() {
+            return pass!();
+        }
+
+        let Ok(text) = std::fs::read_to_string(list) else {
+            return pass!();
+        };
+
+        let mut lines: Vec<String> = Vec::new();
+        for line in text.lines() {
+            let Some((mime, apps)) = line.split_once('=') else {
+                lines.push(line.to_string());
+                continue;
+            };
+
+            let listed: Vec<&str> = apps.split(';').filter(|app| !app.is_empty()).collect();
+            let kept: Vec<&str> = listed
+                .iter()
+                .copied()
+                .filter(|app| !self.associations.iter().any(|a| a == app))
+                .collect();
+
+            if kept.len() == listed.len() {
+                lines.push(line.to_string());
+            } else if !kept.is_empty() {
+                lines.push(format!("{}={};", mime, kept.join(";")));
+            }
+        }
+
+        let mut edited: String = lines.join("\n");
+        edited.push('\n');
+
+        if edited != text {
+            if let Err(e) = std::fs::write(list, edited) {
+                throw!(Kind::PrefixWrite, "{}: {}", list.to_string_lossy(), e);
+            }
+        }
+
+        pass!()
+    }
+}
+
+/// Lists the `.desktop` files in `dir` and its subdirectories
+fn desktop_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = Vec::new();
+
+    for entry in dir.read_dir().into_iter().flatten().flatten() {
+        let path: PathBuf = entry.path();
+        if path.is_dir() {
+            files.extend(desktop_files(&path));
+        } else if path.extension().is_some_and(|e| e == "desktop") {
+            files.push(path);
+        }
+    }
+
+    files
+}
+
+/// Finds the sizes of the icon `name` in the `hicolor` theme below `icons`
+fn icons(icons: &Path, name: &str) -> Vec<PathBuf> {
+    icons
+        .join("hicolor")
+        .read_dir()
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|size| size.path().join("apps").join(format!("{}.png", name)))
+        .filter(|icon| icon.is_file())
+        .collect()
+}
@@ -0,0 +1,238 @@
+use crate::error::{Error, Kind};
+use crate::util::{format_time, state_dir};
+use crate::{pass, throw, RunOutcome};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// A finished launch, one line of the run log
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct RunRecord {
+    program: PathBuf,
+    version: String,
+    started: u64,
+    duration: f64,
+    code: Option<i32>,
+    signal: Option<i32>,
+    spawn: f64,
+    first_output: Option<f64>,
+}
+
+impl RunRecord {
+    #[must_use]
+    /// Creates a record of `program` having run with `outcome`, which just finished
+    pub fn new(program: &Path, outcome: &RunOutcome) -> RunRecord {
+        let program: PathBuf = program
+            .canonicalize()
+            .unwrap_or_else(|_| program.to_path_buf());
+        let started: u64 = SystemTime::now()
+            .checked_sub(outcome.duration())
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+
+        RunRecord {
+            program,
+            version: outcome.version().to_string(),
+            started,
+            duration: outcome.duration().as_secs_f64(),
+            code: outcome.code(),
+            signal: outcome.signal(),
+            spawn: outcome.timing().spawn().as_secs_f64(),
+            first_output: outcome.timing().first_output().map(|f| f.as_secs_f64()),
+        }
+    }
+
+    #[must_use]
+    /// Returns the program which ran
+    pub fn program(&self) -> &Path {
+        &self.program
+    }
+
+    #[must_use]
+    /// Returns the Proton version, or Wine runner name, the program ran with
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    #[must_use]
+    /// Returns when the launch started
+    pub fn started(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(self.started)
+    }
+
+    #[must_use]
+    /// Returns how long the launch ran for
+    pub fn duration(&self) -> Duration {
+        Duration::from_secs_f64(self.duration.max(0.0))
+    }
+
+    #[must_use]
+    /// Returns true if the program exited successfully
+    pub fn success(&self) -> bool {
+        self.code == Some(0)
+    }
+
+    /// Describes how the program exited
+    fn exit(&self) -> String {
+        match (self.code, self.signal) {
+            (Some(code), _) => format!("code {}", code),
+            (None, Some(signal)) => format!("signal {}", signal),
+            (None, None) => String::from("unknown"),
+        }
+    }
+}
+
+/// The log of finished launches, kept offline as JSON lines in proton-call's state directory
+#[derive(Debug, Clone, Default)]
+pub struct Metrics {
+    runs: Vec<RunRecord>,
+}
+
+impl Metrics {
+    /// Reads the run log, skipping lines which can not be parsed
+    ///
+    /// # Errors
+    ///
+    /// Will fail if the state directory can not be found or created
+    pub fn open() -> Result<Metrics, Error> {
+        let text: String = std::fs::read_to_string(Metrics::location()?).unwrap_or_default();
+
+        pass!(Metrics {
+            runs: text
+                .lines()
+                .filter_map(|l| serde_json::from_str(l).ok())
+                .collect(),
+        })
+    }
+
+    /// Appends `record` to the run log
+    ///
+    /// # Errors
+    ///
+    /// Will fail if the run log can not be written
+    pub fn record(record: &RunRecord) -> Result<(), Error> {
+        use std::fs::OpenOptions;
+        use std::io::Write;
+
+        let line: String = match serde_json::to_string(record) {
+            Ok(l) => l,
+            Err(e) => throw!(Kind::Environment, "{}", e),
+        };
+
+        let location: PathBuf = Metrics::location()?;
+        let result = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&location)
+            .and_then(|mut f| writeln!(f, "{}", line));
+
+        if let Err(e) = result {
+            throw!(Kind::Environment, "{}: {}", location.to_string_lossy(), e);
+        }
+
+        pass!()
+    }
+
+    #[must_use]
+    /// Returns every recorded launch, oldest first
+    pub fn runs(&self) -> &[RunRecord] {
+        &self.runs
+    }
+
+    /// Keeps only the newest `n` launches
+    pub fn truncate(&mut self, n: usize) {
+        let skip: usize = self.runs.len().saturating_sub(n);
+        self.runs.drain(..skip);
+    }
+
+    #[must_use]
+    /// Returns true if nothing has been recorded yet
+    pub fn is_empty(&self) -> bool {
+        self.runs.is_empty()
+    }
+
+    #[must_use]
+    /// Totals the recorded launches of each program
+    pub fn stats(&self) -> Stats {
+        let mut programs: BTreeMap<&Path, Totals> = BTreeMap::new();
+
+        for run in &self.runs {
+            let totals: &mut Totals = programs.entry(run.program()).or_default();
+            totals.runs += 1;
+            totals.failures += usize::from(!run.success());
+            totals.time += run.duration();
+            totals.last.clone_from(&run.version);
+        }
+
+        Stats {
+            programs: programs
+                .into_iter()
+                .map(|(p, t)| (p.to_path_buf(), t))
+                .collect(),
+        }
+    }
+
+    /// Returns the path to the run log
+    fn location() -> Result<PathBuf, Error> {
+        pass!(state_dir("")?.join("runs.jsonl"))
+    }
+}
+
+impl Display for Metrics {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Recorded launches:")?;
+
+        for run in &self.runs {
+            write!(
+                f,
+                "\n{}  {:>9.1}s  {:<10}  {:<12}  {}",
+                format_time(run.started()),
+                run.duration,
+                run.exit(),
+                run.version,
+                run.program.to_string_lossy()
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Launch totals of one program
+#[derive(Debug, Clone, Default)]
+struct Totals {
+    runs: usize,
+    failures: usize,
+    time: Duration,
+    last: String,
+}
+
+/// Launch totals of every program in the run log
+#[derive(Debug, Clone, Default)]
+pub struct Stats {
+    programs: Vec<(PathBuf, Totals)>,
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Launch statistics:")?;
+
+        for (program, totals) in &self.programs {
+            let hours: f64 = totals.time.as_secs_f64() / 3600.0;
+
+            write!(
+                f,
+                "\n{:>5} runs  {:>4} failed  {:>8.1}h  last {:<12}  {}",
+                totals.runs,
+                totals.failures,
+                hours,
+                totals.last,
+                program.to_string_lossy()
+            )?;
+        }
+
+        Ok(())
+    }
+}
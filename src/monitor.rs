@@ -0,0 +1,331 @@
+use crate::util::format_size;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// Clock ticks per second `/proc` counts CPU time in, which Linux fixes at 100
+const TICKS: f64 = 100.0;
+
+/// Peak and average resource use of the processes a launch ran
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Usage {
+    samples: u32,
+    cpu_total: f64,
+    cpu_peak: f64,
+    rss_peak: u64,
+    gpu_peak: Option<u64>,
+}
+
+impl Usage {
+    #[must_use]
+    /// Returns the average CPU use over the launch, in percent of one core
+    pub fn cpu_average(&self) -> f64 {
+        if self.samples == 0 {
+            return 0.0;
+        }
+
+        self.cpu_total / f64::from(self.samples)
+    }
+
+    #[must_use]
+    /// Returns the highest CPU use sampled, in percent of one core
+    pub fn cpu_peak(&self) -> f64 {
+        self.cpu_peak
+    }
+
+    #[must_use]
+    /// Returns the highest resident memory sampled, in bytes
+    pub fn rss_peak(&self) -> u64 {
+        self.rss_peak
+    }
+
+    #[must_use]
+    /// Returns the highest GPU memory sampled, in bytes, `None` if the driver does not report it
+    pub fn gpu_peak(&self) -> Option<u64> {
+        self.gpu_peak
+    }
+
+    /// Adds a sample to the totals and peaks
+    fn add(&mut self, cpu: f64, rss: u64, gpu: Option<u64>) {
+        self.samples += 1;
+        self.cpu_total += cpu;
+        self.cpu_peak = self.cpu_peak.max(cpu);
+        self.rss_peak = self.rss_peak.max(rss);
+
+        if let Some(gpu) = gpu {
+            self.gpu_peak = Some(self.gpu_peak.unwrap_or_default().max(gpu));
+        }
+    }
+}
+
+impl Display for Usage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "CPU:                  {:.0}% average, {:.0}% peak, of one core",
+            self.cpu_average(),
+            self.cpu_peak
+        )?;
+        writeln!(
+            f,
+            "Memory:               {} peak resident",
+            format_size(self.rss_peak)
+        )?;
+
+        match self.gpu_peak {
+            Some(gpu) => write!(f, "GPU memory:           {} peak", format_size(gpu)),
+            None => write!(f, "GPU memory:           not reported by the driver"),
+        }
+    }
+}
+
+/// Samples the resource use of every process proton-call starts on a thread, until stopped
+pub(crate) struct Monitor {
+    stop: Arc<AtomicBool>,
+    handle: JoinHandle<Usage>,
+}
+
+impl Monitor {
+    #[must_use]
+    /// Starts sampling every `interval`
+    pub(crate) fn start(interval: Duration) -> Monitor {
+        let stop: Arc<AtomicBool> = Arc::new(AtomicBool::new(false));
+        let stopped: Arc<AtomicBool> = stop.clone();
+
+        let handle: JoinHandle<Usage> = std::thread::spawn(move || {
+            let mut sampler: Sampler = Sampler::new(std::process::id());
+            let mut usage: Usage = Usage::default();
+
+            loop {
+                std::thread::park_timeout(interval);
+                if stopped.load(Ordering::Relaxed) {
+                    return usage;
+                }
+
+                let (cpu, rss, gpu) = sampler.sample();
+                usage.add(cpu, rss, gpu);
+            }
+        });
+
+        Monitor { stop, handle }
+    }
+
+    #[must_use]
+    /// Stops sampling and returns what was sampled
+    pub(crate) fn stop(self) -> Usage {
+        self.stop.store(true, Ordering::Relaxed);
+        self.handle.thread().unpark();
+        self.handle.join().unwrap_or_default()
+    }
+}
+
+/// One process read from `/proc`
+struct Process {
+    pid: u32,
+    parent: u32,
+    ticks: u64,
+}
+
+/// Follows the processes below a root process between samples, keeping those which outlive
+/// their parent
+struct Sampler {
+    root: u32,
+    tracked: HashSet<u32>,
+    ticks: HashMap<u32, u64>,
+    last: Instant,
+    nvidia: bool,
+}
+
+impl Sampler {
+    /// Creates a new `Sampler` following the processes below `root`
+    fn new(root: u32) -> Sampler {
+        Sampler {
+            root,
+            tracked: HashSet::from([root]),
+            ticks: HashMap::new(),
+            last: Instant::now(),
+            nvidia: Command::new("nvidia-smi").arg("-L").output().is_ok(),
+        }
+    }
+
+    /// Samples the CPU use in percent of one core since the last sample, and the resident and
+    /// GPU memory in bytes, of the processes followed
+    fn sample(&mut self) -> (f64, u64, Option<u64>) {
+        let processes: Vec<Process> = processes();
+        let alive: HashSet<u32> = processes.iter().map(|p| p.pid).collect();
+        self.tracked.retain(|pid| alive.contains(pid));
+
+        // Children are listed before their parents at times, so this runs until nothing is added
+        loop {
+            let found: Vec<u32> = processes
+                .iter()
+                .filter(|p| self.tracked.contains(&p.parent) && !self.tracked.contains(&p.pid))
+                .map(|p| p.pid)
+                .collect();
+
+            if found.is_empty() {
+                break;
+            }
+
+            self.tracked.extend(found);
+        }
+
+        let followed: Vec<&Process> = processes
+            .iter()
+            .filter(|p| p.pid != self.root && self.tracked.contains(&p.pid))
+            .collect();
+
+        let mut ticks: HashMap<u32, u64> = HashMap::new();
+        let mut spent: u64 = 0;
+        for process in &followed {
+            let before: u64 = self.ticks.get(&process.pid).copied().unwrap_or_default();
+            spent += process.ticks.saturating_sub(before);
+            ticks.insert(process.pid, process.ticks);
+        }
+        self.ticks = ticks;
+
+        let elapsed: f64 = self.last.elapsed().as_secs_f64().max(f64::EPSILON);
+        self.last = Instant::now();
+
+        #[allow(clippy::cast_precision_loss)]
+        let cpu: f64 = spent as f64 / TICKS / elapsed * 100.0;
+        let rss: u64 = followed.iter().map(|p| resident(p.pid)).sum();
+        let pids: Vec<u32> = followed.iter().map(|p| p.pid).collect();
+
+        (cpu, rss, self.gpu(&pids))
+    }
+
+    /// Sums the GPU memory of `pids`, from `nvidia-smi` on NVIDIA and the DRM file descriptor
+    /// info of other drivers
+    fn gpu(&self, pids: &[u32]) -> Option<u64> {
+        let drm: Option<u64> = drm_memory(pids);
+
+        if !self.nvidia {
+            return drm;
+        }
+
+        let nvidia: Option<u64> = nvidia_memory(pids);
+        match (drm, nvidia) {
+            (Some(drm), Some(nvidia)) => Some(drm + nvidia),
+            (drm, nvidia) => drm.or(nvidia),
+        }
+    }
+}
+
+/// Lists every process with its parent and the CPU time it spent, from `/proc/<pid>/stat`
+fn processes() -> Vec<Process> {
+    let Ok(entries) = std::fs::read_dir("/proc") else {
+        return Vec::new();
+    };
+
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let pid: u32 = entry.file_name().to_str()?.parse().ok()?;
+            let stat: String = std::fs::read_to_string(entry.path().join("stat")).ok()?;
+
+            // The name in parentheses may hold spaces, the fields after it are numbered from
+            // the state, so the parent is 1 and user and system time are 11 and 12
+            let fields: Vec<&str> = stat[stat.rfind(')')? + 1..].split_whitespace().collect();
+            let parent: u32 = fields.get(1)?.parse().ok()?;
+            let user: u64 = fields.get(11)?.parse().ok()?;
+            let system: u64 = fields.get(12)?.parse().ok()?;
+
+            Some(Process {
+                pid,
+                parent,
+                ticks: user + system,
+            })
+        })
+        .collect()
+}
+
+/// Reads the resident memory of a process in bytes, from `VmRSS` in `/proc/<pid>/status`
+fn resident(pid: u32) -> u64 {
+    std::fs::read_to_string(format!("/proc/{}/status", pid))
+        .ok()
+        .and_then(|status| {
+            status
+                .lines()
+                .find_map(|line| line.strip_prefix("VmRSS:"))
+                .and_then(|value| value.trim().trim_end_matches("kB").trim().parse().ok())
+        })
+        .map_or(0, |kib: u64| kib * 1024)
+}
+
+/// Sums the VRAM the DRM clients opened by `pids` hold, from `/proc/<pid>/fdinfo`, counting a
+/// client shared between descriptors once
+fn drm_memory(pids: &[u32]) -> Option<u64> {
+    let mut clients: HashMap<String, u64> = HashMap::new();
+
+    for pid in pids {
+        let Ok(entries) = std::fs::read_dir(format!("/proc/{}/fdinfo", pid)) else {
+            continue;
+        };
+
+        for entry in entries.flatten() {
+            let Ok(info) = std::fs::read_to_string(entry.path()) else {
+                continue;
+            };
+
+            let mut client: Option<String> = None;
+            let mut vram: Option<u64> = None;
+
+            for line in info.lines() {
+                let Some((key, value)) = line.split_once(':') else {
+                    continue;
+                };
+
+                match key {
+                    "drm-client-id" => client = Some(value.trim().to_string()),
+                    "drm-memory-vram" | "drm-total-vram" => vram = kib(value),
+                    _ => (),
+                }
+            }
+
+            if let (Some(client), Some(vram)) = (client, vram) {
+                clients.insert(client, vram);
+            }
+        }
+    }
+
+    if clients.is_empty() {
+        return None;
+    }
+
+    Some(clients.values().sum())
+}
+
+/// Parses a `N KiB` size of DRM fdinfo into bytes
+fn kib(value: &str) -> Option<u64> {
+    let kib: u64 = value.trim().trim_end_matches("KiB").trim().parse().ok()?;
+    Some(kib * 1024)
+}
+
+/// Sums the GPU memory `nvidia-smi` lists for `pids`, from the process table of its plain output
+fn nvidia_memory(pids: &[u32]) -> Option<u64> {
+    let output = Command::new("nvidia-smi").output().ok()?;
+    let output: String = String::from_utf8_lossy(&output.stdout).to_string();
+
+    // Rows of the process table read `| GPU GI CI PID Type Name Memory |`
+    let used: u64 = output
+        .lines()
+        .filter_map(|line| {
+            let cells: Vec<&str> = line.split_whitespace().collect();
+            let pid: u32 = cells.get(4)?.parse().ok()?;
+            let memory: u64 = cells
+                .get(cells.len().checked_sub(2)?)?
+                .strip_suffix("MiB")?
+                .parse()
+                .ok()?;
+
+            pids.contains(&pid).then_some(memory * 1024 * 1024)
+        })
+        .sum();
+
+    Some(used)
+}
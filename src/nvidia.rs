@@ -0,0 +1,52 @@
+/// NVIDIA options deciding whether games see NVAPI, and so offer DLSS, from the config and flags
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Nvidia {
+    nvapi: Option<bool>,
+    dlss_indicator: bool,
+}
+
+impl Nvidia {
+    #[must_use]
+    /// Exposes NVAPI and the NVIDIA GPU to games, which DLSS needs, or hides both so games fall
+    /// back to other upscalers, instead of leaving it to Proton's defaults
+    pub fn nvapi(mut self, enable: bool) -> Nvidia {
+        self.nvapi = Some(enable);
+        self
+    }
+
+    #[must_use]
+    /// Shows DLSS' on-screen indicator, telling whether DLSS and frame generation are running
+    pub fn dlss_indicator(mut self, enable: bool) -> Nvidia {
+        self.dlss_indicator = enable;
+        self
+    }
+
+    #[must_use]
+    /// Returns the variables setting the options
+    pub fn env(&self) -> Vec<(&'static str, String)> {
+        let mut env: Vec<(&'static str, String)> = Vec::new();
+
+        if let Some(enable) = self.nvapi {
+            env.push(("PROTON_ENABLE_NVAPI", String::from(flag(enable))));
+            env.push(("PROTON_HIDE_NVIDIA_GPU", String::from(flag(!enable))));
+        }
+
+        if self.dlss_indicator {
+            env.push((
+                "DXVK_NVAPI_SET_NGX_DEBUG_OPTIONS",
+                String::from("DLSSIndicator=1024,DLSSGIndicator=2"),
+            ));
+        }
+
+        env
+    }
+}
+
+/// Returns the value Proton's switches take for `enable`
+fn flag(enable: bool) -> &'static str {
+    if enable {
+        "1"
+    } else {
+        "0"
+    }
+}
@@ -0,0 +1,179 @@
+use crate::{Timing, Usage};
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::process::ExitStatus;
+use std::time::Duration;
+
+/// What a finished launch returned, and how it was run
+#[derive(Debug, Clone)]
+pub struct RunOutcome {
+    status: ExitStatus,
+    timing: Timing,
+    compat: PathBuf,
+    proton: PathBuf,
+    version: String,
+    usage: Option<Usage>,
+    output: Option<(String, String)>,
+    command_line: Vec<String>,
+    log: Option<PathBuf>,
+}
+
+impl RunOutcome {
+    #[must_use]
+    /// Creates a new instance of `RunOutcome`
+    pub(crate) fn new(
+        status: ExitStatus,
+        timing: Timing,
+        compat: PathBuf,
+        proton: PathBuf,
+        version: String,
+    ) -> RunOutcome {
+        RunOutcome {
+            status,
+            timing,
+            compat,
+            proton,
+            version,
+            usage: None,
+            output: None,
+            command_line: Vec::new(),
+            log: None,
+        }
+    }
+
+    #[must_use]
+    /// Adds the resource use sampled during the launch
+    pub(crate) fn with_usage(mut self, usage: Option<Usage>) -> RunOutcome {
+        self.usage = usage;
+        self
+    }
+
+    #[must_use]
+    /// Adds the command line the launch ran and the file its Proton log was written to
+    pub(crate) fn with_launch(
+        mut self,
+        command_line: Vec<String>,
+        log: Option<PathBuf>,
+    ) -> RunOutcome {
+        self.command_line = command_line;
+        self.log = log;
+        self
+    }
+
+    #[must_use]
+    /// Adds the standard output and error captured by `Proton::run_captured`
+    pub(crate) fn with_output(mut self, stdout: String, stderr: String) -> RunOutcome {
+        self.output = Some((stdout, stderr));
+        self
+    }
+
+    #[must_use]
+    /// Returns the raw exit status of the launch
+    pub fn status(&self) -> ExitStatus {
+        self.status
+    }
+
+    #[must_use]
+    /// Returns true if the launch exited successfully
+    pub fn success(&self) -> bool {
+        self.status.success()
+    }
+
+    #[must_use]
+    /// Returns the exit code, or `None` if the launch was killed by a signal
+    pub fn code(&self) -> Option<i32> {
+        self.status.code()
+    }
+
+    #[must_use]
+    /// Returns the signal which killed the launch, if any
+    pub fn signal(&self) -> Option<i32> {
+        use std::os::unix::process::ExitStatusExt;
+
+        self.status.signal()
+    }
+
+    #[must_use]
+    /// Tells whether the launch was shut down by its time limit, which a precache always is,
+    /// so its exit status reports the shutdown rather than a failure
+    pub fn time_limit_reached(&self) -> bool {
+        self.timing.time_limit_reached()
+    }
+
+    #[must_use]
+    /// Returns how long the launch ran for, not counting save sync hooks
+    pub fn duration(&self) -> Duration {
+        self.timing.total()
+    }
+
+    #[must_use]
+    /// Returns how long the launch took to reach each of its milestones
+    pub fn timing(&self) -> Timing {
+        self.timing
+    }
+
+    #[must_use]
+    /// Returns the resource use sampled during the launch, if it was monitored
+    pub fn usage(&self) -> Option<Usage> {
+        self.usage
+    }
+
+    #[must_use]
+    /// Returns the program's standard output, if the launch was run by `Proton::run_captured`
+    pub fn stdout(&self) -> Option<&str> {
+        self.output.as_ref().map(|(stdout, _)| stdout.as_str())
+    }
+
+    #[must_use]
+    /// Returns the program's standard error, if the launch was run by `Proton::run_captured`
+    pub fn stderr(&self) -> Option<&str> {
+        self.output.as_ref().map(|(_, stderr)| stderr.as_str())
+    }
+
+    #[must_use]
+    /// Returns the program and arguments the launch ran, through Proton and any wrappers
+    pub fn command_line(&self) -> &[String] {
+        &self.command_line
+    }
+
+    #[must_use]
+    /// Returns the file the Proton log was written to, if the launch wrote one
+    pub fn log(&self) -> Option<&Path> {
+        self.log.as_deref()
+    }
+
+    #[must_use]
+    /// Returns the compat data directory the launch ran in
+    pub fn compat(&self) -> &Path {
+        &self.compat
+    }
+
+    #[must_use]
+    /// Returns the Proton script, or Wine binary, the launch ran
+    pub fn proton(&self) -> &Path {
+        &self.proton
+    }
+
+    #[must_use]
+    /// Returns the Proton version, or Wine runner name, the launch ran
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+}
+
+impl Display for RunOutcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match (self.code(), self.signal()) {
+            (Some(code), _) => write!(f, "exited with code {}", code)?,
+            (None, Some(signal)) => write!(f, "killed by signal {}", signal)?,
+            (None, None) => write!(f, "exited")?,
+        }
+
+        write!(f, " after {:.1}s", self.duration().as_secs_f64())?;
+
+        match &self.log {
+            Some(log) => write!(f, ", Proton log in {}", log.to_string_lossy()),
+            None => Ok(()),
+        }
+    }
+}
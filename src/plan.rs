@@ -0,0 +1,127 @@
+use crate::error::{Error, Kind};
+use crate::{pass, throw};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus};
+
+/// Everything a launch resolved to, as plain data: the command line, its environment and
+/// directories, for other tools to run the launch their own way, such as in a container or on
+/// another machine
+///
+/// A plan runs the launch as is, without the save sync hooks, retries, waiting, monitoring or
+/// prefix bookkeeping `Proton::run` adds around it.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ExecutionPlan {
+    program: PathBuf,
+    argv: Vec<String>,
+    env: BTreeMap<String, String>,
+    cwd: Option<PathBuf>,
+    compat: PathBuf,
+}
+
+impl ExecutionPlan {
+    #[must_use]
+    /// Creates a new `ExecutionPlan` running `argv`, its first item the executable, with
+    /// exactly `env`
+    pub fn new(
+        program: PathBuf,
+        argv: Vec<String>,
+        env: BTreeMap<String, String>,
+        cwd: Option<PathBuf>,
+        compat: PathBuf,
+    ) -> ExecutionPlan {
+        ExecutionPlan {
+            program,
+            argv,
+            env,
+            cwd,
+            compat,
+        }
+    }
+
+    #[must_use]
+    /// Returns the program the launch runs, as given to proton-call
+    pub fn program(&self) -> &Path {
+        &self.program
+    }
+
+    #[must_use]
+    /// Returns the command line, the executable first
+    pub fn argv(&self) -> &[String] {
+        &self.argv
+    }
+
+    #[must_use]
+    /// Returns the complete environment, nothing else is inherited
+    pub fn env(&self) -> &BTreeMap<String, String> {
+        &self.env
+    }
+
+    #[must_use]
+    /// Returns the directory to run in, `None` to keep the caller's
+    pub fn cwd(&self) -> Option<&Path> {
+        self.cwd.as_deref()
+    }
+
+    #[must_use]
+    /// Returns the compat data directory holding the Wine prefix
+    pub fn compat(&self) -> &Path {
+        &self.compat
+    }
+
+    /// Serializes the plan to JSON
+    ///
+    /// # Errors
+    ///
+    /// Will fail if the plan can not be serialized
+    pub fn to_json(&self) -> Result<String, Error> {
+        match serde_json::to_string_pretty(self) {
+            Ok(json) => pass!(json),
+            Err(e) => throw!(Kind::Internal, "failed to serialize the plan: {}", e),
+        }
+    }
+
+    /// Reads a plan serialized with `to_json`
+    ///
+    /// # Errors
+    ///
+    /// Will fail if `json` is not a plan
+    pub fn from_json(json: &str) -> Result<ExecutionPlan, Error> {
+        match serde_json::from_str(json) {
+            Ok(plan) => pass!(plan),
+            Err(e) => throw!(Kind::ArgumentParse, "plan: {}", e),
+        }
+    }
+
+    /// Builds the command the plan runs
+    ///
+    /// # Errors
+    ///
+    /// Will fail if the command line is empty
+    pub fn command(&self) -> Result<Command, Error> {
+        let Some((executable, args)) = self.argv.split_first() else {
+            throw!(Kind::ArgumentMissing, "a command line in the plan");
+        };
+
+        let mut command: Command = Command::new(executable);
+        command.args(args).env_clear().envs(&self.env);
+
+        if let Some(cwd) = &self.cwd {
+            command.current_dir(cwd);
+        }
+
+        pass!(command)
+    }
+
+    /// Runs the plan and waits for it
+    ///
+    /// # Errors
+    ///
+    /// Will fail if the command line is empty or the command can not be spawned
+    pub fn run(&self) -> Result<ExitStatus, Error> {
+        match self.command()?.status() {
+            Ok(status) => pass!(status),
+            Err(e) => throw!(Kind::ProtonSpawn, "{}: {}", self.argv[0], e),
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use crate::error::{Error, Kind};
+use crate::{pass, throw, Version, Warnings};
+use std::fmt::{Display, Formatter};
+
+/// What to do about a launch which may break a prefix
+#[derive(Debug, Copy, Clone, Eq, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Policy {
+    /// Launch without a word
+    Allow,
+    /// Launch, and warn about it
+    Warn,
+    /// Refuse to launch, unless forced
+    Block,
+}
+
+impl Display for Policy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Policy::Allow => write!(f, "allow"),
+            Policy::Warn => write!(f, "warn"),
+            Policy::Block => write!(f, "block"),
+        }
+    }
+}
+
+/// Policies for launching a Proton version into a prefix another version last used
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct CompatPolicy {
+    downgrade: Policy,
+    major_jump: Policy,
+}
+
+impl Default for CompatPolicy {
+    fn default() -> Self {
+        CompatPolicy {
+            downgrade: Policy::Warn,
+            major_jump: Policy::Allow,
+        }
+    }
+}
+
+impl CompatPolicy {
+    #[must_use]
+    /// Creates a new `CompatPolicy`
+    pub fn new(downgrade: Policy, major_jump: Policy) -> CompatPolicy {
+        CompatPolicy {
+            downgrade,
+            major_jump,
+        }
+    }
+
+    #[must_use]
+    /// Returns the policy for launching an older version than the prefix last ran
+    pub fn downgrade(&self) -> Policy {
+        self.downgrade
+    }
+
+    #[must_use]
+    /// Returns the policy for launching a newer major version than the prefix last ran
+    pub fn major_jump(&self) -> Policy {
+        self.major_jump
+    }
+
+    /// Checks launching `next` into the prefix `name` which `last` last ran, a block only warns
+    /// when `force` is set
+    ///
+    /// Mainline and GE-Proton builds are compared by their major number when `last` and `next`
+    /// are of different kinds, so GE-Proton8 after Proton 9.0 is a downgrade.
+    ///
+    /// # Errors
+    ///
+    /// Will fail if the launch is blocked and not forced
+    pub fn check(
+        &self,
+        name: &str,
+        last: Version,
+        next: Version,
+        force: bool,
+    ) -> Result<(), Error> {
+        let (Some(last_major), Some(next_major)) = (major(last), major(next)) else {
+            return pass!();
+        };
+
+        // Derived ordering only holds between versions of the same kind
+        let older: bool = if std::mem::discriminant(&last) == std::mem::discriminant(&next) {
+            next < last
+        } else {
+            next_major < last_major
+        };
+
+        let (policy, problem): (Policy, &str) = if older {
+            (self.downgrade, "launching the older")
+        } else if next_major > last_major {
+            (self.major_jump, "launching the next major version")
+        } else {
+            return pass!();
+        };
+
+        let message: String = format!(
+            "'{}' was last used by Proton {}, {} {} into it may break it",
+            name, last, problem, next
+        );
+
+        match policy {
+            Policy::Allow => pass!(),
+            Policy::Block if !force => throw!(
+                Kind::PrefixMismatch,
+                "{}, use --force to launch anyway",
+                message
+            ),
+            Policy::Warn | Policy::Block => {
+                Warnings::push("launch", message);
+                pass!()
+            }
+        }
+    }
+}
+
+/// Returns the major number of a mainline or GE-Proton version
+fn major(version: Version) -> Option<u8> {
+    match version {
+        Version::Mainline(major, _) | Version::Ge(major, _) => Some(major),
+        Version::Experimental | Version::Hotfix | Version::Custom => None,
+    }
+}
@@ -0,0 +1,635 @@
+use crate::error::{Error, Kind};
+use crate::foreign::IMPORTED;
+use crate::registry::Registry;
+use crate::util::{dir_size, format_size, format_time, is_plain_name};
+use crate::{pass, throw, Config, Index, Version};
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Name of the stamp proton-call writes into a prefix on every launch
+static LAST_USED: &str = "proton-call-last-used";
+
+/// Name of the stamp recording the architecture a prefix was created with
+static ARCH: &str = "proton-call-arch";
+
+/// Name of the stamp recording the Proton version proton-call last launched in a prefix
+static LAST_VERSION: &str = "proton-call-version";
+
+/// File in the compat data directory listing the winetricks verbs fixes installed
+static FIX_VERBS: &str = "proton-call-verbs";
+
+/// Architecture of a Wine prefix or program
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Arch {
+    /// 32-bit only
+    Win32,
+    /// 64-bit, also running 32-bit programs
+    Win64,
+}
+
+impl Display for Arch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Arch::Win32 => write!(f, "win32"),
+            Arch::Win64 => write!(f, "win64"),
+        }
+    }
+}
+
+impl FromStr for Arch {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "win32" => pass!(Arch::Win32),
+            "win64" => pass!(Arch::Win64),
+            _ => throw!(Kind::ArgumentParse, "'{}' is not win32 or win64", s),
+        }
+    }
+}
+
+/// Prefix type to handle a Proton compat data directory
+#[derive(Debug, Clone)]
+pub struct Prefix {
+    name: String,
+    path: PathBuf,
+}
+
+impl Prefix {
+    /// Finds a prefix in `data` by directory name, or by the Proton version which created it
+    ///
+    /// # Errors
+    ///
+    /// Will fail if no matching directory exists in `data`
+    pub fn find(data: &Path, name: &str) -> Result<Prefix, Error> {
+        let path: PathBuf = data.join(name);
+        if path.is_dir() {
+            return pass!(Prefix::new(path));
+        }
+
+        if let Ok(version) = name.parse::<Version>() {
+            let path: PathBuf = data.join(format!("Proton {}", version));
+            if path.is_dir() {
+                return pass!(Prefix::new(path));
+            }
+        }
+
+        throw!(
+            Kind::PrefixMissing,
+            "'{}' in {}",
+            name,
+            data.to_string_lossy()
+        )
+    }
+
+    /// Lists every prefix in `data`, sorted by name
+    ///
+    /// # Errors
+    ///
+    /// Will fail if `data` can not be read
+    pub fn list(data: &Path) -> Result<Vec<Prefix>, Error> {
+        let rd = match data.read_dir() {
+            Ok(rd) => rd,
+            Err(e) => throw!(Kind::PrefixRead, "{}: {}", data.to_string_lossy(), e),
+        };
+
+        let mut prefixes: Vec<Prefix> = rd
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.is_dir())
+            .map(Prefix::new)
+            .collect();
+
+        prefixes.sort_by(|a, b| a.name.cmp(&b.name));
+
+        pass!(prefixes)
+    }
+
+    /// Creates a new instance of `Prefix` for the compat data directory `name` in `data`, which
+    /// need not exist yet
+    ///
+    /// # Errors
+    ///
+    /// Will fail if `name` is not a plain directory name, which could reach outside `data`
+    pub fn named(data: &Path, name: &str) -> Result<Prefix, Error> {
+        if !is_plain_name(name) {
+            throw!(
+                Kind::ArgumentParse,
+                "prefix name '{}' is not a plain directory name",
+                name
+            );
+        }
+
+        pass!(Prefix::new(data.join(name)))
+    }
+
+    #[must_use]
+    /// Creates a new instance of `Prefix` for a compat data directory
+    pub fn new(path: PathBuf) -> Prefix {
+        let name: String = match path.file_name() {
+            Some(n) => n.to_string_lossy().to_string(),
+            None => path.to_string_lossy().to_string(),
+        };
+
+        Prefix { name, path }
+    }
+
+    #[must_use]
+    /// Returns the name of the prefix
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[must_use]
+    /// Returns the path to the compat data directory
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    #[must_use]
+    /// Returns the path to the Wine prefix inside the compat data directory
+    pub fn pfx(&self) -> PathBuf {
+        self.path.join("pfx")
+    }
+
+    #[must_use]
+    /// Returns the path to the Wine user profile inside the prefix
+    pub fn user_dir(&self) -> PathBuf {
+        self.pfx().join("drive_c/users/steamuser")
+    }
+
+    #[must_use]
+    /// Returns true if Proton has set up a Wine prefix in the directory
+    pub fn is_initialized(&self) -> bool {
+        self.pfx().join("system.reg").exists()
+    }
+
+    #[must_use]
+    /// Returns the version stamp Proton wrote into the prefix
+    pub fn version_stamp(&self) -> Option<String> {
+        let stamp: String = std::fs::read_to_string(self.path.join("version")).ok()?;
+        Some(stamp.trim().to_string())
+    }
+
+    #[must_use]
+    /// Returns the Proton version which last used the prefix, from its name or version stamp
+    ///
+    /// Prefixes imported from other launchers fall back to the version chosen on import.
+    pub fn version(&self) -> Option<Version> {
+        match Version::from_custom(&self.path) {
+            Version::Custom => (),
+            version => return Some(version),
+        }
+
+        if let Some(version) = self.last_version() {
+            return Some(version);
+        }
+
+        if let Some(version) = self.version_stamp().as_deref().and_then(stamp_version) {
+            return Some(version);
+        }
+
+        let imported: String = std::fs::read_to_string(self.path.join(IMPORTED)).ok()?;
+        imported.trim().parse().ok()
+    }
+
+    #[must_use]
+    /// Returns the Proton version proton-call last launched in the prefix, from its stamp
+    pub fn last_version(&self) -> Option<Version> {
+        let stamp: String = std::fs::read_to_string(self.path.join(LAST_VERSION)).ok()?;
+        stamp.trim().parse().ok()
+    }
+
+    /// Records `version` as the Proton version last launched in the prefix
+    ///
+    /// # Errors
+    ///
+    /// Will fail if the stamp can not be written
+    pub fn record_version(&self, version: Version) -> Result<(), Error> {
+        let stamp: PathBuf = self.path.join(LAST_VERSION);
+
+        if let Err(e) = std::fs::write(&stamp, format!("{}\n", version)) {
+            throw!(Kind::PrefixWrite, "{}: {}", stamp.to_string_lossy(), e);
+        }
+
+        pass!()
+    }
+
+    #[must_use]
+    /// Returns the winetricks verbs fixes installed in the prefix
+    pub fn fix_verbs(&self) -> Vec<String> {
+        std::fs::read_to_string(self.path.join(FIX_VERBS))
+            .map(|list| list.lines().map(str::to_string).collect())
+            .unwrap_or_default()
+    }
+
+    /// Records `verbs` as installed in the prefix by fixes, besides those recorded before
+    ///
+    /// # Errors
+    ///
+    /// Will fail if the list can not be written
+    pub fn record_fix_verbs(&self, verbs: &[String]) -> Result<(), Error> {
+        let list: PathBuf = self.path.join(FIX_VERBS);
+
+        let mut recorded: Vec<String> = self.fix_verbs();
+        recorded.extend(verbs.iter().cloned());
+
+        if let Err(e) = std::fs::write(&list, format!("{}\n", recorded.join("\n"))) {
+            throw!(Kind::PrefixWrite, "{}: {}", list.to_string_lossy(), e);
+        }
+
+        pass!()
+    }
+
+    /// Removes Proton's version stamp so the next launch redoes prefix setup
+    ///
+    /// # Errors
+    ///
+    /// Will fail if the stamp exists but can not be removed
+    pub fn invalidate(&self) -> Result<(), Error> {
+        let stamp: PathBuf = self.path.join("version");
+
+        if stamp.exists() {
+            if let Err(e) = std::fs::remove_file(&stamp) {
+                throw!(Kind::PrefixWrite, "{}: {}", stamp.to_string_lossy(), e);
+            }
+        }
+
+        pass!()
+    }
+
+    #[must_use]
+    /// Returns the last time the prefix was used
+    ///
+    /// This is when proton-call last launched in it, or for prefixes it never stamped,
+    /// when Wine last wrote the user registry.
+    pub fn last_used(&self) -> Option<SystemTime> {
+        let stamp: PathBuf = self.path.join(LAST_USED);
+        let user_reg: PathBuf = self.pfx().join("user.reg");
+
+        let path: &Path = if stamp.exists() {
+            &stamp
+        } else if user_reg.exists() {
+            &user_reg
+        } else {
+            &self.path
+        };
+        path.metadata().ok()?.modified().ok()
+    }
+
+    #[must_use]
+    /// Returns the architecture the prefix was created with, from its stamp or registry
+    pub fn arch(&self) -> Option<Arch> {
+        if let Ok(stamp) = std::fs::read_to_string(self.path.join(ARCH)) {
+            return stamp.parse().ok();
+        }
+
+        Registry::open(&self.pfx().join("system.reg"))
+            .ok()?
+            .arch()?
+            .parse()
+            .ok()
+    }
+
+    /// Records the architecture Wine created the prefix with, if it is not recorded yet
+    ///
+    /// # Errors
+    ///
+    /// Will fail if the stamp can not be written
+    pub fn record_arch(&self) -> Result<(), Error> {
+        let stamp: PathBuf = self.path.join(ARCH);
+        if stamp.exists() {
+            return pass!();
+        }
+
+        if let Some(arch) = self.arch() {
+            if let Err(e) = std::fs::write(&stamp, arch.to_string()) {
+                throw!(Kind::PrefixWrite, "{}: {}", stamp.to_string_lossy(), e);
+            }
+        }
+
+        pass!()
+    }
+
+    /// Stamps the prefix as used now
+    ///
+    /// # Errors
+    ///
+    /// Will fail if the stamp can not be written
+    pub fn mark_used(&self) -> Result<(), Error> {
+        let stamp: PathBuf = self.path.join(LAST_USED);
+        let now: u64 = match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(d) => d.as_secs(),
+            Err(_) => 0,
+        };
+
+        if let Err(e) = std::fs::write(&stamp, format!("{}\n", now)) {
+            throw!(Kind::PrefixWrite, "{}: {}", stamp.to_string_lossy(), e);
+        }
+
+        pass!()
+    }
+
+    #[must_use]
+    /// Returns true if the prefix has not been used for at least `age`
+    pub fn unused_for(&self, age: Duration) -> bool {
+        match self.last_used() {
+            Some(time) => matches!(time.elapsed(), Ok(elapsed) if elapsed >= age),
+            None => true,
+        }
+    }
+
+    #[must_use]
+    /// Checks whether the Proton version or program which used the prefix is gone
+    pub fn orphaned(&self, index: &Index, config: &Config) -> Option<Orphan> {
+        for (name, game) in config.games() {
+            if game.prefix().unwrap_or(name) != self.name {
+                continue;
+            }
+
+            match game.path() {
+                Some(path) if !path.exists() => {
+                    return Some(Orphan::ProgramMissing(name.to_string(), path.to_path_buf()))
+                }
+                _ => return None,
+            }
+        }
+
+        match self.version() {
+            Some(version) if index.get(version).is_none() => Some(Orphan::ProtonMissing(version)),
+            _ => None,
+        }
+    }
+
+    /// Deletes the prefix and everything in it
+    ///
+    /// # Errors
+    ///
+    /// Will fail if the directory can not be removed
+    pub fn remove(self) -> Result<(), Error> {
+        let result = if self.path.is_symlink() {
+            std::fs::remove_file(&self.path)
+        } else {
+            std::fs::remove_dir_all(&self.path)
+        };
+
+        if let Err(e) = result {
+            throw!(Kind::PrefixWrite, "{}: {}", self.path.to_string_lossy(), e);
+        }
+
+        pass!()
+    }
+
+    /// Gathers information about the prefix from its files on disk
+    ///
+    /// # Errors
+    ///
+    /// Will fail if the prefix has not been initialized by Proton
+    pub fn info(&self) -> Result<PrefixInfo, Error> {
+        let pfx: PathBuf = self.pfx();
+        if !self.is_initialized() {
+            throw!(
+                Kind::PrefixRead,
+                "'{}' has no wine prefix, it may not have been initialized",
+                self.name
+            );
+        }
+
+        let system: Registry = Registry::open(&pfx.join("system.reg"))?;
+        let user: Registry = Registry::open(&pfx.join("user.reg"))?;
+
+        let windows: Option<String> = match user.value("Software\\Wine", "Version") {
+            Some(v) => Some(v.to_string()),
+            None => system
+                .value(
+                    "Software\\Microsoft\\Windows NT\\CurrentVersion",
+                    "ProductName",
+                )
+                .map(str::to_string),
+        };
+
+        let overrides: Vec<(String, String)> = match user.section("Software\\Wine\\DllOverrides") {
+            Some(s) => s.values.clone(),
+            None => Vec::new(),
+        };
+
+        pass!(PrefixInfo {
+            name: self.name.clone(),
+            path: self.path.clone(),
+            version: self.version_stamp(),
+            arch: system.arch().map(str::to_string),
+            windows,
+            size: dir_size(&self.path),
+            last_used: self.last_used(),
+            overrides,
+        })
+    }
+}
+
+/// Reason a prefix is considered orphaned
+#[derive(Debug, Clone)]
+pub enum Orphan {
+    /// The Proton version which used the prefix is no longer installed
+    ProtonMissing(Version),
+    /// The program using the prefix, by its name in the config, no longer exists
+    ProgramMissing(String, PathBuf),
+}
+
+impl Display for Orphan {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Orphan::ProtonMissing(version) => write!(f, "Proton {} is not installed", version),
+            Orphan::ProgramMissing(name, path) => {
+                write!(f, "{} `{}` does not exist", name, path.to_string_lossy())
+            }
+        }
+    }
+}
+
+/// Information gathered about a `Prefix`
+#[derive(Debug, Clone)]
+pub struct PrefixInfo {
+    name: String,
+    path: PathBuf,
+    version: Option<String>,
+    arch: Option<String>,
+    windows: Option<String>,
+    size: u64,
+    last_used: Option<SystemTime>,
+    overrides: Vec<(String, String)>,
+}
+
+impl PrefixInfo {
+    #[must_use]
+    /// Returns the Wine/Proton version stamp
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    #[must_use]
+    /// Returns the prefix architecture, `win32` or `win64`
+    pub fn arch(&self) -> Option<&str> {
+        self.arch.as_deref()
+    }
+
+    #[must_use]
+    /// Returns the Windows version the prefix reports
+    pub fn windows(&self) -> Option<&str> {
+        self.windows.as_deref()
+    }
+
+    #[must_use]
+    /// Returns the size of the prefix on disk in bytes
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    #[must_use]
+    /// Returns the last time the prefix was used
+    pub fn last_used(&self) -> Option<SystemTime> {
+        self.last_used
+    }
+
+    #[must_use]
+    /// Returns the DLL overrides set in the prefix
+    pub fn overrides(&self) -> &[(String, String)] {
+        &self.overrides
+    }
+}
+
+impl Display for PrefixInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let unknown = || String::from("unknown");
+
+        writeln!(f, "Prefix: {}", self.name)?;
+        writeln!(f, "Path: {}", self.path.to_string_lossy())?;
+        writeln!(
+            f,
+            "Wine prefix: {}",
+            self.path.join("pfx").to_string_lossy()
+        )?;
+        writeln!(
+            f,
+            "Version: {}",
+            self.version.clone().unwrap_or_else(unknown)
+        )?;
+        writeln!(
+            f,
+            "Architecture: {}",
+            self.arch.clone().unwrap_or_else(unknown)
+        )?;
+        writeln!(
+            f,
+            "Windows: {}",
+            self.windows.clone().unwrap_or_else(unknown)
+        )?;
+        writeln!(f, "Size: {}", format_size(self.size))?;
+        writeln!(
+            f,
+            "Last used: {}",
+            self.last_used.map_or_else(unknown, format_time)
+        )?;
+
+        write!(f, "\nDLL overrides ({}):", self.overrides.len())?;
+        for (dll, mode) in &self.overrides {
+            write!(f, "\n    {} = {}", dll, mode)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Table of compat data directories, the Proton version which created each, when each was last
+/// used and its Wine prefix, see `PrefixListing::new`
+#[derive(Debug)]
+pub struct PrefixListing<'a> {
+    prefixes: &'a [Prefix],
+}
+
+impl PrefixListing<'_> {
+    #[must_use]
+    /// Creates a new listing of `prefixes`
+    pub fn new(prefixes: &[Prefix]) -> PrefixListing<'_> {
+        PrefixListing { prefixes }
+    }
+}
+
+impl Display for PrefixListing<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut rows: Vec<[String; 4]> = vec![[
+            String::from("NAME"),
+            String::from("VERSION"),
+            String::from("LAST USED"),
+            String::from("WINE PREFIX"),
+        ]];
+
+        for prefix in self.prefixes {
+            rows.push([
+                prefix.name().to_string(),
+                prefix
+                    .version()
+                    .map_or_else(|| String::from("unknown"), |v| v.to_string()),
+                prefix
+                    .last_used()
+                    .map_or_else(|| String::from("never"), format_time),
+                prefix.pfx().to_string_lossy().to_string(),
+            ]);
+        }
+
+        let mut widths: [usize; 4] = [0; 4];
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+
+        for (i, row) in rows.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+
+            let line: Vec<String> = row
+                .iter()
+                .zip(widths)
+                .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+                .collect();
+
+            write!(f, "{}", line.join("  ").trim_end())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Reads the Proton version out of a prefix's version stamp, whole for GE-Proton builds such as
+/// `GE-Proton8-25`, or up to the build number for mainline ones such as `8.0-104`
+fn stamp_version(stamp: &str) -> Option<Version> {
+    stamp
+        .parse()
+        .ok()
+        .or_else(|| stamp.split('-').next()?.parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_mainline_stamps() {
+        assert_eq!(stamp_version("8.0-104"), Some(Version::Mainline(8, 0)));
+        assert_eq!(stamp_version("7.0"), Some(Version::Mainline(7, 0)));
+    }
+
+    #[test]
+    fn reads_ge_stamps() {
+        assert_eq!(stamp_version("GE-Proton8-25"), Some(Version::Ge(8, 25)));
+    }
+
+    #[test]
+    fn rejects_unknown_stamps() {
+        assert_eq!(stamp_version("wine-9.0"), None);
+        assert_eq!(stamp_version(""), None);
+    }
+}
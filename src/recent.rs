@@ -0,0 +1,88 @@
+use crate::error::{Error, Kind};
+use crate::util::data_dir;
+use crate::{pass, throw};
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+
+/// How many programs are remembered
+const LIMIT: usize = 20;
+
+/// Recent type to remember recently launched programs, most recent first
+#[derive(Debug, Clone, Default)]
+pub struct Recent {
+    programs: Vec<PathBuf>,
+}
+
+impl Recent {
+    /// Opens the list of recently launched programs
+    ///
+    /// # Errors
+    ///
+    /// Will fail if the data directory can not be found or created
+    pub fn open() -> Result<Recent, Error> {
+        let text: String = std::fs::read_to_string(Recent::location()?).unwrap_or_default();
+
+        pass!(Recent {
+            programs: text
+                .lines()
+                .filter(|l| !l.is_empty())
+                .map(PathBuf::from)
+                .collect(),
+        })
+    }
+
+    /// Moves `program` to the top of the list and saves it
+    ///
+    /// # Errors
+    ///
+    /// Will fail if the list can not be written
+    pub fn record(&mut self, program: &Path) -> Result<(), Error> {
+        let program: PathBuf = program
+            .canonicalize()
+            .unwrap_or_else(|_| program.to_path_buf());
+
+        self.programs.retain(|p| *p != program);
+        self.programs.insert(0, program);
+        self.programs.truncate(LIMIT);
+
+        let lines: Vec<Cow<str>> = self.programs.iter().map(|p| p.to_string_lossy()).collect();
+        let text: String = lines.join("\n");
+
+        let location: PathBuf = Recent::location()?;
+        if let Err(e) = std::fs::write(&location, text) {
+            throw!(Kind::Environment, "{}: {}", location.to_string_lossy(), e);
+        }
+
+        pass!()
+    }
+
+    #[must_use]
+    /// Returns the `n`th most recent program, counting from 1
+    pub fn get(&self, n: usize) -> Option<&Path> {
+        self.programs.get(n.checked_sub(1)?).map(PathBuf::as_path)
+    }
+
+    #[must_use]
+    /// Returns true if nothing has been launched yet
+    pub fn is_empty(&self) -> bool {
+        self.programs.is_empty()
+    }
+
+    /// Returns the path to the file the list is kept in
+    fn location() -> Result<PathBuf, Error> {
+        pass!(data_dir("")?.join("recent"))
+    }
+}
+
+impl Display for Recent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Recently launched programs:")?;
+
+        for (i, program) in self.programs.iter().enumerate() {
+            write!(f, "\n@{}  {}", i + 1, program.to_string_lossy())?;
+        }
+
+        Ok(())
+    }
+}
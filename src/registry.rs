@@ -0,0 +1,286 @@
+use crate::error::{Error, Kind};
+use crate::{pass, throw};
+use std::fmt::Write;
+use std::path::Path;
+
+/// A key and its values read from a Wine registry file
+#[derive(Debug, Clone)]
+pub(crate) struct Section {
+    pub(crate) key: String,
+    pub(crate) values: Vec<(String, String)>,
+}
+
+/// Minimal reader for Wine's `system.reg` and `user.reg` files
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Registry {
+    arch: Option<String>,
+    sections: Vec<Section>,
+}
+
+impl Registry {
+    /// Reads and parses a Wine registry file
+    ///
+    /// # Errors
+    ///
+    /// Will fail if the file can not be read
+    pub(crate) fn open(path: &Path) -> Result<Registry, Error> {
+        let bytes: Vec<u8> = match std::fs::read(path) {
+            Ok(b) => b,
+            Err(e) => throw!(Kind::PrefixRead, "{}: {}", path.to_string_lossy(), e),
+        };
+
+        pass!(Registry::parse(&String::from_utf8_lossy(&bytes)))
+    }
+
+    /// Parses the contents of a Wine registry file
+    fn parse(text: &str) -> Registry {
+        let mut registry: Registry = Registry::default();
+
+        for line in text.lines() {
+            let line: &str = line.trim_end();
+
+            if let Some(arch) = line.strip_prefix("#arch=") {
+                registry.arch = Some(arch.to_string());
+            } else if let Some(rest) = line.strip_prefix('[') {
+                if let Some(end) = rest.rfind(']') {
+                    registry.sections.push(Section {
+                        key: rest[..end].replace("\\\\", "\\"),
+                        values: Vec::new(),
+                    });
+                }
+            } else if line.starts_with('"') || line.starts_with('@') {
+                if let (Some(section), Some(value)) =
+                    (registry.sections.last_mut(), parse_value(line))
+                {
+                    section.values.push(value);
+                }
+            }
+        }
+
+        registry
+    }
+
+    #[must_use]
+    /// Returns the architecture recorded in the file header
+    pub(crate) fn arch(&self) -> Option<&str> {
+        self.arch.as_deref()
+    }
+
+    #[must_use]
+    /// Returns the section matching `key`, compared case insensitively
+    pub(crate) fn section(&self, key: &str) -> Option<&Section> {
+        self.sections
+            .iter()
+            .find(|s| s.key.eq_ignore_ascii_case(key))
+    }
+
+    #[must_use]
+    /// Returns a single value from the section matching `key`
+    pub(crate) fn value(&self, key: &str, name: &str) -> Option<&str> {
+        self.section(key)?
+            .values
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// Parses a `"name"="value"` line, unquoting string values
+fn parse_value(line: &str) -> Option<(String, String)> {
+    let (name, rest): (String, &str) = if let Some(rest) = line.strip_prefix('@') {
+        (String::from("@"), rest)
+    } else {
+        unquote(line)?
+    };
+
+    let value: &str = rest.strip_prefix('=')?;
+
+    if value.starts_with('"') {
+        let (value, _) = unquote(value)?;
+        Some((name, value))
+    } else {
+        Some((name, value.to_string()))
+    }
+}
+
+/// Reads a quoted string from the start of `s`, returning it and the remainder
+fn unquote(s: &str) -> Option<(String, &str)> {
+    let mut out: String = String::new();
+    let mut chars = s.char_indices().skip(1);
+
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' => {
+                if let Some((_, e)) = chars.next() {
+                    out.push(e);
+                }
+            }
+            '"' => return Some((out, &s[i + 1..])),
+            _ => out.push(c),
+        }
+    }
+
+    None
+}
+
+/// Builder for registry files to import with `regedit`
+#[derive(Debug, Clone)]
+pub(crate) struct RegFile {
+    text: String,
+}
+
+impl RegFile {
+    #[must_use]
+    /// Creates a new, empty registry file
+    pub(crate) fn new() -> RegFile {
+        RegFile {
+            text: String::from("Windows Registry Editor Version 5.00\n"),
+        }
+    }
+
+    /// Starts a new key, such as `HKEY_CURRENT_USER\Software\Wine`
+    pub(crate) fn key(&mut self, key: &str) {
+        let _ = write!(self.text, "\n[{}]\n", key);
+    }
+
+    /// Adds a string value to the current key
+    pub(crate) fn string(&mut self, name: &str, value: &str) {
+        let _ = writeln!(self.text, "\"{}\"=\"{}\"", escape(name), escape(value));
+    }
+
+    /// Adds an already encoded value, such as `dword:00000000`, to the current key
+    pub(crate) fn raw(&mut self, name: &str, value: &str) {
+        let _ = writeln!(self.text, "\"{}\"={}", escape(name), value);
+    }
+
+    /// Adds a `REG_MULTI_SZ` value made of `values` to the current key
+    pub(crate) fn multi_string(&mut self, name: &str, values: &[&str]) {
+        let mut units: Vec<u16> = Vec::new();
+
+        for value in values {
+            units.extend(value.encode_utf16());
+            units.push(0);
+        }
+        units.push(0);
+
+        let bytes: Vec<String> = units
+            .iter()
+            .flat_map(|u| u.to_le_bytes())
+            .map(|b| format!("{:02x}", b))
+            .collect();
+
+        self.raw(name, &format!("hex(7):{}", bytes.join(",")));
+    }
+
+    /// Writes the registry file to `path`
+    ///
+    /// # Errors
+    ///
+    /// Will fail if the file can not be written
+    pub(crate) fn write(&self, path: &Path) -> Result<(), Error> {
+        if let Err(e) = std::fs::write(path, &self.text) {
+            throw!(Kind::PrefixWrite, "{}: {}", path.to_string_lossy(), e);
+        }
+
+        pass!()
+    }
+}
+
+/// Escapes backslashes and quotes for a registry file string
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static KEY: &str = "HKEY_CURRENT_USER\\Software\\Wine\\DllOverrides";
+
+    fn round_trip(build: impl FnOnce(&mut RegFile)) -> Registry {
+        let mut reg: RegFile = RegFile::new();
+        reg.key(KEY);
+        build(&mut reg);
+        Registry::parse(&reg.text)
+    }
+
+    #[test]
+    fn strings_round_trip_escaped() {
+        let value: &str = "C:\\Program Files\\\"Game\"\\game.exe";
+        let registry: Registry = round_trip(|reg| reg.string("path \"quoted\"", value));
+
+        assert_eq!(registry.value(KEY, "path \"quoted\""), Some(value));
+    }
+
+    #[test]
+    fn raw_values_round_trip() {
+        let registry: Registry = round_trip(|reg| {
+            reg.raw("flag", "dword:00000001");
+            reg.multi_string("list", &["a", "b"]);
+        });
+
+        assert_eq!(registry.value(KEY, "flag"), Some("dword:00000001"));
+        assert_eq!(
+            registry.value(KEY, "list"),
+            Some("hex(7):61,00,00,00,62,00,00,00,00,00")
+        );
+    }
+
+    #[test]
+    fn sections_are_found_case_insensitively() {
+        let registry: Registry = round_trip(|reg| {
+            reg.string("d3d11", "native");
+            reg.key("HKEY_CURRENT_USER\\Software\\Wine\\Direct3D");
+            reg.string("renderer", "vulkan");
+        });
+
+        let section: &Section = registry
+            .section("hkey_current_user\\software\\wine\\dlloverrides")
+            .unwrap();
+        assert_eq!(section.key, KEY);
+        assert_eq!(
+            section.values,
+            [(String::from("d3d11"), String::from("native"))]
+        );
+        assert_eq!(
+            registry.value("HKEY_CURRENT_USER\\Software\\Wine\\Direct3D", "RENDERER"),
+            Some("vulkan")
+        );
+    }
+
+    #[test]
+    fn reads_wine_registry_files() {
+        let registry: Registry = Registry::parse(
+            "WINE REGISTRY Version 2\n\
+             #arch=win64\n\
+             \n\
+             [Software\\\\Wine\\\\DllOverrides] 1700000000\n\
+             #time=1da0000000000000\n\
+             @=\"default\"\n\
+             \"dxgi\"=\"native\"\n\
+             \"count\"=dword:0000000a\n",
+        );
+
+        assert_eq!(registry.arch(), Some("win64"));
+        assert_eq!(
+            registry.value("Software\\Wine\\DllOverrides", "@"),
+            Some("default")
+        );
+        assert_eq!(
+            registry.value("Software\\Wine\\DllOverrides", "dxgi"),
+            Some("native")
+        );
+        assert_eq!(
+            registry.value("Software\\Wine\\DllOverrides", "count"),
+            Some("dword:0000000a")
+        );
+    }
+
+    #[test]
+    fn unterminated_strings_are_skipped() {
+        let registry: Registry = Registry::parse("[Key]\n\"name\"=\"open\n\"ok\"=\"1\"\n");
+
+        assert_eq!(registry.value("Key", "name"), None);
+        assert_eq!(registry.value("Key", "ok"), Some("1"));
+    }
+}
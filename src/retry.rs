@@ -0,0 +1,63 @@
+use crate::timing::{forward, FirstOutput};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+use std::process::ChildStderr;
+use std::sync::mpsc::{channel, Receiver};
+use std::time::Duration;
+
+/// How soon after starting a failed launch counts as a startup failure
+pub(crate) const STARTUP_WINDOW: Duration = Duration::from_secs(5);
+
+/// How long to wait for the stderr watcher to catch up after the program exits
+const CATCH_UP: Duration = Duration::from_millis(250);
+
+/// Lowercased error output of failures which usually succeed when launched again
+static TRANSIENT: &[&str] = &[
+    // The X server or compositor was not ready to accept the connection yet
+    "cannot open display",
+    "can't open display",
+    "failed to connect to wayland display",
+    "authorization required, but no authorization protocol specified",
+    // The Steam runtime container could not be set up
+    "pressure-vessel-wrap[",
+    "pv-adverb[",
+    "bwrap: ",
+];
+
+/// Returns true if `line` of error output shows a transient failure
+fn is_transient(line: &str) -> bool {
+    let line: String = line.to_lowercase();
+    TRANSIENT.iter().any(|t| line.contains(t))
+}
+
+/// Forwards the program's error output to ours, sending each line showing a transient failure
+pub(crate) fn watch(stderr: ChildStderr, first: FirstOutput) -> Receiver<String> {
+    let (sender, receiver) = channel();
+
+    forward(stderr, std::io::stderr(), first, move |line| {
+        if is_transient(line) {
+            let _ = sender.send(line.to_string());
+        }
+    });
+
+    receiver
+}
+
+/// Returns the first transient failure sent by `watch`, waiting briefly for it to catch up
+pub(crate) fn received(receiver: &Receiver<String>) -> Option<String> {
+    receiver.recv_timeout(CATCH_UP).ok()
+}
+
+/// Returns the first transient failure written to the file at `path` past `offset`
+pub(crate) fn written(path: &Path, offset: u64) -> Option<String> {
+    let mut file = std::fs::File::open(path).ok()?;
+    file.seek(SeekFrom::Start(offset)).ok()?;
+
+    let mut text: Vec<u8> = Vec::new();
+    file.read_to_end(&mut text).ok()?;
+
+    String::from_utf8_lossy(&text)
+        .lines()
+        .find(|l| is_transient(l))
+        .map(str::to_string)
+}
@@ -0,0 +1,105 @@
+use crate::error::{Error, Kind};
+use crate::steam::{vdf_values, Library};
+use crate::{pass, throw};
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Steam Linux Runtime containers Proton can be launched through
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Runtime {
+    /// Steam Linux Runtime 3.0, required by Proton 8.0 and newer
+    Sniper,
+    /// Steam Linux Runtime 2.0, required by Proton 5.13 to 7.0
+    Soldier,
+    /// No container, Proton runs directly on the host
+    None,
+}
+
+impl Display for Runtime {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Runtime::Sniper => write!(f, "sniper"),
+            Runtime::Soldier => write!(f, "soldier"),
+            Runtime::None => write!(f, "none"),
+        }
+    }
+}
+
+impl FromStr for Runtime {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "sniper" => pass!(Runtime::Sniper),
+            "soldier" => pass!(Runtime::Soldier),
+            "none" => pass!(Runtime::None),
+            _ => throw!(
+                Kind::ArgumentParse,
+                "runtime '{}', expected sniper, soldier or none",
+                s
+            ),
+        }
+    }
+}
+
+impl Runtime {
+    #[must_use]
+    /// Reads which runtime the Proton in `proton_dir` requires from its `toolmanifest.vdf`,
+    /// `Runtime::None` for versions which predate the containers
+    pub fn required(proton_dir: &Path) -> Runtime {
+        let Ok(manifest) = std::fs::read_to_string(proton_dir.join("toolmanifest.vdf")) else {
+            return Runtime::None;
+        };
+
+        let appid: Option<u32> = vdf_values(&manifest, "require_tool_appid")
+            .next()
+            .and_then(|id| id.parse().ok());
+
+        [Runtime::Sniper, Runtime::Soldier]
+            .into_iter()
+            .find(|runtime| runtime.appid() == appid)
+            .unwrap_or(Runtime::None)
+    }
+
+    /// Returns the Steam app id the runtime is installed as
+    fn appid(self) -> Option<u32> {
+        match self {
+            Runtime::Sniper => Some(1_628_350),
+            Runtime::Soldier => Some(1_391_110),
+            Runtime::None => None,
+        }
+    }
+
+    /// Returns the directory name Steam installs the runtime under
+    fn dir_name(self) -> Option<&'static str> {
+        match self {
+            Runtime::Sniper => Some("SteamLinuxRuntime_sniper"),
+            Runtime::Soldier => Some("SteamLinuxRuntime_soldier"),
+            Runtime::None => None,
+        }
+    }
+
+    #[must_use]
+    /// Finds the runtime's container entry point next to the Proton in `proton_dir`, or in any
+    /// library of the Steam install at `steam`
+    pub(crate) fn entry_point(self, proton_dir: &Path, steam: &Path) -> Option<PathBuf> {
+        let name: &str = self.dir_name()?;
+
+        let mut commons: Vec<PathBuf> = proton_dir
+            .parent()
+            .map(Path::to_path_buf)
+            .into_iter()
+            .collect();
+        commons.extend(
+            Library::all(steam)
+                .iter()
+                .map(|l| l.steamapps().join("common")),
+        );
+
+        commons
+            .iter()
+            .map(|common| common.join(name).join("_v2-entry-point"))
+            .find(|entry| entry.is_file())
+    }
+}
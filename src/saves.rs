@@ -0,0 +1,431 @@
+use crate::error::{Error, Kind};
+use crate::util::{format_time, run, shell_quote, timestamp};
+use crate::{pass, throw, Prefix, ProtonEvent};
+use std::cmp::Reverse;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::SystemTime;
+
+/// Directories in the user profile where games usually keep their saves
+static SAVE_ROOTS: &[&str] = &[
+    "Documents",
+    "My Documents",
+    "Saved Games",
+    "AppData/Roaming",
+    "AppData/Local",
+    "AppData/LocalLow",
+    "Application Data",
+    "Local Settings/Application Data",
+];
+
+/// User profile directories archived by a save backup
+static PROFILE_DIRS: &[&str] = &[
+    "Documents",
+    "My Documents",
+    "Saved Games",
+    "AppData",
+    "Application Data",
+    "Local Settings",
+];
+
+/// Directories which never contain saves, skipped while scanning
+static IGNORED: &[&str] = &["microsoft", "temp", "wine", "steam", "openal", "crashdumps"];
+
+/// File extensions commonly used for save files
+static SAVE_EXTENSIONS: &[&str] = &["sav", "save", "ess", "sl2", "savegame", "bak"];
+
+/// How deep below a save root to look for save directories
+const MAX_DEPTH: usize = 4;
+
+/// A directory which looks like it contains save games
+#[derive(Debug, Clone)]
+pub struct SaveLocation {
+    prefix: String,
+    path: PathBuf,
+    modified: Option<SystemTime>,
+}
+
+impl SaveLocation {
+    #[must_use]
+    /// Returns the name of the prefix containing the saves
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    #[must_use]
+    /// Returns the host path to the save directory
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    #[must_use]
+    /// Returns the last time anything in the save directory changed
+    pub fn modified(&self) -> Option<SystemTime> {
+        self.modified
+    }
+}
+
+/// Saves type to discover save game directories in prefixes
+#[derive(Debug, Clone, Default)]
+pub struct Saves {
+    locations: Vec<SaveLocation>,
+}
+
+impl Saves {
+    #[must_use]
+    /// Scans the user profile of `prefix` for save directories, most recently modified first
+    pub fn locate(prefix: &Prefix) -> Saves {
+        let user: PathBuf = prefix.user_dir();
+        let mut saves: Saves = Saves::default();
+
+        for root in SAVE_ROOTS {
+            let root: PathBuf = user.join(root);
+            // `My Documents` and friends are often symlinks to the newer names
+            if root.is_symlink() {
+                continue;
+            }
+            saves.scan(prefix.name(), &root, 0);
+        }
+
+        saves.sort();
+        saves
+    }
+
+    #[must_use]
+    /// Scans every prefix in `prefixes` for save directories whose path matches `query`
+    pub fn search(prefixes: &[Prefix], query: &str) -> Saves {
+        let query: String = normalize(query);
+        let mut saves: Saves = Saves::default();
+
+        for prefix in prefixes {
+            let user: PathBuf = prefix.user_dir();
+            saves
+                .locations
+                .extend(Saves::locate(prefix).locations.into_iter().filter(|l| {
+                    match l.path.strip_prefix(&user) {
+                        Ok(p) => normalize(&p.to_string_lossy()).contains(&query),
+                        Err(_) => false,
+                    }
+                }));
+        }
+
+        saves.sort();
+        saves
+    }
+
+    #[must_use]
+    /// Returns the discovered save locations
+    pub fn locations(&self) -> &[SaveLocation] {
+        &self.locations
+    }
+
+    #[must_use]
+    /// Returns true if no saves were found
+    pub fn is_empty(&self) -> bool {
+        self.locations.is_empty()
+    }
+
+    /// Archives the user profile of `prefix` into `dir`, keeping at most `retention` archives
+    ///
+    /// Only the documents, saved games and application data directories are archived,
+    /// not the whole compat data directory.
+    ///
+    /// # Errors
+    ///
+    /// Will fail if the profile is missing, or running `tar` or pruning old backups fails
+    pub fn backup(prefix: &Prefix, dir: &Path, retention: Option<usize>) -> Result<PathBuf, Error> {
+        let user: PathBuf = prefix.user_dir();
+
+        let roots: Vec<&str> = PROFILE_DIRS
+            .iter()
+            .copied()
+            .filter(|r| {
+                let path: PathBuf = user.join(r);
+                path.is_dir() && !path.is_symlink()
+            })
+            .collect();
+
+        if roots.is_empty() {
+            throw!(Kind::Backup, "'{}' has no user profile", prefix.name());
+        }
+
+        if let Err(e) = std::fs::create_dir_all(dir) {
+            throw!(Kind::Backup, "{}: {}", dir.to_string_lossy(), e);
+        }
+
+        let archive: PathBuf = dir.join(format!("{}.tar.gz", timestamp(SystemTime::now())));
+
+        run(
+            Command::new("tar")
+                .arg("-C")
+                .arg(&user)
+                .arg("-czf")
+                .arg(&archive)
+                .args(&roots),
+            Kind::Backup,
+        )?;
+
+        if let Some(keep) = retention {
+            let backups: Vec<PathBuf> = Saves::backups(dir);
+            for old in backups.iter().skip(keep.max(1)) {
+                if let Err(e) = std::fs::remove_file(old) {
+                    throw!(Kind::Backup, "{}: {}", old.to_string_lossy(), e);
+                }
+            }
+        }
+
+        pass!(archive)
+    }
+
+    #[must_use]
+    /// Lists the save backups in `dir`, newest first
+    pub fn backups(dir: &Path) -> Vec<PathBuf> {
+        let mut backups: Vec<PathBuf> = dir
+            .read_dir()
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.to_string_lossy().ends_with(".tar.gz"))
+            .collect();
+
+        backups.sort_by(|a, b| b.cmp(a));
+        backups
+    }
+
+    /// Extracts a backup made by `Saves::backup` back into the user profile of `prefix`
+    ///
+    /// # Errors
+    ///
+    /// Will fail if the archive is missing or running `tar` fails
+    pub fn restore(prefix: &Prefix, archive: &Path) -> Result<(), Error> {
+        if !archive.is_file() {
+            throw!(Kind::Backup, "{} does not exist", archive.to_string_lossy());
+        }
+
+        let user: PathBuf = prefix.user_dir();
+
+        if let Err(e) = std::fs::create_dir_all(&user) {
+            throw!(Kind::Backup, "{}: {}", user.to_string_lossy(), e);
+        }
+
+        run(
+            Command::new("tar")
+                .arg("-C")
+                .arg(&user)
+                .arg("-xzf")
+                .arg(archive),
+            Kind::Backup,
+        )
+    }
+
+    /// Looks through `dir` for save directories, stopping at the first match on each branch
+    fn scan(&mut self, prefix: &str, dir: &Path, depth: usize) {
+        let mut has_saves: bool = false;
+        let mut children: Vec<PathBuf> = Vec::new();
+
+        for entry in dir.read_dir().into_iter().flatten().flatten() {
+            let path: PathBuf = entry.path();
+
+            if path.is_dir() && !path.is_symlink() {
+                children.push(path);
+            } else if is_save_file(&path) {
+                has_saves = true;
+            }
+        }
+
+        let name: String = dir
+            .file_name()
+            .map(|n| n.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+
+        if depth > 0 && (has_saves || name.contains("save")) {
+            self.locations.push(SaveLocation {
+                prefix: prefix.to_string(),
+                path: dir.to_path_buf(),
+                modified: newest(dir),
+            });
+            return;
+        }
+
+        if depth >= MAX_DEPTH {
+            return;
+        }
+
+        for child in children {
+            let name: String = child
+                .file_name()
+                .map(|n| n.to_string_lossy().to_lowercase())
+                .unwrap_or_default();
+
+            if !IGNORED.contains(&name.as_str()) {
+                self.scan(prefix, &child, depth + 1);
+            }
+        }
+    }
+
+    /// Sorts locations by most recently modified
+    fn sort(&mut self) {
+        self.locations.sort_by_key(|l| Reverse(l.modified));
+    }
+}
+
+impl Display for Saves {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Found {} save locations:", self.locations.len())?;
+
+        for location in &self.locations {
+            let modified: String = location
+                .modified
+                .map_or_else(|| String::from("unknown"), format_time);
+
+            write!(
+                f,
+                "\n[{}] {} `{}`",
+                location.prefix,
+                modified,
+                location.path.to_string_lossy()
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns true if `path` has a save-like extension
+fn is_save_file(path: &Path) -> bool {
+    match path.extension() {
+        Some(ext) => {
+            let ext: String = ext.to_string_lossy().to_lowercase();
+            SAVE_EXTENSIONS.contains(&ext.as_str())
+        }
+        None => false,
+    }
+}
+
+/// Finds the most recent modification time of anything below `dir`
+fn newest(dir: &Path) -> Option<SystemTime> {
+    let mut time: Option<SystemTime> = dir.metadata().ok()?.modified().ok();
+
+    if let Ok(rd) = dir.read_dir() {
+        for entry in rd.flatten() {
+            let path: PathBuf = entry.path();
+            let modified: Option<SystemTime> = if path.is_dir() && !path.is_symlink() {
+                newest(&path)
+            } else {
+                path.metadata().ok().and_then(|m| m.modified().ok())
+            };
+
+            time = time.max(modified);
+        }
+    }
+
+    time
+}
+
+/// Lowercases and strips everything but letters and digits, for loose matching
+fn normalize(s: &str) -> String {
+    s.chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+/// Commands run before and after a launch to sync the program's saves, from the config
+///
+/// `%saves%` is replaced with the host path of the newest save directory found in the prefix,
+/// or its user profile when none is found, and `%prefix%` with the compat data directory, both
+/// quoted for the shell.
+#[derive(Debug, Clone, Default)]
+pub struct SyncHooks {
+    before: Option<String>,
+    after: Option<String>,
+}
+
+impl SyncHooks {
+    #[must_use]
+    /// Creates a new instance of `SyncHooks`
+    pub fn new(before: Option<String>, after: Option<String>) -> SyncHooks {
+        SyncHooks { before, after }
+    }
+
+    #[must_use]
+    /// Returns true if there are no hooks to run
+    pub fn is_empty(&self) -> bool {
+        self.before.is_none() && self.after.is_none()
+    }
+
+    /// Runs the hook meant for before the launch
+    pub(crate) fn before(&self, compat: &Path) -> Result<(), Error> {
+        match &self.before {
+            Some(hook) => run_hook(hook, compat),
+            None => pass!(),
+        }
+    }
+
+    /// Runs the hook meant for after the launch
+    pub(crate) fn after(&self, compat: &Path) -> Result<(), Error> {
+        match &self.after {
+            Some(hook) => run_hook(hook, compat),
+            None => pass!(),
+        }
+    }
+}
+
+/// Runs a sync hook through `sh`, substituting the save and prefix paths
+fn run_hook(hook: &str, compat: &Path) -> Result<(), Error> {
+    let prefix: Prefix = Prefix::new(compat.to_path_buf());
+
+    let saves: PathBuf = match Saves::locate(&prefix).locations.into_iter().next() {
+        Some(location) => location.path,
+        None => prefix.user_dir(),
+    };
+
+    let command: String = hook_command(hook, &saves, compat);
+
+    ProtonEvent::SyncingSaves(command.clone()).emit();
+
+    match Command::new("sh").arg("-c").arg(&command).status() {
+        Ok(s) if s.success() => pass!(),
+        Ok(s) => throw!(Kind::Hook, "'{}' exited with {}", command, s),
+        Err(e) => throw!(Kind::Hook, "'{}': {}", command, e),
+    }
+}
+
+/// Substitutes the save and prefix paths into a sync hook, quoted since save directories such
+/// as `My Games` often hold spaces
+fn hook_command(hook: &str, saves: &Path, compat: &Path) -> String {
+    hook.replace("%saves%", &shell_quote(&saves.to_string_lossy()))
+        .replace("%prefix%", &shell_quote(&compat.to_string_lossy()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hook_paths_are_quoted() {
+        let command: String = hook_command(
+            "rclone sync %saves% remote:saves && ls %prefix%",
+            Path::new("/games/pfx/drive_c/users/steamuser/Documents/My Games/Skyrim"),
+            Path::new("/data/skyrim"),
+        );
+
+        assert_eq!(
+            command,
+            "rclone sync '/games/pfx/drive_c/users/steamuser/Documents/My Games/Skyrim' remote:saves && ls /data/skyrim"
+        );
+    }
+
+    #[test]
+    fn hook_paths_can_not_inject_commands() {
+        let command: String = hook_command(
+            "echo %saves%",
+            Path::new("/x/it's; rm -rf ~"),
+            Path::new("/"),
+        );
+
+        assert_eq!(command, "echo '/x/it'\\''s; rm -rf ~'");
+    }
+}
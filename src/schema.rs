@@ -0,0 +1,135 @@
+use crate::Warnings;
+use toml::value::Table;
+
+/// Version of the config format this proton-call writes and reads, files without a `schema`
+/// key are taken to be the first
+pub(crate) const SCHEMA: u32 = 1;
+
+/// Steps bringing a config from each older schema to the next, the first from schema 1 to 2
+static MIGRATIONS: &[fn(&mut Table)] = &[];
+
+/// Keys the config's top level may hold
+static KEYS: &[&str] = &[
+    "schema",
+    "data",
+    "steam",
+    "common",
+    "backups",
+    "runners",
+    "retries",
+    "downgrade",
+    "major_jump",
+    "dxvk_async",
+    "gpl",
+    "radv_perftest",
+    "nvapi",
+    "dlss_indicator",
+    "discord",
+    "discord_client_id",
+    "allow_menus",
+    "dedup",
+    "games",
+    "aliases",
+    "roots",
+    "env_allow",
+    "env_deny",
+];
+
+/// Keys a `[games.NAME]` table may hold
+static GAME_KEYS: &[&str] = &[
+    "path",
+    "prefix",
+    "proton",
+    "retention",
+    "sync_before",
+    "sync_after",
+    "command_template",
+    "nvapi",
+    "dlss_indicator",
+    "env",
+    "args",
+];
+
+/// Brings a parsed config up to the current schema and warns about keys proton-call does not
+/// know, which would otherwise be ignored silently, suggesting the closest known key
+pub(crate) fn check(config: &mut Table) {
+    migrate(config);
+
+    for key in config.keys() {
+        unknown(key, KEYS, key);
+    }
+
+    if let Some(toml::Value::Table(games)) = config.get("games") {
+        for (name, game) in games {
+            if let toml::Value::Table(game) = game {
+                for key in game.keys() {
+                    unknown(key, GAME_KEYS, &format!("games.{}.{}", name, key));
+                }
+            }
+        }
+    }
+}
+
+/// Runs the migrations from the config's schema to the current one
+fn migrate(config: &mut Table) {
+    let schema: u32 = match config.get("schema") {
+        Some(toml::Value::Integer(n)) => u32::try_from(*n).unwrap_or(SCHEMA),
+        _ => 1,
+    };
+
+    if schema > SCHEMA {
+        Warnings::push(
+            "config",
+            format!(
+                "the config is written for schema {}, this proton-call reads schema {}, \
+                 newer keys may be ignored",
+                schema, SCHEMA
+            ),
+        );
+        return;
+    }
+
+    for migration in MIGRATIONS.iter().skip(schema.saturating_sub(1) as usize) {
+        migration(config);
+    }
+}
+
+/// Warns about `key` unless it is one of `known`, naming it `shown`
+fn unknown(key: &str, known: &[&str], shown: &str) {
+    if known.contains(&key) {
+        return;
+    }
+
+    let closest: Option<&str> = known
+        .iter()
+        .map(|k| (distance(key, k), *k))
+        .filter(|(d, k)| *d <= (k.len() / 3).max(1))
+        .min()
+        .map(|(_, k)| k);
+
+    let warning: String = match closest {
+        Some(k) => format!("unknown key '{}' is ignored, did you mean '{}'?", shown, k),
+        None => format!("unknown key '{}' is ignored", shown),
+    };
+
+    Warnings::push("config", warning);
+}
+
+/// Counts the single character edits between `a` and `b`, their Levenshtein distance
+fn distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut previous: usize = row[0];
+        row[0] = i + 1;
+
+        for (j, cb) in b.iter().enumerate() {
+            let replaced: usize = previous + usize::from(ca != *cb);
+            previous = row[j + 1];
+            row[j + 1] = replaced.min(row[j] + 1).min(previous + 1);
+        }
+    }
+
+    row[b.len()]
+}
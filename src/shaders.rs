@@ -0,0 +1,68 @@
+use std::fmt::Write;
+
+/// Shader compilation options of DXVK and Mesa's RADV driver, from the config and flags
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Shaders {
+    dxvk_async: bool,
+    gpl: Option<bool>,
+    radv_perftest: Vec<String>,
+}
+
+impl Shaders {
+    #[must_use]
+    /// Compiles shaders in the background with builds of DXVK which support it, such as GE's,
+    /// trading stutter for missing effects while they compile
+    pub fn dxvk_async(mut self, enable: bool) -> Shaders {
+        self.dxvk_async = enable;
+        self
+    }
+
+    #[must_use]
+    /// Forces DXVK's use of the graphics pipeline library on or off, instead of leaving it to
+    /// DXVK to decide
+    pub fn gpl(mut self, enable: bool) -> Shaders {
+        self.gpl = Some(enable);
+        self
+    }
+
+    #[must_use]
+    /// Adds comma separated `RADV_PERFTEST` options, such as `gpl` on older Mesa
+    pub fn radv_perftest(mut self, options: &str) -> Shaders {
+        for option in options.split(',').map(str::trim) {
+            if !option.is_empty() && !self.radv_perftest.iter().any(|o| o == option) {
+                self.radv_perftest.push(option.to_string());
+            }
+        }
+        self
+    }
+
+    #[must_use]
+    /// Returns the variables setting the options, added to the values of `inherited`
+    pub fn env(&self, inherited: impl Fn(&str) -> Option<String>) -> Vec<(&'static str, String)> {
+        let mut env: Vec<(&'static str, String)> = Vec::new();
+
+        if self.dxvk_async {
+            env.push(("DXVK_ASYNC", String::from("1")));
+        }
+
+        if let Some(enable) = self.gpl {
+            let mut config: String = inherited("DXVK_CONFIG").unwrap_or_default();
+            if !config.is_empty() {
+                config.push(';');
+            }
+            let value: &str = if enable { "True" } else { "False" };
+            let _ = write!(config, "dxvk.enableGraphicsPipelineLibrary = {}", value);
+            env.push(("DXVK_CONFIG", config));
+        }
+
+        if !self.radv_perftest.is_empty() {
+            let mut options: Vec<String> = inherited("RADV_PERFTEST")
+                .map(|o| o.split(',').map(str::to_string).collect())
+                .unwrap_or_default();
+            options.extend(self.radv_perftest.iter().cloned());
+            env.push(("RADV_PERFTEST", options.join(",")));
+        }
+
+        env
+    }
+}
@@ -0,0 +1,276 @@
+use crate::Prefix;
+use std::io::{IsTerminal, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus, Output, Stdio};
+
+/// Word completed against the prefix's `pfx` directory, and programs resolved into it
+static DRIVE_C: &str = "drive_c";
+
+/// Line reader for `proton-call shell`, completing `drive_c` paths inside a prefix with Tab
+pub struct Shell {
+    pfx: PathBuf,
+    prompt: String,
+}
+
+impl Shell {
+    #[must_use]
+    /// Creates a new `Shell` inside `prefix`
+    pub fn new(prefix: &Prefix) -> Shell {
+        Shell {
+            pfx: prefix.pfx(),
+            prompt: format!("{}> ", prefix.name()),
+        }
+    }
+
+    #[must_use]
+    /// Reads the next line, editing it on a terminal, `None` once input ends
+    pub fn read_line(&self) -> Option<String> {
+        if std::io::stdin().is_terminal() {
+            if let Some(_raw) = RawMode::enter() {
+                return self.edit();
+            }
+
+            print!("{}", self.prompt);
+            let _ = std::io::stdout().flush();
+        }
+
+        let mut line: String = String::new();
+        match std::io::stdin().read_line(&mut line) {
+            Ok(0) | Err(_) => None,
+            Ok(_) => Some(line.trim_end_matches(['\r', '\n']).to_string()),
+        }
+    }
+
+    #[must_use]
+    /// Returns the path to run a program at, paths starting at `drive_c` are inside the prefix
+    pub fn program(&self, word: &str) -> PathBuf {
+        if word == DRIVE_C || word.starts_with("drive_c/") {
+            self.pfx.join(word)
+        } else {
+            PathBuf::from(word)
+        }
+    }
+
+    #[must_use]
+    /// Splits a command line into words, double or single quotes keep spaces within a word
+    pub fn words(line: &str) -> Vec<String> {
+        let mut words: Vec<String> = Vec::new();
+        let mut word: Option<String> = None;
+        let mut quote: Option<char> = None;
+
+        for c in line.chars() {
+            match (quote, c) {
+                (Some(q), c) if c == q => quote = None,
+                (None, '"' | '\'') => {
+                    quote = Some(c);
+                    word.get_or_insert_with(String::new);
+                }
+                (None, c) if c.is_whitespace() => words.extend(word.take()),
+                _ => word.get_or_insert_with(String::new).push(c),
+            }
+        }
+
+        words.extend(word);
+        words
+    }
+
+    /// Edits a line on a terminal in raw mode until Enter, or Ctrl-D on an empty line
+    fn edit(&self) -> Option<String> {
+        let mut stdin = std::io::stdin().lock();
+        let mut line: String = String::new();
+        let mut pending: Vec<u8> = Vec::new();
+
+        self.redraw(&line);
+
+        loop {
+            let mut byte: [u8; 1] = [0];
+            if stdin.read(&mut byte).ok()? == 0 {
+                return None;
+            }
+
+            match byte[0] {
+                b'\r' | b'\n' => {
+                    print!("\r\n");
+                    let _ = std::io::stdout().flush();
+                    return Some(line);
+                }
+                // Ctrl-D
+                0x04 if line.is_empty() => {
+                    print!("\r\n");
+                    return None;
+                }
+                // Ctrl-C drops the line
+                0x03 => {
+                    line.clear();
+                    print!("^C\r\n");
+                }
+                // Backspace and delete
+                0x7f | 0x08 => {
+                    line.pop();
+                }
+                b'\t' => self.complete(&mut line),
+                // Escape sequences, such as the arrow keys, are not supported
+                0x1b => {
+                    let mut sequence: [u8; 2] = [0; 2];
+                    let _ = stdin.read(&mut sequence);
+                }
+                b if b >= 0x20 => {
+                    pending.push(b);
+                    if let Ok(text) = std::str::from_utf8(&pending) {
+                        line.push_str(text);
+                        pending.clear();
+                    } else if pending.len() >= 4 {
+                        pending.clear();
+                    }
+                }
+                _ => (),
+            }
+
+            self.redraw(&line);
+        }
+    }
+
+    /// Prints the prompt and line over the current one
+    fn redraw(&self, line: &str) {
+        print!("\r\x1b[K{}{}", self.prompt, line);
+        let _ = std::io::stdout().flush();
+    }
+
+    /// Completes the last word of `line` if it is a `drive_c` path, extending it to the longest
+    /// common start of the matches and listing them when there are several
+    fn complete(&self, line: &mut String) {
+        let start: usize = word_start(line);
+        let quoted: bool = line[start..].starts_with('"');
+        let word: &str = line[start..].trim_start_matches('"');
+
+        if word.is_empty() || !DRIVE_C.starts_with(word) && !word.starts_with(DRIVE_C) {
+            return;
+        }
+
+        let (dir, partial): (&str, &str) = match word.rfind('/') {
+            Some(i) => (&word[..=i], &word[i + 1..]),
+            None => ("", word),
+        };
+
+        let matches: Vec<(String, bool)> = entries(&self.pfx.join(dir), partial);
+        let Some(common) = common_start(&matches) else {
+            return;
+        };
+
+        if matches.len() > 1 && common == partial {
+            let names: Vec<&str> = matches.iter().map(|(name, _)| name.as_str()).collect();
+            print!("\r\n{}\r\n", names.join("  "));
+            return;
+        }
+
+        let completed: String = format!("{}{}", dir, common);
+        let quoted: bool = quoted || completed.contains(' ');
+
+        line.truncate(start);
+        if quoted {
+            line.push('"');
+        }
+        line.push_str(&completed);
+
+        // A single match is finished, directories to go on into them and files with the word
+        match matches.as_slice() {
+            [(_, true)] => line.push('/'),
+            [(_, false)] if quoted => line.push_str("\" "),
+            [(_, false)] => line.push(' '),
+            _ => (),
+        }
+    }
+}
+
+/// Finds where the last word of `line` starts, counting an open quote as its start
+fn word_start(line: &str) -> usize {
+    let mut start: usize = 0;
+    let mut quote: Option<char> = None;
+
+    for (i, c) in line.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (None, '"' | '\'') => quote = Some(c),
+            (None, c) if c.is_whitespace() => start = i + c.len_utf8(),
+            _ => (),
+        }
+    }
+
+    start
+}
+
+/// Lists the entries of `dir` starting with `partial`, and whether each is a directory
+fn entries(dir: &Path, partial: &str) -> Vec<(String, bool)> {
+    let mut entries: Vec<(String, bool)> = dir
+        .read_dir()
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let name: String = entry.file_name().to_string_lossy().to_string();
+            name.starts_with(partial)
+                .then(|| (name, entry.path().is_dir()))
+        })
+        .collect();
+
+    entries.sort();
+    entries
+}
+
+/// Returns the longest start the names of `matches` share, `None` without matches
+fn common_start(matches: &[(String, bool)]) -> Option<String> {
+    let (first, _) = matches.first()?;
+
+    let mut common: &str = first;
+    for (name, _) in &matches[1..] {
+        while !name.starts_with(common) {
+            common = &common[..common.len() - common.chars().last()?.len_utf8()];
+        }
+    }
+
+    Some(common.to_string())
+}
+
+/// Terminal raw mode through `stty`, restored when dropped
+struct RawMode {
+    saved: String,
+}
+
+impl RawMode {
+    /// Switches the terminal to raw mode, `None` if `stty` is not available or standard input
+    /// is not a terminal
+    fn enter() -> Option<RawMode> {
+        let output: Output = Command::new("stty")
+            .arg("-g")
+            .stdin(Stdio::inherit())
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        // Dropping it restores the saved settings, should switching to raw mode fail halfway
+        let mode: RawMode = RawMode {
+            saved: String::from_utf8_lossy(&output.stdout).trim().to_string(),
+        };
+
+        let raw: ExitStatus = Command::new("stty")
+            .args(["-icanon", "-echo", "-isig", "min", "1"])
+            .stdin(Stdio::inherit())
+            .status()
+            .ok()?;
+        if !raw.success() {
+            return None;
+        }
+
+        Some(mode)
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        let _ = Command::new("stty")
+            .arg(&self.saved)
+            .stdin(Stdio::inherit())
+            .status();
+    }
+}
@@ -0,0 +1,326 @@
+use crate::error::{Error, Kind};
+use crate::util::shell_quote;
+use crate::{pass, throw};
+use std::path::{Path, PathBuf};
+
+/// Name proton-call is listed under in Steam's compatibility tool menu
+static TOOL_NAME: &str = "proton-call";
+
+/// A Steam library's `steamapps` directory
+#[derive(Debug, Clone)]
+pub(crate) struct Library {
+    steamapps: PathBuf,
+}
+
+impl Library {
+    #[must_use]
+    /// Finds the library `program` is installed in, from a path like `steamapps/common/<game>/...`
+    pub(crate) fn containing(program: &Path) -> Option<Library> {
+        let common: &Path = program
+            .ancestors()
+            .find(|p| p.ends_with("steamapps/common"))?;
+
+        Some(Library {
+            steamapps: common.parent()?.to_path_buf(),
+        })
+    }
+
+    #[must_use]
+    /// Lists the `steamapps` directory of every library known to the Steam install at `steam`
+    pub(crate) fn all(steam: &Path) -> Vec<Library> {
+        let mut libraries: Vec<Library> = Vec::new();
+
+        for vdf in ["steamapps/libraryfolders.vdf", "config/libraryfolders.vdf"] {
+            let text: String = match std::fs::read_to_string(steam.join(vdf)) {
+                Ok(t) => t,
+                Err(_) => continue,
+            };
+
+            for path in vdf_values(&text, "path") {
+                let steamapps: PathBuf = PathBuf::from(path).join("steamapps");
+                if steamapps.is_dir() && !libraries.iter().any(|l| l.steamapps == steamapps) {
+                    libraries.push(Library { steamapps });
+                }
+            }
+        }
+
+        libraries
+    }
+
+    #[must_use]
+    /// Returns the path to the `steamapps` directory
+    pub(crate) fn steamapps(&self) -> &Path {
+        &self.steamapps
+    }
+
+    #[must_use]
+    /// Finds the app id of `program` from the app manifest whose `installdir` holds it
+    pub(crate) fn appid(&self, program: &Path) -> Option<u32> {
+        let common: PathBuf = self.steamapps.join("common");
+        let installdir: String = program
+            .strip_prefix(&common)
+            .ok()?
+            .components()
+            .next()?
+            .as_os_str()
+            .to_string_lossy()
+            .to_string();
+
+        for entry in self.steamapps.read_dir().ok()?.flatten() {
+            let path: PathBuf = entry.path();
+            let manifest: bool = entry
+                .file_name()
+                .to_string_lossy()
+                .starts_with("appmanifest_")
+                && matches!(path.extension(), Some(e) if e == "acf");
+            if !manifest {
+                continue;
+            }
+
+            let text: String = match std::fs::read_to_string(&path) {
+                Ok(t) => t,
+                Err(_) => continue,
+            };
+
+            if vdf_values(&text, "installdir").any(|d| d == installdir) {
+                return vdf_values(&text, "appid").next()?.parse().ok();
+            }
+        }
+
+        None
+    }
+
+    #[must_use]
+    /// Returns the shader cache directory Steam keeps for `appid` in this library
+    pub(crate) fn shader_cache(&self, appid: u32) -> PathBuf {
+        self.steamapps.join("shadercache").join(appid.to_string())
+    }
+}
+
+/// Reads every `"key"  "value"` pair with the given key from a Valve `KeyValues` file
+pub(crate) fn vdf_values<'a>(text: &'a str, key: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+    text.lines().filter_map(move |line| {
+        let mut parts = line.split('"').filter(|p| !p.trim().is_empty());
+
+        if parts.next()?.eq_ignore_ascii_case(key) {
+            parts.next()
+        } else {
+            None
+        }
+    })
+}
+
+/// Finds the app id of a program installed in a Steam library
+pub(crate) fn library_appid(program: &Path) -> Option<u32> {
+    let program: PathBuf = program.canonicalize().ok()?;
+    Library::containing(&program)?.appid(&program)
+}
+
+/// Generates a stable app id for a program Steam does not know, like Steam does for shortcuts
+///
+/// The id is an FNV-1a hash of the program's path with the high bit set, keeping it out of
+/// the range of real app ids.
+pub(crate) fn synthetic_appid(program: &Path) -> u32 {
+    let program: PathBuf = program
+        .canonicalize()
+        .unwrap_or_else(|_| program.to_path_buf());
+
+    let hash: u32 = program
+        .to_string_lossy()
+        .bytes()
+        .fold(0x811c_9dc5, |hash: u32, b| {
+            (hash ^ u32::from(b)).wrapping_mul(0x0100_0193)
+        });
+
+    hash | 0x8000_0000
+}
+
+/// Environment Steam sets for games launched from a library, so pressure-vessel mounts it
+pub(crate) fn library_env(program: &Path, steam: &Path) -> Vec<(&'static str, String)> {
+    let program: PathBuf = program
+        .canonicalize()
+        .unwrap_or_else(|_| program.to_path_buf());
+
+    let library: Library = match Library::containing(&program) {
+        Some(l) => l,
+        None => return Vec::new(),
+    };
+
+    let mut paths: Vec<String> = vec![library.steamapps().to_string_lossy().to_string()];
+    for other in Library::all(steam) {
+        let other: String = other.steamapps().to_string_lossy().to_string();
+        if !paths.contains(&other) {
+            paths.push(other);
+        }
+    }
+
+    let mut env: Vec<(&'static str, String)> =
+        vec![("STEAM_COMPAT_LIBRARY_PATHS", paths.join(":"))];
+
+    if let Some(appid) = library.appid(&program) {
+        env.push((
+            "STEAM_COMPAT_SHADER_PATH",
+            library.shader_cache(appid).to_string_lossy().to_string(),
+        ));
+    }
+
+    env
+}
+
+/// Rewrites `LD_PRELOAD` to load the Steam overlay from the Steam install at `steam`, or strip it
+pub(crate) fn overlay_preload(current: &str, steam: &Path, enable: bool) -> String {
+    let mut preload: Vec<String> = current
+        .split([':', ' '])
+        .filter(|lib| !lib.is_empty() && !lib.ends_with("gameoverlayrenderer.so"))
+        .map(str::to_string)
+        .collect();
+
+    if enable {
+        for arch in ["ubuntu12_32", "ubuntu12_64"] {
+            let lib: PathBuf = steam.join(arch).join("gameoverlayrenderer.so");
+            if lib.is_file() {
+                preload.push(lib.to_string_lossy().to_string());
+            }
+        }
+    }
+
+    preload.join(":")
+}
+
+/// A Steam compatibility tool which routes Steam games through proton-call
+#[derive(Debug, Clone)]
+pub struct CompatTool {
+    dir: PathBuf,
+    caller: PathBuf,
+    proton: Option<String>,
+}
+
+impl CompatTool {
+    #[must_use]
+    /// Creates a new `CompatTool` in the `compatibilitytools.d` directory of the Steam install at
+    /// `steam`, running the proton-call executable at `caller`
+    pub fn new(steam: &Path, caller: PathBuf) -> CompatTool {
+        CompatTool {
+            dir: steam.join("compatibilitytools.d").join(TOOL_NAME),
+            caller,
+            proton: None,
+        }
+    }
+
+    #[must_use]
+    /// Launches games with the Proton version `proton`, as given to `-p`
+    pub fn proton(mut self, proton: String) -> CompatTool {
+        self.proton = Some(proton);
+        self
+    }
+
+    #[must_use]
+    /// Returns the directory the tool is installed in
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Writes the tool's manifests and wrapper script, replacing an earlier install
+    ///
+    /// # Errors
+    ///
+    /// Will fail if the directory or any of the files can not be written
+    pub fn install(&self) -> Result<(), Error> {
+        use std::os::unix::fs::PermissionsExt;
+
+        if let Err(e) = std::fs::create_dir_all(&self.dir) {
+            throw!(Kind::ToolInstall, "{}: {}", self.dir.to_string_lossy(), e);
+        }
+
+        let files: [(&str, String); 3] = [
+            ("compatibilitytool.vdf", CompatTool::compatibilitytool()),
+            ("toolmanifest.vdf", CompatTool::toolmanifest()),
+            (TOOL_NAME, self.script()),
+        ];
+
+        for (name, text) in files {
+            let path: PathBuf = self.dir.join(name);
+
+            if let Err(e) = std::fs::write(&path, text) {
+                throw!(Kind::ToolInstall, "{}: {}", path.to_string_lossy(), e);
+            }
+        }
+
+        let script: PathBuf = self.dir.join(TOOL_NAME);
+        let permissions = std::fs::Permissions::from_mode(0o755);
+        if let Err(e) = std::fs::set_permissions(&script, permissions) {
+            throw!(Kind::ToolInstall, "{}: {}", script.to_string_lossy(), e);
+        }
+
+        pass!()
+    }
+
+    /// Lists the tool in Steam's compatibility tool menu, for Windows games on Linux
+    fn compatibilitytool() -> String {
+        format!(
+            "\"compatibilitytools\"
+{{
+  \"compat_tools\"
+  {{
+    \"{name}\"
+    {{
+      \"install_path\" \".\"
+      \"display_name\" \"{name}\"
+      \"from_oslist\" \"windows\"
+      \"to_oslist\" \"linux\"
+    }}
+  }}
+}}
+",
+            name = TOOL_NAME
+        )
+    }
+
+    /// Tells Steam to run the wrapper script with the verb of each launch
+    fn toolmanifest() -> String {
+        format!(
+            "\"manifest\"
+{{
+  \"version\" \"2\"
+  \"commandline\" \"/{} %verb%\"
+}}
+",
+            TOOL_NAME
+        )
+    }
+
+    /// Hands the game Steam launches to proton-call, ignoring verbs which only query paths
+    ///
+    /// Launches use the per-app prefix Steam names in `STEAM_COMPAT_DATA_PATH`, under
+    /// `steamapps/compatdata`, so each game keeps the prefix Steam would give it.
+    fn script(&self) -> String {
+        let proton: String = match &self.proton {
+            Some(version) => format!(" -p {}", shell_quote(version)),
+            None => String::new(),
+        };
+
+        format!(
+            "#!/bin/sh
+# Written by `proton-call steam install-tool`, Steam runs this as `{name} VERB EXE [ARGS]...`
+verb=\"$1\"
+shift
+
+case \"$verb\" in
+    run|waitforexitandrun) ;;
+    *) exit 0 ;;
+esac
+
+if [ -n \"$STEAM_COMPAT_DATA_PATH\" ]; then
+    data=\"$(dirname \"$STEAM_COMPAT_DATA_PATH\")\"
+    app=\"$(basename \"$STEAM_COMPAT_DATA_PATH\")\"
+    exec {caller} --data \"$data\"{proton} -P \"$app\" -r \"$@\"
+fi
+
+exec {caller}{proton} -r \"$@\"
+",
+            name = TOOL_NAME,
+            caller = shell_quote(&self.caller.to_string_lossy()),
+            proton = proton
+        )
+    }
+}
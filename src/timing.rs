@@ -0,0 +1,114 @@
+use std::fmt::{Display, Formatter};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// When a launched program first wrote output, shared with the threads forwarding it
+pub(crate) type FirstOutput = Arc<Mutex<Option<Instant>>>;
+
+/// How long a launch took to reach each of its milestones
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Timing {
+    spawn: Duration,
+    first_output: Option<Duration>,
+    total: Duration,
+    limited: bool,
+}
+
+impl Timing {
+    #[must_use]
+    /// Measures a launch started at `start`, spawned at `spawned` and exited at `exited`
+    pub(crate) fn new(
+        start: Instant,
+        spawned: Instant,
+        first_output: &FirstOutput,
+        exited: Instant,
+    ) -> Timing {
+        let first_output: Option<Instant> = first_output.lock().ok().and_then(|f| *f);
+
+        Timing {
+            spawn: spawned.duration_since(start),
+            first_output: first_output.map(|f| f.duration_since(start)),
+            total: exited.duration_since(start),
+            limited: false,
+        }
+    }
+
+    #[must_use]
+    /// Marks the launch as stopped by its time limit
+    pub(crate) fn limited(mut self) -> Timing {
+        self.limited = true;
+        self
+    }
+
+    #[must_use]
+    /// Moves the end of a launch started at `start` to `end`, for waiting on after the program
+    pub(crate) fn until(mut self, start: Instant, end: Instant) -> Timing {
+        self.total = end.duration_since(start);
+        self
+    }
+
+    #[must_use]
+    /// Returns how long it took to start the program, from preparing the launch to spawning it
+    pub fn spawn(&self) -> Duration {
+        self.spawn
+    }
+
+    #[must_use]
+    /// Returns how long it took the program to write its first output, if its output was watched
+    pub fn first_output(&self) -> Option<Duration> {
+        self.first_output
+    }
+
+    #[must_use]
+    /// Returns how long the launch took from preparing it to the program exiting
+    pub fn total(&self) -> Duration {
+        self.total
+    }
+
+    #[must_use]
+    /// Tells whether the launch was stopped by its time limit, as precaching is
+    pub fn time_limit_reached(&self) -> bool {
+        self.limited
+    }
+}
+
+impl Display for Timing {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Time to spawn:        {:.3}s", self.spawn.as_secs_f64())?;
+
+        match self.first_output {
+            Some(first) => writeln!(f, "Time to first output: {:.3}s", first.as_secs_f64())?,
+            None => writeln!(f, "Time to first output: none")?,
+        }
+
+        write!(f, "Total runtime:        {:.3}s", self.total.as_secs_f64())
+    }
+}
+
+/// Forwards `stream` to `sink` on a thread, noting when output first arrives and passing each
+/// line to `inspect`
+pub(crate) fn forward<R, W>(
+    stream: R,
+    mut sink: W,
+    first: FirstOutput,
+    inspect: impl Fn(&str) + Send + 'static,
+) where
+    R: Read + Send + 'static,
+    W: Write + Send + 'static,
+{
+    std::thread::spawn(move || {
+        let mut reader: BufReader<R> = BufReader::new(stream);
+        let mut line: Vec<u8> = Vec::new();
+
+        while matches!(reader.read_until(b'\n', &mut line), Ok(n) if n > 0) {
+            if let Ok(mut first) = first.lock() {
+                first.get_or_insert_with(Instant::now);
+            }
+
+            let _ = sink.write_all(&line).and_then(|()| sink.flush());
+            inspect(&String::from_utf8_lossy(&line));
+            line.clear();
+        }
+    });
+}
@@ -0,0 +1,84 @@
+use crate::error::{Error, Kind};
+use crate::util::run;
+use crate::{throw, Prefix, ProtonEvent};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Tricks type to run winetricks verbs in a prefix
+#[derive(Debug, Clone)]
+pub struct Tricks {
+    verbs: Vec<String>,
+}
+
+impl Tricks {
+    /// Creates a new instance of `Tricks` for the winetricks `verbs`
+    ///
+    /// # Errors
+    ///
+    /// Will fail if no verbs are given
+    pub fn new(verbs: Vec<String>) -> Result<Tricks, Error> {
+        if verbs.is_empty() {
+            throw!(Kind::ArgumentMissing, "winetricks verbs");
+        }
+
+        Ok(Tricks { verbs })
+    }
+
+    /// Runs the verbs through protontricks, which finds the prefix of the Steam app `appid` itself
+    ///
+    /// # Errors
+    ///
+    /// Will fail if protontricks can not be run or fails
+    pub fn via_protontricks(&self, appid: u32) -> Result<(), Error> {
+        ProtonEvent::Protontricks {
+            appid,
+            verbs: self.verbs.clone(),
+        }
+        .emit();
+
+        run(
+            Command::new("protontricks")
+                .arg(appid.to_string())
+                .args(&self.verbs),
+            Kind::Tricks,
+        )
+    }
+
+    /// Runs the verbs with winetricks in `prefix`, using the Wine bundled with the Proton at `proton`
+    ///
+    /// # Errors
+    ///
+    /// Will fail if...
+    /// * The Proton build has no Wine binaries
+    /// * winetricks can not be run or fails
+    pub fn in_prefix(&self, prefix: &Prefix, proton: &Path) -> Result<(), Error> {
+        // Newer Proton builds keep Wine in `files`, older ones in `dist`
+        let bin: PathBuf = match ["files/bin", "dist/bin"]
+            .iter()
+            .map(|b| proton.join(b))
+            .find(|b| b.join("wine").is_file())
+        {
+            Some(b) => b,
+            None => throw!(
+                Kind::ProtonMissing,
+                "no Wine in {}",
+                proton.to_string_lossy()
+            ),
+        };
+
+        ProtonEvent::Winetricks {
+            verbs: self.verbs.clone(),
+            prefix: prefix.name().to_string(),
+        }
+        .emit();
+
+        run(
+            Command::new("winetricks")
+                .args(&self.verbs)
+                .env("WINEPREFIX", prefix.pfx())
+                .env("WINE", bin.join("wine"))
+                .env("WINESERVER", bin.join("wineserver")),
+            Kind::Tricks,
+        )
+    }
+}
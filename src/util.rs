@@ -0,0 +1,324 @@
+use crate::error::{Error, Kind};
+use crate::{pass, throw, Arch};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Recursively sums the size of every file below `path`, without following symlinks
+pub(crate) fn dir_size(path: &Path) -> u64 {
+    match path.symlink_metadata() {
+        Ok(meta) if meta.is_dir() => (),
+        Ok(meta) => return meta.len(),
+        Err(_) => return 0,
+    }
+
+    let mut size: u64 = 0;
+
+    if let Ok(rd) = path.read_dir() {
+        for entry in rd.flatten() {
+            size += dir_size(&entry.path());
+        }
+    }
+
+    size
+}
+
+#[must_use]
+/// Formats a byte count into a human readable size
+pub(crate) fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+
+    #[allow(clippy::cast_precision_loss)]
+    let mut size: f64 = bytes as f64;
+    let mut unit: usize = 0;
+
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+#[must_use]
+/// Formats a time as a `YYYY-MM-DD HH:MM` UTC date
+pub(crate) fn format_time(time: SystemTime) -> String {
+    match civil(time) {
+        Some([year, month, day, hour, min, _]) => {
+            format!("{:04}-{:02}-{:02} {:02}:{:02}", year, month, day, hour, min)
+        }
+        None => String::from("unknown"),
+    }
+}
+
+#[must_use]
+/// Formats a time as a sortable `YYYYMMDD-HHMMSS` UTC stamp, for file names
+pub(crate) fn timestamp(time: SystemTime) -> String {
+    match civil(time) {
+        Some([year, month, day, hour, min, sec]) => format!(
+            "{:04}{:02}{:02}-{:02}{:02}{:02}",
+            year, month, day, hour, min, sec
+        ),
+        None => String::from("unknown"),
+    }
+}
+
+/// Splits a time into UTC year, month, day, hour, minute and second
+fn civil(time: SystemTime) -> Option<[i64; 6]> {
+    let secs: i64 = i64::try_from(time.duration_since(UNIX_EPOCH).ok()?.as_secs()).ok()?;
+
+    // Civil from days, see http://howardhinnant.github.io/date_algorithms.html
+    let days: i64 = secs / 86400 + 719_468;
+    let era: i64 = days.div_euclid(146_097);
+    let doe: i64 = days.rem_euclid(146_097);
+    let yoe: i64 = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy: i64 = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp: i64 = (5 * doy + 2) / 153;
+    let day: i64 = doy - (153 * mp + 2) / 5 + 1;
+    let month: i64 = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year: i64 = yoe + era * 400 + i64::from(month <= 2);
+
+    let rem: i64 = secs % 86400;
+    Some([year, month, day, rem / 3600, (rem % 3600) / 60, rem % 60])
+}
+
+/// Finds proton-call's cache directory, creating it if needed
+///
+/// # Errors
+///
+/// Will fail if `XDG_CACHE_HOME` and `HOME` are missing or the directory can not be created
+pub(crate) fn cache_dir(name: &str) -> Result<PathBuf, Error> {
+    xdg_dir("XDG_CACHE_HOME", ".cache", name)
+}
+
+/// Finds proton-call's data directory, creating it if needed
+///
+/// # Errors
+///
+/// Will fail if `XDG_DATA_HOME` and `HOME` are missing or the directory can not be created
+pub(crate) fn data_dir(name: &str) -> Result<PathBuf, Error> {
+    xdg_dir("XDG_DATA_HOME", ".local/share", name)
+}
+
+/// Finds proton-call's state directory, creating it if needed
+///
+/// # Errors
+///
+/// Will fail if `XDG_STATE_HOME` and `HOME` are missing or the directory can not be created
+pub(crate) fn state_dir(name: &str) -> Result<PathBuf, Error> {
+    xdg_dir("XDG_STATE_HOME", ".local/state", name)
+}
+
+/// Finds `name` in proton-call's directory under an XDG base directory, creating it if needed
+fn xdg_dir(var: &str, fallback: &str, name: &str) -> Result<PathBuf, Error> {
+    let base: PathBuf = match xdg_base(var, fallback) {
+        Some(b) => b,
+        None => throw!(Kind::Environment, "{} / HOME missing", var),
+    };
+
+    let dir: PathBuf = base.join("proton-call").join(name);
+
+    if let Err(e) = std::fs::create_dir_all(&dir) {
+        throw!(Kind::Environment, "{}: {}", dir.to_string_lossy(), e);
+    }
+
+    pass!(dir)
+}
+
+#[must_use]
+/// Finds an XDG base directory from `var`, or `fallback` in `HOME`
+pub(crate) fn xdg_base(var: &str, fallback: &str) -> Option<PathBuf> {
+    use std::env;
+
+    if let Ok(val) = env::var(var) {
+        Some(PathBuf::from(val))
+    } else if let Ok(val) = env::var("HOME") {
+        Some(PathBuf::from(format!("{}/{}", val, fallback)))
+    } else {
+        None
+    }
+}
+
+#[must_use]
+/// Reads the soft limit on open files of this process, `None` if unlimited or unknown
+pub(crate) fn open_file_limit() -> Option<u64> {
+    let limits: String = std::fs::read_to_string("/proc/self/limits").ok()?;
+    let line: &str = limits.lines().find(|l| l.starts_with("Max open files"))?;
+
+    line.trim_start_matches("Max open files")
+        .split_whitespace()
+        .next()?
+        .parse()
+        .ok()
+}
+
+/// Downloads `url` to `dest` with curl, skipping the download if `dest` already exists
+///
+/// # Errors
+///
+/// Will fail if curl can not be run or the download fails
+pub(crate) fn fetch(url: &str, dest: &Path) -> Result<(), Error> {
+    if dest.exists() {
+        return pass!();
+    }
+
+    let part: PathBuf = dest.with_extension("part");
+
+    let status = match Command::new("curl")
+        .args(["-fsSL", "-o"])
+        .arg(&part)
+        .arg(url)
+        .status()
+    {
+        Ok(s) => s,
+        Err(e) => throw!(Kind::Download, "failed to run curl: {}", e),
+    };
+
+    if !status.success() {
+        let _ = std::fs::remove_file(&part);
+        throw!(Kind::Download, "{}", url);
+    }
+
+    if let Err(e) = std::fs::rename(&part, dest) {
+        throw!(Kind::Download, "{}: {}", dest.to_string_lossy(), e);
+    }
+
+    pass!()
+}
+
+#[must_use]
+/// Converts a host path into a path on Wine's `Z:` drive
+pub fn windows_path(path: &Path) -> String {
+    format!("Z:{}", path.to_string_lossy().replace('/', "\\"))
+}
+
+/// Tells whether `name` is exactly one normal path component, so joining it to a directory stays
+/// inside it
+pub(crate) fn is_plain_name(name: &str) -> bool {
+    let mut components: std::path::Components = Path::new(name).components();
+
+    matches!(
+        (components.next(), components.next()),
+        (Some(std::path::Component::Normal(_)), None)
+    )
+}
+
+/// Quotes an argument for the shell if it contains anything special
+pub(crate) fn shell_quote(s: &str) -> String {
+    if !s.is_empty()
+        && s.chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./=:,+@%".contains(c))
+    {
+        s.to_string()
+    } else {
+        format!("'{}'", s.replace('\'', "'\\''"))
+    }
+}
+
+#[must_use]
+/// Reads the architecture of a Windows executable from its PE header
+pub(crate) fn pe_arch(program: &Path) -> Option<Arch> {
+    let (header, offset): (Vec<u8>, usize) = pe_header(program)?;
+
+    match header.get(offset + 4..offset + 6)? {
+        [0x4c, 0x01] => Some(Arch::Win32),
+        [0x64, 0x86] => Some(Arch::Win64),
+        _ => None,
+    }
+}
+
+/// Tells whether a Windows executable is a console program, by the subsystem in its header
+pub(crate) fn pe_console(program: &Path) -> bool {
+    // The subsystem sits at the same place in the optional header of 32 and 64-bit executables
+    pe_header(program)
+        .and_then(|(header, offset)| header.get(offset + 92..offset + 94).map(<[u8]>::to_vec))
+        .is_some_and(|subsystem| subsystem == [3, 0])
+}
+
+/// Reads the start of a Windows executable, with the offset of its PE signature
+fn pe_header(program: &Path) -> Option<(Vec<u8>, usize)> {
+    use std::io::Read;
+
+    let mut header: Vec<u8> = Vec::new();
+    std::fs::File::open(program)
+        .ok()?
+        .take(4096)
+        .read_to_end(&mut header)
+        .ok()?;
+
+    let offset: [u8; 4] = header.get(0x3c..0x40)?.try_into().ok()?;
+    let offset: usize = u32::from_le_bytes(offset) as usize;
+
+    if header.get(offset..offset + 4)? != b"PE\0\0" {
+        return None;
+    }
+
+    Some((header, offset))
+}
+
+/// Hashes the contents of a file with 64-bit FNV-1a
+pub(crate) fn file_hash(path: &Path) -> std::io::Result<u64> {
+    use std::io::Read;
+
+    let mut file: std::fs::File = std::fs::File::open(path)?;
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    let mut buffer: Vec<u8> = vec![0; 1 << 16];
+
+    loop {
+        let read: usize = match file.read(&mut buffer)? {
+            0 => return Ok(hash),
+            n => n,
+        };
+
+        for byte in &buffer[..read] {
+            hash = (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+#[must_use]
+/// Tells whether the program `name` is found in `PATH`
+pub(crate) fn in_path(name: &str) -> bool {
+    std::env::var_os("PATH")
+        .is_some_and(|path| std::env::split_paths(&path).any(|dir| dir.join(name).is_file()))
+}
+
+/// Runs a prepared command, failing with `kind` if it can not be run or exits unsuccessfully
+pub(crate) fn run(command: &mut Command, kind: Kind) -> Result<(), Error> {
+    let name: String = command.get_program().to_string_lossy().to_string();
+
+    match command.status() {
+        Ok(s) if s.success() => pass!(),
+        Ok(s) => throw!(kind, "{} exited with {}", name, s),
+        Err(e) => throw!(kind, "failed to run {}: {}", name, e),
+    }
+}
+
+/// Parses an age such as `90d`, `12w` or `36h`, plain numbers are days
+///
+/// # Errors
+///
+/// Will fail if the number or unit can not be parsed, or the age is too long to represent
+pub fn parse_age(s: &str) -> Result<Duration, Error> {
+    let (number, unit): (&str, u64) = match s.char_indices().last() {
+        Some((i, 'h')) => (&s[..i], 3600),
+        Some((i, 'd')) => (&s[..i], 86400),
+        Some((i, 'w')) => (&s[..i], 7 * 86400),
+        Some(_) => (s, 86400),
+        None => throw!(Kind::ArgumentParse, "empty age"),
+    };
+
+    match number.parse::<u64>().map(|n| n.checked_mul(unit)) {
+        Ok(Some(secs)) => pass!(Duration::from_secs(secs)),
+        Ok(None) => throw!(Kind::ArgumentParse, "age '{}' is too long", s),
+        Err(_) => throw!(
+            Kind::ArgumentParse,
+            "age '{}', expected a number of h, d or w",
+            s
+        ),
+    }
+}
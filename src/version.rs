@@ -8,8 +8,12 @@ use std::str::FromStr;
 pub enum Version {
     /// Two number version
     Mainline(u8, u8),
+    /// GE-Proton build, by its major and release numbers as in `GE-Proton9-20`
+    Ge(u8, u16),
     /// Experimental version
     Experimental,
+    /// Proton Hotfix, which Steam installs for games it is forced on
+    Hotfix,
     /// Custom version (will be replaced by Mainline if possible)
     Custom,
 }
@@ -28,18 +32,11 @@ impl Version {
     }
 
     #[must_use]
-    /// Tries parsing custom Proton path into `Version::Mainline`
+    /// Tries parsing the directory name of a custom Proton path into a `Version`
     pub fn from_custom(name: &Path) -> Version {
-        if let Some(n) = name.file_name() {
-            let name_str = n.to_string_lossy().to_string();
-            if let Some(version_str) = name_str.split(' ').last() {
-                if let Ok(version) = version_str.parse() {
-                    return version;
-                }
-            }
-        }
-
-        Version::Custom
+        name.file_name()
+            .and_then(|n| n.to_string_lossy().parse().ok())
+            .unwrap_or(Version::Custom)
     }
 }
 
@@ -47,23 +44,169 @@ impl Display for Version {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             Version::Mainline(mj, mn) => write!(f, "{}.{}", mj, mn),
+            Version::Ge(mj, release) => write!(f, "GE-Proton{}-{}", mj, release),
             Version::Experimental => write!(f, "Experimental"),
+            Version::Hotfix => write!(f, "Hotfix"),
             Version::Custom => write!(f, "Custom"),
         }
     }
 }
 
+impl serde::Serialize for Version {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
 impl FromStr for Version {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.to_ascii_lowercase() == "experimental" {
+        // Directory names such as `Proton 7.0` and `Proton - Experimental` are read whole
+        let lower: String = s.trim().to_ascii_lowercase();
+
+        let name: &str = match lower.strip_prefix("proton") {
+            Some(rest) => rest.trim_start_matches([' ', '-']),
+            None => &lower,
+        };
+
+        if name == "experimental" {
             return pass!(Version::Experimental);
         }
 
-        match s.split('.').collect::<Vec<&str>>().as_slice() {
+        if name == "hotfix" {
+            return pass!(Version::Hotfix);
+        }
+
+        // GE-Proton builds, also in the `Proton GE-Proton9-20` compat directories named after them
+        if let Some(number) = name.strip_prefix("ge-proton") {
+            return match number.split_once('-') {
+                Some((maj, release)) => pass!(Version::Ge(maj.parse()?, release.parse()?)),
+                None => throw!(Kind::VersionParse, "'{}'", s),
+            };
+        }
+
+        match name.split('.').collect::<Vec<&str>>().as_slice() {
             [maj, min] => pass!(Version::new(maj.parse()?, min.parse()?)),
             _ => throw!(Kind::VersionParse, "'{}'", s),
         }
     }
 }
+
+/// Names `-p` accepts in place of a version number, resolved against what is installed so
+/// scripts keep working as versions come and go
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Keyword {
+    /// The newest numbered Proton version in `common`
+    Latest,
+    /// Proton Experimental
+    Experimental,
+    /// Proton Hotfix, which Steam installs for games it is forced on
+    Hotfix,
+    /// The newest GE-Proton build in Steam's `compatibilitytools.d` directory
+    GeLatest,
+}
+
+impl Display for Keyword {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Keyword::Latest => write!(f, "latest"),
+            Keyword::Experimental => write!(f, "experimental"),
+            Keyword::Hotfix => write!(f, "hotfix"),
+            Keyword::GeLatest => write!(f, "ge-latest"),
+        }
+    }
+}
+
+impl FromStr for Keyword {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "latest" => pass!(Keyword::Latest),
+            "experimental" => pass!(Keyword::Experimental),
+            "hotfix" => pass!(Keyword::Hotfix),
+            "ge-latest" => pass!(Keyword::GeLatest),
+            _ => throw!(
+                Kind::VersionParse,
+                "'{}', expected latest, experimental, hotfix or ge-latest",
+                s
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_version_numbers() {
+        assert_eq!("7.0".parse::<Version>().unwrap(), Version::Mainline(7, 0));
+        assert_eq!(
+            "Proton 7.0".parse::<Version>().unwrap(),
+            Version::Mainline(7, 0)
+        );
+        assert_eq!(
+            "proton 9.0".parse::<Version>().unwrap(),
+            Version::Mainline(9, 0)
+        );
+    }
+
+    #[test]
+    fn parses_named_versions() {
+        assert_eq!(
+            "Proton - Experimental".parse::<Version>().unwrap(),
+            Version::Experimental
+        );
+        assert_eq!(
+            "Proton Experimental".parse::<Version>().unwrap(),
+            Version::Experimental
+        );
+        assert_eq!(
+            "experimental".parse::<Version>().unwrap(),
+            Version::Experimental
+        );
+        assert_eq!("Proton Hotfix".parse::<Version>().unwrap(), Version::Hotfix);
+    }
+
+    #[test]
+    fn parses_ge_builds() {
+        assert_eq!(
+            "GE-Proton9-5".parse::<Version>().unwrap(),
+            Version::Ge(9, 5)
+        );
+        assert_eq!(
+            "Proton GE-Proton9-20".parse::<Version>().unwrap(),
+            Version::Ge(9, 20)
+        );
+    }
+
+    #[test]
+    fn rejects_junk() {
+        for junk in [
+            "",
+            "Proton",
+            "banana",
+            "7",
+            "7.0.1",
+            "7.x",
+            "GE-Proton9",
+            "GE-Proton-5",
+        ] {
+            assert!(junk.parse::<Version>().is_err(), "'{}' parsed", junk);
+        }
+    }
+
+    #[test]
+    fn round_trips_through_display() {
+        for version in [
+            Version::Mainline(8, 0),
+            Version::Ge(9, 5),
+            Version::Experimental,
+            Version::Hotfix,
+        ] {
+            assert_eq!(version.to_string().parse::<Version>().unwrap(), version);
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use crate::error::{Error, Kind};
+use crate::util::run;
+use crate::{interrupt, ProtonEvent};
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+
+/// How often the processes of a launch are checked while waiting for them
+pub(crate) const POLL: Duration = Duration::from_millis(500);
+
+/// What a launch waits for before it is reported as exited
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum Wait {
+    /// The program launched, the default
+    #[default]
+    Program,
+    /// Every process in the prefix, for launchers which start the game and exit
+    Prefix,
+    /// Every process the program started, by the process group it is launched in
+    Children,
+}
+
+/// Waits until no process runs in the prefix at `pfx`, through the prefix's `wineserver`, which
+/// exits once its last client does
+///
+/// # Errors
+///
+/// Will fail if `wineserver` can not be run
+pub(crate) fn prefix_idle(wineserver: &Path, pfx: &Path) -> Result<(), Error> {
+    ProtonEvent::WaitingForPrefix.emit();
+
+    run(
+        Command::new(wineserver).arg("-w").env("WINEPREFIX", pfx),
+        Kind::ProtonWait,
+    )
+}
+
+/// Waits until no process is left in the process group `group`, skipping zombies which only wait
+/// to be reaped, or until proton-call is interrupted
+pub(crate) fn group_idle(group: u32) {
+    let mut announced: bool = false;
+
+    while in_group(group) && !interrupt::received() {
+        if !announced {
+            ProtonEvent::WaitingForChildren.emit();
+            announced = true;
+        }
+
+        std::thread::sleep(POLL);
+    }
+}
+
+/// Tells whether any live process is in the process group `group`, from `/proc`
+fn in_group(group: u32) -> bool {
+    let Ok(entries) = std::fs::read_dir("/proc") else {
+        return false;
+    };
+
+    entries.flatten().any(|entry| {
+        let Ok(stat) = std::fs::read_to_string(entry.path().join("stat")) else {
+            return false;
+        };
+
+        // The name in parentheses may hold spaces, the state, parent and group follow it
+        let fields: Vec<&str> = match stat.rfind(')') {
+            Some(i) => stat[i + 1..].split_whitespace().take(3).collect(),
+            None => return false,
+        };
+
+        matches!(fields.as_slice(), [state, _, pgrp] if *state != "Z" && pgrp.parse() == Ok(group))
+    })
+}
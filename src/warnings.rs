@@ -0,0 +1,107 @@
+use crate::locale::tr;
+use lliw::Fg::LightYellow as Yellow;
+use lliw::Reset;
+use std::fmt::{Display, Formatter};
+use std::sync::Mutex;
+
+/// Warnings collected so far, until taken with `Warnings::take`
+static COLLECTED: Mutex<Vec<Warning>> = Mutex::new(Vec::new());
+
+/// A non-fatal problem, and the part of proton-call which met it
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Warning {
+    origin: &'static str,
+    message: String,
+}
+
+impl Warning {
+    #[must_use]
+    /// Returns the part of proton-call which met the problem, such as `config` or `index`
+    pub fn origin(&self) -> &str {
+        self.origin
+    }
+
+    #[must_use]
+    /// Returns what the problem is
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for Warning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}{}:{} {}: {}",
+            Yellow,
+            tr("warning"),
+            Reset,
+            self.origin,
+            self.message
+        )
+    }
+}
+
+/// Non-fatal problems met by `Config`, `Index` and `Proton`, collected to be shown together
+/// instead of interleaved with other output
+#[derive(Debug, Clone, Default)]
+pub struct Warnings {
+    warnings: Vec<Warning>,
+}
+
+impl Warnings {
+    /// Adds a warning met by `origin` to the collected warnings, once
+    pub fn push(origin: &'static str, message: String) {
+        let warning: Warning = Warning { origin, message };
+
+        if let Ok(mut collected) = COLLECTED.lock() {
+            if !collected.contains(&warning) {
+                collected.push(warning);
+            }
+        }
+    }
+
+    /// Withdraws a warning met by `origin` which no longer holds, such as one about a default a
+    /// flag later overrides
+    pub(crate) fn retract(origin: &str, message: &str) {
+        if let Ok(mut collected) = COLLECTED.lock() {
+            collected.retain(|w| w.origin != origin || w.message != message);
+        }
+    }
+
+    #[must_use]
+    /// Takes every warning collected so far
+    pub fn take() -> Warnings {
+        let warnings: Vec<Warning> = COLLECTED
+            .lock()
+            .map(|mut c| std::mem::take(&mut *c))
+            .unwrap_or_default();
+
+        Warnings { warnings }
+    }
+
+    /// Iterates over the warnings, in the order they were met
+    pub fn iter(&self) -> impl Iterator<Item = &Warning> {
+        self.warnings.iter()
+    }
+
+    #[must_use]
+    /// Returns true if there are no warnings
+    pub fn is_empty(&self) -> bool {
+        self.warnings.is_empty()
+    }
+}
+
+impl Display for Warnings {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, warning) in self.warnings.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+
+            write!(f, "{}", warning)?;
+        }
+
+        Ok(())
+    }
+}